@@ -0,0 +1,44 @@
+use derive_more::Display;
+
+use crate::lexer::Span;
+
+#[derive(thiserror::Error, Display, Debug, Clone, PartialEq, Eq)]
+#[display("{span}: {kind}")]
+pub struct Error {
+    pub span: Span,
+    pub kind: ErrorKind,
+}
+
+#[derive(thiserror::Error, Display, Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    #[display("unexpected character {_0:?}")]
+    UnexpectedChar(char),
+    #[display("unterminated string")]
+    UnterminatedString,
+    #[display("invalid escape sequence")]
+    InvalidEscape,
+    #[display("invalid char literal")]
+    InvalidChar,
+    #[display("unexpected end of input")]
+    UnexpectedEnd,
+    #[display("expected {expected}, found {found}")]
+    Expected { expected: String, found: String },
+    #[display("invalid number {_0:?}")]
+    InvalidNumber(String),
+    #[display("invalid version {_0:?}")]
+    InvalidVersion(String),
+    #[display("unknown variant {_0:?}")]
+    UnknownVariant(String),
+    #[display("unknown directive {_0:?}")]
+    UnknownDirective(String),
+    #[display("unknown label {_0:?}")]
+    UnknownLabel(String),
+    #[display("duplicate label {_0:?}")]
+    DuplicateLabel(String),
+}
+
+impl Error {
+    pub fn new(span: Span, kind: ErrorKind) -> Self {
+        Self { span, kind }
+    }
+}
@@ -0,0 +1,167 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use derive_more::Display;
+
+use crate::error::{Error, ErrorKind};
+
+/// 1-based position in the source
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+#[display("{line}:{column}")]
+pub struct Span {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Clone, Debug, Display, PartialEq)]
+pub enum Token {
+    /// Directives, labels, names of variants and numbers
+    #[display("{_0:?}")]
+    Word(String),
+    #[display("string {_0:?}")]
+    String(String),
+    #[display("char {_0:?}")]
+    Char(char),
+    #[display("'->'")]
+    Arrow,
+    #[display("{_0:?}")]
+    Punct(char),
+}
+
+const PUNCTS: &[char] = &['[', ']', '(', ')', '{', '}', ',', ':', '%', '@'];
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '+')
+}
+
+struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    line: u32,
+    column: u32,
+}
+
+impl Lexer<'_> {
+    fn span(&self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_, c) = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.chars.clone();
+        chars.next();
+        chars.next().map(|(_, c)| c)
+    }
+
+    /// Reads the content of a string or char literal after the opening quote
+    fn quoted(&mut self, start: Span, quote: char) -> Result<String, Error> {
+        let mut s = String::new();
+        loop {
+            let span = self.span();
+            match self.bump() {
+                None => return Err(Error::new(start, ErrorKind::UnterminatedString)),
+                Some(c) if c == quote => return Ok(s),
+                Some('\\') => s.push(self.escape(span)?),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn escape(&mut self, span: Span) -> Result<char, Error> {
+        let err = || Error::new(span, ErrorKind::InvalidEscape);
+        match self.bump().ok_or_else(err)? {
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            '0' => Ok('\0'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '\'' => Ok('\''),
+            'u' => {
+                if self.bump() != Some('{') {
+                    return Err(err());
+                }
+                let mut hex = String::new();
+                loop {
+                    match self.bump().ok_or_else(err)? {
+                        '}' => break,
+                        c => hex.push(c),
+                    }
+                }
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(err)
+            }
+            _ => Err(err()),
+        }
+    }
+}
+
+/// Splits the source into tokens, skipping whitespaces and `//` comments
+pub fn lex(source: &str) -> Result<Vec<(Token, Span)>, Error> {
+    let mut lexer = Lexer {
+        chars: source.char_indices().peekable(),
+        line: 1,
+        column: 1,
+    };
+    let mut tokens = Vec::new();
+
+    while let Some(c) = lexer.peek() {
+        let span = lexer.span();
+        if c.is_whitespace() {
+            lexer.bump();
+        } else if c == '/' && lexer.peek_second() == Some('/') {
+            while lexer.peek().is_some_and(|c| c != '\n') {
+                lexer.bump();
+            }
+        } else if c == '-' && lexer.peek_second() == Some('>') {
+            lexer.bump();
+            lexer.bump();
+            tokens.push((Token::Arrow, span));
+        } else if c == '"' {
+            lexer.bump();
+            let s = lexer.quoted(span, '"')?;
+            tokens.push((Token::String(s), span));
+        } else if c == '\'' {
+            lexer.bump();
+            let s = lexer.quoted(span, '\'')?;
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => tokens.push((Token::Char(c), span)),
+                _ => return Err(Error::new(span, ErrorKind::InvalidChar)),
+            }
+        } else if PUNCTS.contains(&c) {
+            lexer.bump();
+            tokens.push((Token::Punct(c), span));
+        } else if is_word_char(c) {
+            let mut word = String::new();
+            while let Some(c) = lexer.peek()
+                && is_word_char(c)
+            {
+                word.push(c);
+                lexer.bump();
+            }
+            tokens.push((Token::Word(word), span));
+        } else {
+            return Err(Error::new(span, ErrorKind::UnexpectedChar(c)));
+        }
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,62 @@
+//! A textual format of assemblies, so that programs can be written and reviewed as text
+//!
+//! ```text
+//! .assembly "Test"
+//!
+//! .typeref "!" 0
+//! .typeref "!" 1
+//!
+//! .class Public "Test::Test"
+//!     .extends typeref[0]
+//!     .field Private [Static] "Count" typeref[1]
+//!
+//!     .method Public [Static] "Loop" (typeref[1]) -> typeref[1]
+//!         .locals [typeref[1]]
+//!         Load { addr: %0, content: Arg 0 }
+//!     loop:
+//!         Calculate U64 SubOne { target: %0 }
+//!         Jump { target: @loop, condition: IfCheckFails IsAllZero %0 }
+//!         ReturnVal { register_addr: %0 }
+//!     .end
+//! .end
+//! ```
+//!
+//! - Directives start with `.`, definitions of types and methods end with `.end`
+//! - Items are referred by their tokens such as `typeref[0]`, `typedef[1]` and `method[2]`,
+//!   which index the tables of the assembly in the order they are written
+//! - Instructions are written like the Rust enums in the ISA: `Variant`,
+//!   `Variant <inner>` or `Variant { field: <value>, .. }`, registers are written as `%<index>`
+//! - `<name>:` defines a label before the next instruction, which can be used as
+//!   `@<name>` by jump targets and the ranges of `.try`
+//! - `.try <start> <end> catch <type> <start> <end>` adds an entry to the exception table
+//!   of the method, optionally followed by `filter <type> <method>`,
+//!   `finally <start> <end>` and `fault <start> <end>`
+//! - `.line <document> <line> <column>`, `.arg_names` and `.local_names` in a method
+//!   and `.document` in the assembly are stored in the debug info
+//! - `//` starts a comment
+
+#![feature(decl_macro)]
+
+mod error;
+mod lexer;
+mod parser;
+mod printer;
+mod syntax;
+
+#[cfg(test)]
+mod tests;
+
+use binary::assembly::AssemblyBuilder;
+
+pub use error::{Error, ErrorKind};
+pub use lexer::Span;
+
+/// Parses the textual format into an assembly
+pub fn assemble(source: &str) -> Result<AssemblyBuilder, Error> {
+    parser::parse(source)
+}
+
+/// Prints an assembly in the textual format, which can be parsed back by [`assemble`]
+pub fn disassemble(assembly: &AssemblyBuilder) -> binary_core::BinaryResult<String> {
+    printer::print(assembly)
+}
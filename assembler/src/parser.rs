@@ -0,0 +1,581 @@
+use std::{collections::HashMap, fmt::Debug, range::Range};
+
+use binary::{
+    assembly::{AssemblyBuilder, AssemblyRef},
+    custom_attribute::CustomAttribute,
+    debug_info::{Document, MethodDebugInfo, SequencePoint},
+    item_token::{MethodToken, TypeToken},
+    ty::{
+        BinaryInstruction, ClassDef, ExceptionTableEntry, Field, GenericCountRequirement,
+        InterfaceDef, InterfaceImplementation, Method, MethodSpec, Parameter, StructDef, TypeDef,
+        TypeRef, TypeSpec,
+    },
+};
+use binary_core::traits::StringRef;
+use global::{
+    attrs::{
+        CallConvention, FieldAttr, MethodAttr, ParameterAttr, TypeAttr, TypeSpecificAttr,
+        Visibility,
+    },
+    enumflags2::{BitFlag, BitFlags},
+    instruction::JumpTarget,
+};
+
+use crate::{
+    error::{Error, ErrorKind},
+    lexer::{Span, Token, lex},
+    syntax::{Syntax, jump_targets_mut},
+};
+
+pub struct Parser {
+    tokens: Vec<(Token, Span)>,
+    pos: usize,
+    pub assembly: AssemblyBuilder,
+    /// Labels of the method being parsed
+    labels: HashMap<String, u64>,
+    /// Labels referred by the jump targets parsed so far, in the order they are written
+    jump_labels: Vec<Option<(String, Span)>>,
+}
+
+/// A position in the instructions of a method
+pub enum LabelRef {
+    Label(String, Span),
+    Index(u64),
+}
+
+impl Parser {
+    pub fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(x, _)| x)
+    }
+
+    fn peek_second(&self) -> Option<&Token> {
+        self.tokens.get(self.pos + 1).map(|(x, _)| x)
+    }
+
+    /// Span of the next token, or of the last one at the end
+    fn span(&self) -> Span {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(Span { line: 1, column: 1 }, |(_, x)| *x)
+    }
+
+    pub fn bump(&mut self) {
+        self.pos += 1;
+    }
+
+    pub fn unexpected(&self, expected: &str) -> Error {
+        match self.peek() {
+            Some(found) => Error::new(
+                self.span(),
+                ErrorKind::Expected {
+                    expected: expected.to_owned(),
+                    found: found.to_string(),
+                },
+            ),
+            None => Error::new(self.span(), ErrorKind::UnexpectedEnd),
+        }
+    }
+
+    pub fn eat_punct(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect_punct(&mut self, c: char) -> Result<(), Error> {
+        if self.eat_punct(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("{c:?}")))
+        }
+    }
+
+    pub fn expect_word(&mut self) -> Result<(String, Span), Error> {
+        let span = self.span();
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                let word = word.clone();
+                self.bump();
+                Ok((word, span))
+            }
+            _ => Err(self.unexpected("a word")),
+        }
+    }
+
+    pub fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Word(x)) if x == keyword) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect_keyword(&mut self, keyword: &str) -> Result<(), Error> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("{keyword:?}")))
+        }
+    }
+
+    pub fn expect_string(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Some(Token::String(s)) => {
+                let s = s.clone();
+                self.bump();
+                Ok(s)
+            }
+            _ => Err(self.unexpected("a string")),
+        }
+    }
+
+    pub fn begin_fields(&mut self) -> Result<(), Error> {
+        self.expect_punct('{')
+    }
+
+    /// Parses `<name>: <value>` and the comma after it
+    pub fn field<T: Syntax>(&mut self, name: &str) -> Result<T, Error> {
+        self.expect_keyword(name)?;
+        self.expect_punct(':')?;
+        let val = T::parse(self)?;
+        self.eat_punct(',');
+        Ok(val)
+    }
+
+    pub fn end_fields(&mut self) -> Result<(), Error> {
+        self.expect_punct('}')
+    }
+
+    /// Flags are optional and empty by default
+    fn flags<T: BitFlag + Debug>(&mut self) -> Result<BitFlags<T>, Error> {
+        if self.peek() == Some(&Token::Punct('[')) {
+            Syntax::parse(self)
+        } else {
+            Ok(BitFlags::empty())
+        }
+    }
+
+    /// Parses `@<label>` or the index of an instruction
+    pub fn label_ref(&mut self) -> Result<LabelRef, Error> {
+        if self.eat_punct('@') {
+            let (name, span) = self.expect_word()?;
+            Ok(LabelRef::Label(name, span))
+        } else {
+            Syntax::parse(self).map(LabelRef::Index)
+        }
+    }
+
+    /// Labels are resolved after the whole method is parsed,
+    /// so a placeholder is returned for them
+    pub fn jump_target(&mut self, target: LabelRef) -> JumpTarget {
+        match target {
+            LabelRef::Label(name, span) => {
+                self.jump_labels.push(Some((name, span)));
+                JumpTarget::absolute(0)
+            }
+            LabelRef::Index(index) => {
+                self.jump_labels.push(None);
+                JumpTarget::absolute(index)
+            }
+        }
+    }
+
+    fn resolve_label(&self, target: LabelRef) -> Result<u64, Error> {
+        match target {
+            LabelRef::Label(name, span) => self
+                .labels
+                .get(&name)
+                .copied()
+                .ok_or_else(|| Error::new(span, ErrorKind::UnknownLabel(name))),
+            LabelRef::Index(index) => Ok(index),
+        }
+    }
+
+    fn range(&mut self) -> Result<(LabelRef, LabelRef), Error> {
+        Ok((self.label_ref()?, self.label_ref()?))
+    }
+
+    fn resolve_range(&self, (start, end): (LabelRef, LabelRef)) -> Result<Range<u64>, Error> {
+        Ok(Range {
+            start: self.resolve_label(start)?,
+            end: self.resolve_label(end)?,
+        })
+    }
+}
+
+/// An exception table entry whose labels are not resolved yet
+struct PendingExceptionTableEntry {
+    range: (LabelRef, LabelRef),
+    exception_type: TypeToken,
+    filter: Option<(TypeToken, MethodToken)>,
+    catch: (LabelRef, LabelRef),
+    finally: Option<(LabelRef, LabelRef)>,
+    fault: Option<(LabelRef, LabelRef)>,
+}
+
+/// Parses the textual format into an assembly
+pub fn parse(source: &str) -> Result<AssemblyBuilder, Error> {
+    let tokens = lex(source)?;
+    let name = header(&tokens)?;
+    let mut p = Parser {
+        tokens,
+        pos: 2,
+        assembly: AssemblyBuilder::new(&name),
+        labels: HashMap::new(),
+        jump_labels: Vec::new(),
+    };
+
+    while p.peek().is_some() {
+        let (directive, span) = p.expect_word()?;
+        match directive.as_str() {
+            ".version" => {
+                p.assembly.extra_header.version = Syntax::parse(&mut p)?;
+            }
+            ".assembly_ref" => {
+                let name = Syntax::parse(&mut p)?;
+                let requirement = Syntax::parse(&mut p)?;
+                p.assembly
+                    .assembly_refs
+                    .push(AssemblyRef { name, requirement });
+            }
+            ".min_runtime" => {
+                p.assembly.extra_header.min_runtime_version = Syntax::parse(&mut p)?;
+            }
+            ".typeref" => {
+                let assembly = Syntax::parse(&mut p)?;
+                let index = Syntax::parse(&mut p)?;
+                p.assembly.type_refs.push(TypeRef { assembly, index });
+            }
+            ".typespec" => {
+                let ty = Syntax::parse(&mut p)?;
+                let generics = Syntax::parse(&mut p)?;
+                p.assembly.type_specs.push(TypeSpec { ty, generics });
+            }
+            ".methodspec" => {
+                let m = Syntax::parse(&mut p)?;
+                let generics = Syntax::parse(&mut p)?;
+                p.assembly.method_specs.push(MethodSpec { m, generics });
+            }
+            ".custom_attribute" => {
+                let attr = CustomAttribute::parse(&mut p)?;
+                p.assembly.custom_attributes.push(attr);
+            }
+            ".debug_info" => {
+                p.assembly.debug_info.get_or_insert_default();
+            }
+            ".document" => {
+                let path = Syntax::parse(&mut p)?;
+                p.assembly
+                    .debug_info
+                    .get_or_insert_default()
+                    .documents
+                    .push(Document { path });
+            }
+            ".class" | ".struct" | ".interface" => {
+                let type_def = parse_type_def(&mut p, &directive)?;
+                p.assembly.type_defs.push(type_def);
+            }
+            _ => return Err(Error::new(span, ErrorKind::UnknownDirective(directive))),
+        }
+    }
+
+    Ok(p.assembly)
+}
+
+/// Every file starts with `.assembly "<name>"`
+fn header(tokens: &[(Token, Span)]) -> Result<String, Error> {
+    let expected = |expected: &str, (found, span): &(Token, Span)| {
+        Error::new(
+            *span,
+            ErrorKind::Expected {
+                expected: expected.to_owned(),
+                found: found.to_string(),
+            },
+        )
+    };
+    match tokens {
+        [(Token::Word(word), span), rest @ ..] if word == ".assembly" => match rest {
+            [(Token::String(name), _), ..] => Ok(name.clone()),
+            [found, ..] => Err(expected("a string", found)),
+            [] => Err(Error::new(*span, ErrorKind::UnexpectedEnd)),
+        },
+        [found, ..] => Err(expected("\".assembly\"", found)),
+        [] => Err(Error::new(
+            Span { line: 1, column: 1 },
+            ErrorKind::UnexpectedEnd,
+        )),
+    }
+}
+
+fn parse_type_def(p: &mut Parser, kind: &str) -> Result<TypeDef, Error> {
+    let vis = Visibility::parse(p)?;
+    let specific = match kind {
+        ".class" => TypeSpecificAttr::Class(p.flags()?),
+        ".struct" => TypeSpecificAttr::Struct(p.flags()?),
+        _ => TypeSpecificAttr::Interface(p.flags()?),
+    };
+    let name: StringRef = Syntax::parse(p)?;
+    let type_index = p.assembly.type_defs.len() as u32;
+
+    let mut generic_count_requirement = GenericCountRequirement::Exact(0);
+    let mut generic_bounds = None;
+    let mut parent = None;
+    let mut interfaces = Vec::new();
+    let mut required_interfaces = Vec::new();
+    let mut main = None;
+    let mut sctor = None;
+    let mut fields = Vec::new();
+    let mut method_table = Vec::new();
+
+    loop {
+        let (directive, span) = p.expect_word()?;
+        match directive.as_str() {
+            ".end" => break,
+            ".generics" => generic_count_requirement = Syntax::parse(p)?,
+            ".generic_bounds" => generic_bounds = Some(Syntax::parse(p)?),
+            ".method" => {
+                let method = parse_method(p, type_index, method_table.len() as u32)?;
+                method_table.push(method);
+            }
+            ".field" if kind != ".interface" => fields.push(parse_field(p)?),
+            ".sctor" if kind != ".interface" => sctor = Some(Syntax::parse(p)?),
+            ".extends" if kind == ".class" => parent = Some(Syntax::parse(p)?),
+            ".main" if kind == ".class" => main = Some(Syntax::parse(p)?),
+            ".implements" if kind == ".class" => {
+                let target = Syntax::parse(p)?;
+                let map = Syntax::parse(p)?;
+                interfaces.push(InterfaceImplementation { target, map });
+            }
+            ".requires" if kind == ".interface" => required_interfaces.push(Syntax::parse(p)?),
+            _ => return Err(Error::new(span, ErrorKind::UnknownDirective(directive))),
+        }
+    }
+
+    let attr = TypeAttr::new(vis, specific);
+    Ok(match specific {
+        TypeSpecificAttr::Class(_) => TypeDef::Class(ClassDef {
+            main,
+            name,
+            attr,
+            generic_count_requirement,
+            parent,
+            method_table,
+            fields,
+            sctor,
+            generic_bounds,
+            interfaces,
+        }),
+        TypeSpecificAttr::Struct(_) => TypeDef::Struct(StructDef {
+            name,
+            attr,
+            generic_count_requirement,
+            method_table,
+            fields,
+            sctor,
+            generic_bounds,
+        }),
+        TypeSpecificAttr::Interface(_) => TypeDef::Interface(InterfaceDef {
+            name,
+            attr,
+            generic_count_requirement,
+            required_interfaces,
+            method_table,
+            generic_bounds,
+        }),
+    })
+}
+
+/// `.field <visibility> [<flags>] "<name>" <type>`
+fn parse_field(p: &mut Parser) -> Result<Field, Error> {
+    let vis = Visibility::parse(p)?;
+    let flags = p.flags()?;
+    let name = Syntax::parse(p)?;
+    let ty = Syntax::parse(p)?;
+    Ok(Field {
+        name,
+        attr: FieldAttr::new(vis, flags),
+        ty,
+    })
+}
+
+/// `.method <visibility> [<flags>] "<name>" ([<flags>] <type>, ..) -> <type>`,
+/// followed by the body and `.end`
+fn parse_method(p: &mut Parser, type_def: u32, method_index: u32) -> Result<Method, Error> {
+    let vis = Visibility::parse(p)?;
+    let impl_flags = p.flags()?;
+    let name = Syntax::parse(p)?;
+
+    p.expect_punct('(')?;
+    let mut args = Vec::new();
+    while !p.eat_punct(')') {
+        let attr = ParameterAttr::new(p.flags()?);
+        let ty = Syntax::parse(p)?;
+        args.push(Parameter { ty, attr });
+        if !p.eat_punct(',') {
+            p.expect_punct(')')?;
+            break;
+        }
+    }
+    if p.peek() != Some(&Token::Arrow) {
+        return Err(p.unexpected("'->'"));
+    }
+    p.bump();
+    let return_type = Syntax::parse(p)?;
+
+    p.labels.clear();
+    p.jump_labels.clear();
+    let mut call_convention = CallConvention::default();
+    let mut overrides = None;
+    let mut generic_count_requirement = GenericCountRequirement::Exact(0);
+    let mut generic_bounds = None;
+    let mut local_variable_types = Vec::new();
+    let mut instructions: Vec<BinaryInstruction> = Vec::new();
+    let mut exception_table = Vec::new();
+    let mut debug_info = None;
+
+    loop {
+        let span = p.span();
+        match (p.peek(), p.peek_second()) {
+            (Some(Token::Word(directive)), _) if directive.starts_with('.') => {
+                let (directive, span) = p.expect_word()?;
+                match directive.as_str() {
+                    ".end" => break,
+                    ".call_convention" => call_convention = Syntax::parse(p)?,
+                    ".overrides" => overrides = Some(Syntax::parse(p)?),
+                    ".generics" => generic_count_requirement = Syntax::parse(p)?,
+                    ".generic_bounds" => generic_bounds = Some(Syntax::parse(p)?),
+                    ".locals" => local_variable_types = Syntax::parse(p)?,
+                    ".arg_names" => {
+                        method_debug_info(&mut debug_info, type_def, method_index).arg_names =
+                            Syntax::parse(p)?
+                    }
+                    ".local_names" => {
+                        method_debug_info(&mut debug_info, type_def, method_index).local_names =
+                            Syntax::parse(p)?
+                    }
+                    ".line" => {
+                        let document = Syntax::parse(p)?;
+                        let line = Syntax::parse(p)?;
+                        let column = Syntax::parse(p)?;
+                        let debug_info = method_debug_info(&mut debug_info, type_def, method_index);
+                        debug_info.sequence_points.push(SequencePoint {
+                            instruction: instructions.len() as u64,
+                            document,
+                            line,
+                            column,
+                        });
+                    }
+                    ".try" => exception_table.push(parse_exception_table_entry(p)?),
+                    _ => return Err(Error::new(span, ErrorKind::UnknownDirective(directive))),
+                }
+            }
+            (Some(Token::Word(_)), Some(Token::Punct(':'))) => {
+                let (label, _) = p.expect_word()?;
+                p.bump();
+                if p.labels.contains_key(&label) {
+                    return Err(Error::new(span, ErrorKind::DuplicateLabel(label)));
+                }
+                p.labels.insert(label, instructions.len() as u64);
+            }
+            _ => instructions.push(Syntax::parse(p)?),
+        }
+    }
+
+    let mut jump_labels = std::mem::take(&mut p.jump_labels).into_iter();
+    for ins in &mut instructions {
+        for target in jump_targets_mut(ins) {
+            if let Some(Some((label, span))) = jump_labels.next() {
+                *target = JumpTarget::absolute(p.resolve_label(LabelRef::Label(label, span))?);
+            }
+        }
+    }
+    let exception_table = exception_table
+        .into_iter()
+        .map(|entry| {
+            Ok(ExceptionTableEntry {
+                range: p.resolve_range(entry.range)?,
+                exception_type: entry.exception_type,
+                filter: entry.filter,
+                catch: p.resolve_range(entry.catch)?,
+                finally: entry.finally.map(|x| p.resolve_range(x)).transpose()?,
+                fault: entry.fault.map(|x| p.resolve_range(x)).transpose()?,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    if let Some(debug_info) = debug_info {
+        p.assembly
+            .debug_info
+            .get_or_insert_default()
+            .methods
+            .push(debug_info);
+    }
+
+    Ok(Method {
+        name,
+        attr: MethodAttr {
+            vis,
+            impl_flags,
+            overrides,
+            local_variable_types,
+        },
+        generic_count_requirement,
+        args,
+        return_type,
+        call_convention,
+        generic_bounds,
+        instructions,
+        exception_table,
+    })
+}
+
+fn method_debug_info(
+    debug_info: &mut Option<MethodDebugInfo>,
+    type_def: u32,
+    method: u32,
+) -> &mut MethodDebugInfo {
+    debug_info.get_or_insert_with(|| MethodDebugInfo {
+        type_def,
+        method,
+        sequence_points: Vec::new(),
+        arg_names: Vec::new(),
+        local_names: Vec::new(),
+    })
+}
+
+/// `.try <start> <end> catch <type> <start> <end>`, optionally followed by
+/// `filter <type> <method>`, `finally <start> <end>` and `fault <start> <end>`
+fn parse_exception_table_entry(p: &mut Parser) -> Result<PendingExceptionTableEntry, Error> {
+    let range = p.range()?;
+    p.expect_keyword("catch")?;
+    let exception_type = Syntax::parse(p)?;
+    let catch = p.range()?;
+    let filter = if p.eat_keyword("filter") {
+        Some((Syntax::parse(p)?, Syntax::parse(p)?))
+    } else {
+        None
+    };
+    let finally = if p.eat_keyword("finally") {
+        Some(p.range()?)
+    } else {
+        None
+    };
+    let fault = if p.eat_keyword("fault") {
+        Some(p.range()?)
+    } else {
+        None
+    };
+    Ok(PendingExceptionTableEntry {
+        range,
+        exception_type,
+        filter,
+        catch,
+        finally,
+        fault,
+    })
+}
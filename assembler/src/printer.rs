@@ -0,0 +1,423 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{Debug, Display, Write},
+};
+
+use binary::{
+    assembly::{AssemblyBuilder, ExtraHeader},
+    debug_info::MethodDebugInfo,
+    ty::{ExceptionTableEntry, Field, GenericBounds, GenericCountRequirement, Method, TypeDef},
+};
+use binary_core::{BinaryResult, Version};
+use global::{
+    attrs::{CallConvention, TypeSpecificAttr},
+    enumflags2::{BitFlag, BitFlags},
+    instruction::JumpTargetType,
+};
+
+use crate::syntax::{Syntax, jump_targets};
+
+pub struct Printer<'a> {
+    pub assembly: &'a AssemblyBuilder,
+    out: String,
+    /// Instruction count of the method being printed,
+    /// absolute targets up to it are printed as labels
+    len: u64,
+    /// Number of fields written for each struct being printed
+    fields: Vec<usize>,
+}
+
+impl Printer<'_> {
+    pub fn write(&mut self, s: impl Display) {
+        write!(self.out, "{s}").unwrap();
+    }
+
+    /// Starts a new line, without leaving trailing spaces on blank lines
+    fn line(&mut self, indent: usize) {
+        self.out.truncate(self.out.trim_end_matches(' ').len());
+        self.out.push('\n');
+        self.out.extend(std::iter::repeat_n("    ", indent));
+    }
+
+    pub fn begin_fields(&mut self) {
+        self.write("{ ");
+        self.fields.push(0);
+    }
+
+    pub fn field<T: Syntax>(&mut self, name: &str, val: &T) -> BinaryResult<()> {
+        let count = self.fields.last_mut().unwrap();
+        *count += 1;
+        if *count != 1 {
+            self.write(", ");
+        }
+        self.write(format_args!("{name}: "));
+        val.print(self)
+    }
+
+    pub fn end_fields(&mut self) {
+        self.fields.pop();
+        self.write(" }");
+    }
+
+    pub fn write_label(&mut self, index: u64) {
+        if index <= self.len {
+            self.write(format_args!("@L{index}"));
+        } else {
+            self.write(index);
+        }
+    }
+}
+
+/// Prints the whole assembly in the textual format
+pub fn print(assembly: &AssemblyBuilder) -> BinaryResult<String> {
+    let mut p = Printer {
+        assembly,
+        out: String::new(),
+        len: 0,
+        fields: Vec::new(),
+    };
+
+    p.write(".assembly ");
+    assembly.extra_header.name.print(&mut p)?;
+    let version = assembly.extra_header.version;
+    if version != Version::default() {
+        p.line(0);
+        p.write(".version ");
+        version.print(&mut p)?;
+    }
+    let min_runtime_version = assembly.extra_header.min_runtime_version;
+    if min_runtime_version != ExtraHeader::DEFAULT_MIN_RUNTIME_VERSION {
+        p.line(0);
+        p.write(".min_runtime ");
+        min_runtime_version.print(&mut p)?;
+    }
+    p.line(0);
+
+    for assembly_ref in &assembly.assembly_refs {
+        p.line(0);
+        p.write(".assembly_ref ");
+        assembly_ref.name.print(&mut p)?;
+        p.write(" ");
+        assembly_ref.requirement.print(&mut p)?;
+    }
+    for type_ref in &assembly.type_refs {
+        p.line(0);
+        p.write(".typeref ");
+        type_ref.assembly.print(&mut p)?;
+        p.write(format_args!(" {}", type_ref.index));
+    }
+    for type_spec in &assembly.type_specs {
+        p.line(0);
+        p.write(".typespec ");
+        type_spec.ty.print(&mut p)?;
+        p.write(" ");
+        type_spec.generics.print(&mut p)?;
+    }
+    for method_spec in &assembly.method_specs {
+        p.line(0);
+        p.write(format_args!(".methodspec {} ", method_spec.m));
+        method_spec.generics.print(&mut p)?;
+    }
+    for attr in &assembly.custom_attributes {
+        p.line(0);
+        p.write(".custom_attribute ");
+        attr.print(&mut p)?;
+    }
+    if let Some(debug_info) = &assembly.debug_info {
+        p.line(0);
+        p.line(0);
+        p.write(".debug_info");
+        for doc in &debug_info.documents {
+            p.line(0);
+            p.write(".document ");
+            doc.path.print(&mut p)?;
+        }
+    }
+
+    for (type_index, type_def) in assembly.type_defs.iter().enumerate() {
+        p.line(0);
+        print_type_def(&mut p, type_index as u32, type_def)?;
+    }
+
+    p.line(0);
+    Ok(p.out)
+}
+
+fn print_type_def(p: &mut Printer<'_>, type_index: u32, type_def: &TypeDef) -> BinaryResult<()> {
+    let attr = type_def.attr();
+    p.line(0);
+    match attr.specific() {
+        TypeSpecificAttr::Class(flags) => {
+            p.write(".class ");
+            attr.vis().print(p)?;
+            print_flags(p, flags)?;
+        }
+        TypeSpecificAttr::Struct(flags) => {
+            p.write(".struct ");
+            attr.vis().print(p)?;
+            print_flags(p, flags)?;
+        }
+        TypeSpecificAttr::Interface(flags) => {
+            p.write(".interface ");
+            attr.vis().print(p)?;
+            print_flags(p, flags)?;
+        }
+    }
+    p.write(" ");
+    type_def.name().print(p)?;
+
+    let (generic_count_requirement, generic_bounds, fields, method_table) = match type_def {
+        TypeDef::Class(def) => (
+            &def.generic_count_requirement,
+            &def.generic_bounds,
+            &def.fields[..],
+            &def.method_table,
+        ),
+        TypeDef::Struct(def) => (
+            &def.generic_count_requirement,
+            &def.generic_bounds,
+            &def.fields[..],
+            &def.method_table,
+        ),
+        TypeDef::Interface(def) => (
+            &def.generic_count_requirement,
+            &def.generic_bounds,
+            &[][..],
+            &def.method_table,
+        ),
+    };
+
+    print_generics(p, 1, generic_count_requirement, generic_bounds)?;
+    match type_def {
+        TypeDef::Class(def) => {
+            if let Some(parent) = &def.parent {
+                p.line(1);
+                p.write(".extends ");
+                parent.print(p)?;
+            }
+            for interface in &def.interfaces {
+                p.line(1);
+                p.write(".implements ");
+                interface.target.print(p)?;
+                p.write(" ");
+                interface.map.print(p)?;
+            }
+            if let Some(main) = def.main {
+                p.line(1);
+                p.write(format_args!(".main {main}"));
+            }
+            if let Some(sctor) = def.sctor {
+                p.line(1);
+                p.write(format_args!(".sctor {sctor}"));
+            }
+        }
+        TypeDef::Struct(def) => {
+            if let Some(sctor) = def.sctor {
+                p.line(1);
+                p.write(format_args!(".sctor {sctor}"));
+            }
+        }
+        TypeDef::Interface(def) => {
+            for interface in &def.required_interfaces {
+                p.line(1);
+                p.write(".requires ");
+                interface.print(p)?;
+            }
+        }
+    }
+
+    for field in fields {
+        print_field(p, field)?;
+    }
+    for (method_index, method) in method_table.iter().enumerate() {
+        let debug_info = p
+            .assembly
+            .debug_info
+            .as_ref()
+            .and_then(|x| x.get_method(type_index, method_index as u32));
+        p.line(1);
+        print_method(p, method, debug_info)?;
+    }
+
+    p.line(0);
+    p.write(".end");
+    Ok(())
+}
+
+/// Flags are omitted if there is none
+fn print_flags<T: BitFlag + Debug>(p: &mut Printer<'_>, flags: BitFlags<T>) -> BinaryResult<()> {
+    if !flags.is_empty() {
+        p.write(" ");
+        flags.print(p)?;
+    }
+    Ok(())
+}
+
+fn print_generics(
+    p: &mut Printer<'_>,
+    indent: usize,
+    generic_count_requirement: &GenericCountRequirement,
+    generic_bounds: &Option<Vec<GenericBounds>>,
+) -> BinaryResult<()> {
+    if *generic_count_requirement != GenericCountRequirement::Exact(0) {
+        p.line(indent);
+        p.write(".generics ");
+        generic_count_requirement.print(p)?;
+    }
+    if let Some(generic_bounds) = generic_bounds {
+        p.line(indent);
+        p.write(".generic_bounds ");
+        generic_bounds.print(p)?;
+    }
+    Ok(())
+}
+
+fn print_field(p: &mut Printer<'_>, field: &Field) -> BinaryResult<()> {
+    p.line(1);
+    p.write(".field ");
+    field.attr.vis().print(p)?;
+    print_flags(p, field.attr.impl_flags())?;
+    p.write(" ");
+    field.name.print(p)?;
+    p.write(" ");
+    field.ty.print(p)
+}
+
+fn print_method(
+    p: &mut Printer<'_>,
+    method: &Method,
+    debug_info: Option<&MethodDebugInfo>,
+) -> BinaryResult<()> {
+    p.line(1);
+    p.write(".method ");
+    method.attr.vis.print(p)?;
+    print_flags(p, method.attr.impl_flags)?;
+    p.write(" ");
+    method.name.print(p)?;
+    p.write(" (");
+    for (i, arg) in method.args.iter().enumerate() {
+        if i != 0 {
+            p.write(", ");
+        }
+        if !arg.attr.impl_flags().is_empty() {
+            arg.attr.impl_flags().print(p)?;
+            p.write(" ");
+        }
+        arg.ty.print(p)?;
+    }
+    p.write(") -> ");
+    method.return_type.print(p)?;
+
+    if method.call_convention != CallConvention::default() {
+        p.line(2);
+        p.write(".call_convention ");
+        method.call_convention.print(p)?;
+    }
+    if let Some(overrides) = method.attr.overrides {
+        p.line(2);
+        p.write(format_args!(".overrides {overrides}"));
+    }
+    print_generics(
+        p,
+        2,
+        &method.generic_count_requirement,
+        &method.generic_bounds,
+    )?;
+    if !method.attr.local_variable_types.is_empty() {
+        p.line(2);
+        p.write(".locals ");
+        method.attr.local_variable_types.print(p)?;
+    }
+    if let Some(debug_info) = debug_info {
+        p.line(2);
+        p.write(".arg_names ");
+        debug_info.arg_names.print(p)?;
+        p.line(2);
+        p.write(".local_names ");
+        debug_info.local_names.print(p)?;
+    }
+
+    p.len = method.instructions.len() as u64;
+    let mut labels = BTreeSet::new();
+    labels.extend(
+        method
+            .instructions
+            .iter()
+            .flat_map(jump_targets)
+            .filter(|x| x.ty() == JumpTargetType::Absolute)
+            .map(|x| x.val()),
+    );
+    for entry in &method.exception_table {
+        labels.extend([entry.range.start, entry.range.end]);
+        labels.extend([entry.catch.start, entry.catch.end]);
+        labels.extend(entry.finally.iter().flat_map(|x| [x.start, x.end]));
+        labels.extend(entry.fault.iter().flat_map(|x| [x.start, x.end]));
+    }
+    let sequence_points = debug_info.map_or(&[][..], |x| &x.sequence_points[..]);
+
+    for index in 0..=p.len {
+        if labels.contains(&index) {
+            p.line(1);
+            p.write(format_args!("L{index}:"));
+        }
+        let is_last = index == p.len;
+        for point in sequence_points
+            .iter()
+            .filter(|x| x.instruction == index || (is_last && x.instruction > index))
+        {
+            p.line(2);
+            p.write(format_args!(
+                ".line {} {} {}",
+                point.document, point.line, point.column
+            ));
+        }
+        if let Some(ins) = method.instructions.get(index as usize) {
+            p.line(2);
+            ins.print(p)?;
+        }
+    }
+
+    for entry in &method.exception_table {
+        print_exception_table_entry(p, entry)?;
+    }
+
+    p.line(1);
+    p.write(".end");
+    Ok(())
+}
+
+fn print_exception_table_entry(
+    p: &mut Printer<'_>,
+    entry: &ExceptionTableEntry,
+) -> BinaryResult<()> {
+    p.line(2);
+    p.write(".try ");
+    p.write_label(entry.range.start);
+    p.write(" ");
+    p.write_label(entry.range.end);
+    p.write(" catch ");
+    entry.exception_type.print(p)?;
+    p.write(" ");
+    p.write_label(entry.catch.start);
+    p.write(" ");
+    p.write_label(entry.catch.end);
+    if let Some((ty, method)) = &entry.filter {
+        p.write(" filter ");
+        ty.print(p)?;
+        p.write(" ");
+        method.print(p)?;
+    }
+    if let Some(finally) = &entry.finally {
+        p.write(" finally ");
+        p.write_label(finally.start);
+        p.write(" ");
+        p.write_label(finally.end);
+    }
+    if let Some(fault) = &entry.fault {
+        p.write(" fault ");
+        p.write_label(fault.start);
+        p.write(" ");
+        p.write_label(fault.end);
+    }
+    Ok(())
+}
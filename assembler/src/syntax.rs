@@ -0,0 +1,889 @@
+//! Textual forms of the values stored in an assembly
+//!
+//! Enums are written as `Variant`, `Variant <inner>` or `Variant { field: <value>, .. }`,
+//! structs as `{ field: <value>, .. }`.
+
+use std::{
+    fmt::Debug,
+    range::{RangeFrom, RangeToInclusive},
+};
+
+use binary::{
+    custom_attribute::{CustomAttribute, CustomAttributeValue, Integer, NamedArgument},
+    item_token::{
+        MethodToken, MethodTokenBuilder, MethodType, TypeToken, TypeTokenBuilder, TypeType,
+    },
+    ty::{BinaryInstruction, GenericBounds, GenericCountRequirement},
+};
+use binary_core::{
+    BinaryResult, Version, VersionRequirement, VersionRequirementKind, traits::StringRef,
+};
+use global::{
+    attrs::{CallConvention, Visibility},
+    enumflags2::{BitFlag, BitFlags},
+    instruction::{
+        ArithmeticOp, CommonReadPointerTo, CommonWritePointer, CompareConst, CompareKind,
+        CompareType, IRegisterAddr, Instruction, Instruction_Array, Instruction_Calculate,
+        Instruction_Call, Instruction_Cast, Instruction_CommonCheck, Instruction_Convert,
+        Instruction_Jump, Instruction_Load, Instruction_Move, Instruction_New, Instruction_Set,
+        Instruction_StackAllocate, Instruction_Switch, Instruction_UntypedCalculate, JumpCondition,
+        JumpTarget, JumpTargetType, LoadContent, OverflowMode, PrimitiveKind, RegisterAddr,
+        ShortRegisterAddr, ToCheckContent,
+    },
+    non_purus_call_configuration::{
+        NonPurusCallConfiguration, NonPurusCallType, ObjectStrategy, StringEncoding,
+    },
+};
+
+use crate::{
+    error::{Error, ErrorKind},
+    lexer::Token,
+    parser::Parser,
+    printer::Printer,
+};
+
+pub trait Syntax: Sized {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()>;
+    fn parse(p: &mut Parser) -> Result<Self, Error>;
+}
+
+macro ignore($($t:tt)*) {}
+
+macro syntax_enum(
+    [$($generics:tt)*] $Ty:ty => {
+        $(
+            $Variant:ident
+            $( ($inner:ident) )?
+            $( { $($field:ident),* $(,)? } )?
+        ),* $(,)?
+    }
+) {
+    impl<$($generics)*> Syntax for $Ty {
+        fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+            match self {
+                $(
+                    Self::$Variant $( ($inner) )? $( { $($field),* } )? => {
+                        p.write(stringify!($Variant));
+                        $(
+                            p.write(" ");
+                            $inner.print(p)?;
+                        )?
+                        $(
+                            p.write(" ");
+                            p.begin_fields();
+                            $( p.field(stringify!($field), $field)?; )*
+                            p.end_fields();
+                        )?
+                    }
+                )*
+            }
+            Ok(())
+        }
+
+        fn parse(p: &mut Parser) -> Result<Self, Error> {
+            let (name, span) = p.expect_word()?;
+            $(
+                if name == stringify!($Variant) {
+                    $(
+                        ignore!($($field)*);
+                        p.begin_fields()?;
+                    )?
+                    let val = Self::$Variant
+                        $( ({
+                            ignore!($inner);
+                            Syntax::parse(p)?
+                        }) )?
+                        $( { $($field: p.field(stringify!($field))?),* } )?;
+                    $(
+                        ignore!($($field)*);
+                        p.end_fields()?;
+                    )?
+                    return Ok(val);
+                }
+            )*
+            Err(Error::new(span, ErrorKind::UnknownVariant(name)))
+        }
+    }
+}
+
+macro syntax_struct([$($generics:tt)*] $Ty:ty => { $($field:ident),* $(,)? }) {
+    impl<$($generics)*> Syntax for $Ty {
+        fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+            let Self { $($field),* } = self;
+            p.begin_fields();
+            $( p.field(stringify!($field), $field)?; )*
+            p.end_fields();
+            Ok(())
+        }
+
+        fn parse(p: &mut Parser) -> Result<Self, Error> {
+            p.begin_fields()?;
+            let val = Self { $($field: p.field(stringify!($field))?),* };
+            p.end_fields()?;
+            Ok(val)
+        }
+    }
+}
+
+macro syntax_number($($t:ty)*) {$(
+    impl Syntax for $t {
+        fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+            p.write(self);
+            Ok(())
+        }
+
+        fn parse(p: &mut Parser) -> Result<Self, Error> {
+            let (word, span) = p.expect_word()?;
+            let parsed = match word.strip_prefix("0x") {
+                Some(hex) => <$t>::from_str_radix(hex, 16),
+                None => word.parse::<$t>(),
+            };
+            parsed.map_err(|_| Error::new(span, ErrorKind::InvalidNumber(word)))
+        }
+    }
+)*}
+
+syntax_number!(u8 u16 u32 u64 i8 i16 i32 i64);
+
+macro syntax_float($($t:ty)*) {$(
+    impl Syntax for $t {
+        fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+            // Debug keeps the digits needed to read back the same value
+            p.write(format_args!("{self:?}"));
+            Ok(())
+        }
+
+        fn parse(p: &mut Parser) -> Result<Self, Error> {
+            let (word, span) = p.expect_word()?;
+            word.parse()
+                .map_err(|_| Error::new(span, ErrorKind::InvalidNumber(word)))
+        }
+    }
+)*}
+
+syntax_float!(f32 f64);
+
+impl Syntax for bool {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        p.write(self);
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        if p.eat_keyword("true") {
+            Ok(true)
+        } else if p.eat_keyword("false") {
+            Ok(false)
+        } else {
+            Err(p.unexpected("a boolean"))
+        }
+    }
+}
+
+impl Syntax for char {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        p.write(format_args!("{self:?}"));
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        match p.peek() {
+            Some(Token::Char(c)) => {
+                let c = *c;
+                p.bump();
+                Ok(c)
+            }
+            _ => Err(p.unexpected("a char")),
+        }
+    }
+}
+
+impl Syntax for Version {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        p.write(self);
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        let (word, span) = p.expect_word()?;
+        word.parse()
+            .map_err(|_| Error::new(span, ErrorKind::InvalidVersion(word)))
+    }
+}
+
+syntax_enum!([] VersionRequirementKind => { Exact, Compatible, AtLeast });
+
+/// `Compatible 1.2.3` instead of `^1.2.3`, as the operators are not words
+impl Syntax for VersionRequirement {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        self.kind.print(p)?;
+        p.write(" ");
+        self.version.print(p)
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        let kind = Syntax::parse(p)?;
+        Ok(Self::new(kind, Syntax::parse(p)?))
+    }
+}
+
+impl Syntax for StringRef {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        let s = p.assembly.get_string(*self)?;
+        p.write(format_args!("{s:?}"));
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        let s = p.expect_string()?;
+        Ok(p.assembly.add_string(&s))
+    }
+}
+
+impl<T: Syntax> Syntax for Vec<T> {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        p.write("[");
+        for (i, x) in self.iter().enumerate() {
+            if i != 0 {
+                p.write(", ");
+            }
+            x.print(p)?;
+        }
+        p.write("]");
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        p.expect_punct('[')?;
+        let mut v = Vec::new();
+        while !p.eat_punct(']') {
+            v.push(T::parse(p)?);
+            if !p.eat_punct(',') {
+                p.expect_punct(']')?;
+                break;
+            }
+        }
+        Ok(v)
+    }
+}
+
+impl<T: Syntax> Syntax for Option<T> {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        match self {
+            Some(x) => {
+                p.write("Some ");
+                x.print(p)
+            }
+            None => {
+                p.write("None");
+                Ok(())
+            }
+        }
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        if p.eat_keyword("None") {
+            Ok(None)
+        } else {
+            p.expect_keyword("Some")?;
+            T::parse(p).map(Some)
+        }
+    }
+}
+
+impl<T: Syntax> Syntax for Box<T> {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        T::print(self, p)
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        T::parse(p).map(Box::new)
+    }
+}
+
+impl<A: Syntax, B: Syntax> Syntax for (A, B) {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        p.write("(");
+        self.0.print(p)?;
+        p.write(", ");
+        self.1.print(p)?;
+        p.write(")");
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        p.expect_punct('(')?;
+        let a = A::parse(p)?;
+        p.expect_punct(',')?;
+        let b = B::parse(p)?;
+        p.expect_punct(')')?;
+        Ok((a, b))
+    }
+}
+
+/// Written as the list of the names of the flags
+impl<T: BitFlag + Debug> Syntax for BitFlags<T> {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        p.write("[");
+        for (i, flag) in self.iter().enumerate() {
+            if i != 0 {
+                p.write(", ");
+            }
+            p.write(format_args!("{flag:?}"));
+        }
+        p.write("]");
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        p.expect_punct('[')?;
+        let mut flags = BitFlags::empty();
+        while !p.eat_punct(']') {
+            let (name, span) = p.expect_word()?;
+            let flag = BitFlags::<T>::all()
+                .iter()
+                .find(|x| format!("{x:?}") == name)
+                .ok_or_else(|| Error::new(span, ErrorKind::UnknownVariant(name)))?;
+            flags |= flag;
+            if !p.eat_punct(',') {
+                p.expect_punct(']')?;
+                break;
+            }
+        }
+        Ok(flags)
+    }
+}
+
+macro syntax_register($($t:ty)*) {$(
+    /// Written as `%<index>`
+    impl Syntax for $t {
+        fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+            p.write(format_args!("%{}", self.get()));
+            Ok(())
+        }
+
+        fn parse(p: &mut Parser) -> Result<Self, Error> {
+            p.expect_punct('%')?;
+            Syntax::parse(p).map(<$t>::new)
+        }
+    }
+)*}
+
+syntax_register!(RegisterAddr ShortRegisterAddr);
+
+const TYPE_TOKEN_KINDS: &[(TypeType, &str)] = &[
+    (TypeType::TypeDef, "typedef"),
+    (TypeType::TypeRef, "typeref"),
+    (TypeType::TypeSpec, "typespec"),
+    (TypeType::MethodGeneric, "method_generic"),
+    (TypeType::TypeGeneric, "type_generic"),
+];
+
+const METHOD_TOKEN_KINDS: &[(MethodType, &str)] = &[
+    (MethodType::Method, "method"),
+    (MethodType::MethodSpec, "methodspec"),
+    (MethodType::MethodByRuntime, "method_by_runtime"),
+];
+
+/// Parses `<kind>[<index>]`
+fn parse_token<K: Copy>(p: &mut Parser, kinds: &[(K, &str)]) -> Result<(K, u32), Error> {
+    let (name, span) = p.expect_word()?;
+    let kind = kinds
+        .iter()
+        .find(|(_, x)| *x == name)
+        .map(|(x, _)| *x)
+        .ok_or_else(|| Error::new(span, ErrorKind::UnknownVariant(name)))?;
+    p.expect_punct('[')?;
+    let (word, span) = p.expect_word()?;
+    // Tokens only have 24 bits for the index
+    let index = word
+        .parse::<u32>()
+        .ok()
+        .filter(|x| *x < (1 << 24))
+        .ok_or_else(|| Error::new(span, ErrorKind::InvalidNumber(word)))?;
+    p.expect_punct(']')?;
+    Ok((kind, index))
+}
+
+/// Written as `typeref[<index>]`, `typedef[<index>]` and so on
+impl Syntax for TypeToken {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        let ty = self.ty();
+        let (_, name) = TYPE_TOKEN_KINDS
+            .iter()
+            .find(|(x, _)| *x as u8 == ty as u8)
+            .unwrap();
+        p.write(format_args!("{name}[{}]", self.index()));
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        let (ty, index) = parse_token(p, TYPE_TOKEN_KINDS)?;
+        Ok(TypeTokenBuilder::new()
+            .with_ty(ty)
+            .with_index(index)
+            .build())
+    }
+}
+
+/// Written as `method[<index>]`, `methodspec[<index>]` and so on
+impl Syntax for MethodToken {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        let ty = self.ty();
+        let (_, name) = METHOD_TOKEN_KINDS
+            .iter()
+            .find(|(x, _)| *x as u8 == ty as u8)
+            .unwrap();
+        p.write(format_args!("{name}[{}]", self.index()));
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        let (ty, index) = parse_token(p, METHOD_TOKEN_KINDS)?;
+        Ok(MethodTokenBuilder::new()
+            .with_ty(ty)
+            .with_index(index)
+            .build())
+    }
+}
+
+/// Absolute targets are written as `@<label>`, or as the index of the instruction
+/// if there is no instruction there; relative ones are written as
+/// `forward <offset>` and `backward <offset>`
+impl Syntax for JumpTarget {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        match self.ty() {
+            JumpTargetType::Absolute => p.write_label(self.val()),
+            JumpTargetType::Forward => p.write(format_args!("forward {}", self.val())),
+            JumpTargetType::Backward => p.write(format_args!("backward {}", self.val())),
+            JumpTargetType::Unknown => return Err(binary_core::Error::WrongFormat),
+        }
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        if p.eat_keyword("forward") {
+            return Syntax::parse(p).map(JumpTarget::forward);
+        }
+        if p.eat_keyword("backward") {
+            return Syntax::parse(p).map(JumpTarget::backward);
+        }
+        let target = p.label_ref()?;
+        Ok(p.jump_target(target))
+    }
+}
+
+/// The jump targets in `ins`, in the order they are written
+pub fn jump_targets(ins: &BinaryInstruction) -> Vec<JumpTarget> {
+    match ins {
+        Instruction::Jump(ins) => vec![ins.target],
+        Instruction::SJump(ins) => vec![ins.target],
+        Instruction::Switch(ins) => ins.targets.iter().chain([&ins.default]).copied().collect(),
+        Instruction::SSwitch(ins) => ins.targets.iter().chain([&ins.default]).copied().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The jump targets in `ins`, in the order they are written
+pub fn jump_targets_mut(ins: &mut BinaryInstruction) -> Vec<&mut JumpTarget> {
+    match ins {
+        Instruction::Jump(ins) => vec![&mut ins.target],
+        Instruction::SJump(ins) => vec![&mut ins.target],
+        Instruction::Switch(ins) => ins.targets.iter_mut().chain([&mut ins.default]).collect(),
+        Instruction::SSwitch(ins) => ins.targets.iter_mut().chain([&mut ins.default]).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Written as `Exact <n>`, `AtLeast <n>` or `NoMoreThan <n>`
+impl Syntax for GenericCountRequirement {
+    fn print(&self, p: &mut Printer<'_>) -> BinaryResult<()> {
+        match self {
+            GenericCountRequirement::AtLeast(range) => {
+                p.write(format_args!("AtLeast {}", range.start))
+            }
+            GenericCountRequirement::NoMoreThan(range) => {
+                p.write(format_args!("NoMoreThan {}", range.last))
+            }
+            GenericCountRequirement::Exact(n) => p.write(format_args!("Exact {n}")),
+        }
+        Ok(())
+    }
+
+    fn parse(p: &mut Parser) -> Result<Self, Error> {
+        let (name, span) = p.expect_word()?;
+        match name.as_str() {
+            "AtLeast" => Ok(Self::AtLeast(RangeFrom {
+                start: Syntax::parse(p)?,
+            })),
+            "NoMoreThan" => Ok(Self::NoMoreThan(RangeToInclusive {
+                last: Syntax::parse(p)?,
+            })),
+            "Exact" => Syntax::parse(p).map(Self::Exact),
+            _ => Err(Error::new(span, ErrorKind::UnknownVariant(name))),
+        }
+    }
+}
+
+syntax_struct!([] GenericBounds => { implemented_interfaces, parent });
+
+syntax_struct!([] CustomAttribute => { ty, ctor_name, positional_args, named_args });
+
+syntax_enum!([] CustomAttributeValue => {
+    Boolean(x),
+    Char(x),
+    Integer(x),
+    String(x),
+    SystemType(x),
+    PureEnum { ty, val },
+    Single(x),
+    Double(x),
+    Object { ty, val },
+    Array(x),
+});
+
+syntax_enum!([] NamedArgument => {
+    Field { name, value },
+    Property { name, value },
+});
+
+syntax_enum!([] Integer => {
+    Byte(x),
+    SByte(x),
+    Short(x),
+    UShort(x),
+    Int(x),
+    UInt(x),
+    Long(x),
+    ULong(x),
+});
+
+syntax_enum!([] Visibility => { Public, Private, AssemblyOnly });
+
+syntax_enum!([] CallConvention => {
+    PlatformDefault,
+    CDecl,
+    CDeclWithVararg,
+    Win64,
+    SystemV,
+    Stdcall,
+    Fastcall,
+});
+
+syntax_struct!([] NonPurusCallConfiguration => {
+    call_convention,
+    return_type,
+    encoding,
+    object_strategy,
+    arguments,
+});
+
+syntax_enum!([] NonPurusCallType => {
+    Void,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    String,
+    Object,
+    F32,
+    F64,
+    Structure(fields),
+});
+
+syntax_enum!([] StringEncoding => { Utf16, Utf8, C_Utf16, C_Utf8, Remain });
+
+syntax_enum!([] ObjectStrategy => { Remain, PointToData });
+
+syntax_enum!([] BinaryInstruction => {
+    Nop,
+    Load(ins),
+    SLoad(ins),
+
+    ReadPointerTo(ins),
+    SReadPointerTo(ins),
+
+    WritePointer(ins),
+    SWritePointer(ins),
+
+    Check(ins),
+    SCheck(ins),
+
+    New(ins),
+    SNew(ins),
+
+    Call(ins),
+    SCall(ins),
+
+    Set(ins),
+    SSet(ins),
+
+    Calculate(ins),
+    SCalculate(ins),
+
+    Throw { exception_addr },
+    SThrow { exception_addr },
+    Rethrow,
+
+    ReturnVal { register_addr },
+    SReturnVal { register_addr },
+
+    Jump(ins),
+    SJump(ins),
+
+    StackAllocate(ins),
+    SStackAllocate(ins),
+
+    Convert(ins),
+    SConvert(ins),
+
+    Cast(ins),
+    SCast(ins),
+
+    Switch(ins),
+    SSwitch(ins),
+
+    Move(ins),
+    SMove(ins),
+
+    ZeroInit { target },
+    SZeroInit { target },
+
+    Array(ins),
+    SArray(ins),
+});
+
+syntax_struct!(
+    [R: IRegisterAddr + Syntax] Instruction_Load<StringRef, TypeToken, MethodToken, u32, R> => {
+        addr,
+        content,
+    }
+);
+
+syntax_enum!([R: IRegisterAddr + Syntax] LoadContent<StringRef, TypeToken, MethodToken, u32, R> => {
+    AddressOfRegister(x),
+    True,
+    False,
+
+    U8(x),
+    U16(x),
+    U32(x),
+    U64(x),
+
+    I8(x),
+    I16(x),
+    I32(x),
+    I64(x),
+
+    AddressOfThis,
+    This,
+
+    String(x),
+
+    TypeValueSize(x),
+
+    NonPurusCallConfiguration(x),
+
+    Arg(x),
+    ArgRef(x),
+    ArgValue(x),
+
+    AddressOfStatic { ty, field },
+    Static { ty, field },
+    AddressOfField { container, field },
+    Field { container, field },
+
+    CaughtException,
+
+    F32(x),
+    F64(x),
+
+    MethodPointer { ty, method },
+
+    Null,
+    Default(x),
+    Char(x),
+});
+
+syntax_struct!([R: IRegisterAddr + Syntax] CommonReadPointerTo<R> => { ptr, size, destination });
+
+syntax_struct!([R: IRegisterAddr + Syntax] CommonWritePointer<R> => { source, size, ptr });
+
+syntax_struct!([R: IRegisterAddr + Syntax] Instruction_CommonCheck<R> => { output, content });
+
+syntax_enum!([R: IRegisterAddr + Syntax] ToCheckContent<R> => {
+    IsAllZero(x),
+    Compare { kind, ty, lhs, rhs },
+    CompareWithConst { kind, lhs, rhs },
+});
+
+syntax_enum!([] CompareKind => { Eq, Ne, Lt, Le, Gt, Ge });
+
+syntax_enum!([] CompareType => { U8, U16, U32, U64, I8, I16, I32, I64, F32, F64, Object });
+
+syntax_enum!([] CompareConst => {
+    U8(x),
+    U16(x),
+    U32(x),
+    U64(x),
+
+    I8(x),
+    I16(x),
+    I32(x),
+    I64(x),
+
+    F32(x),
+    F64(x),
+});
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_New<TypeToken, MethodToken, R> => {
+    NewObject { ty, ctor_name, args, output },
+    NewArray { element_type, len, output },
+    NewDynamicArray { element_type, len_addr, output },
+});
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_Call<TypeToken, MethodToken, R> => {
+    InstanceCall { val, method, args, ret_at },
+    StaticCall { ty, method, args, ret_at },
+    InterfaceCall { interface, val, method, args, ret_at },
+    StaticNonPurusCall { f_pointer, config, args, ret_at },
+    DynamicNonPurusCall { f_pointer, config, args, ret_at },
+    IndirectCall { f_pointer, args, ret_at },
+    TailInstanceCall { val, method, args },
+    TailStaticCall { ty, method, args },
+    TailInterfaceCall { interface, val, method, args },
+});
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_Set<TypeToken, u32, R> => {
+    Common { val, container, field },
+    This { val, field },
+    Static { val, ty, field },
+});
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_Calculate<R> => {
+    U8(ins),
+    U16(ins),
+    U32(ins),
+    U64(ins),
+
+    I8(ins),
+    I16(ins),
+    I32(ins),
+    I64(ins),
+
+    F32(ins),
+    F64(ins),
+});
+
+syntax_enum!([R: IRegisterAddr + Syntax, T: Copy + Syntax] Instruction_UntypedCalculate<R, T> => {
+    Add { lhs, rhs, target },
+    Sub { lhs, rhs, target },
+    Mul { lhs, rhs, target },
+    Div { lhs, rhs, target },
+    Rem { lhs, rhs, target },
+
+    ConstAddTo { target, data },
+    ConstSubTo { target, data },
+    ConstMulTo { target, data },
+    ConstDivTo { target, data },
+    ConstRemTo { target, data },
+
+    SubByConst { target, data },
+    DivByConst { target, data },
+    RemByConst { target, data },
+
+    AddOne { target },
+    SubOne { target },
+
+    And { lhs, rhs, target },
+    Or { lhs, rhs, target },
+    Xor { lhs, rhs, target },
+    Not { source, target },
+
+    Shl { lhs, rhs, target },
+    Shr { lhs, rhs, target },
+    Sar { lhs, rhs, target },
+    RotateLeft { lhs, rhs, target },
+    RotateRight { lhs, rhs, target },
+
+    ConstAndTo { target, data },
+    ConstOrTo { target, data },
+    ConstXorTo { target, data },
+
+    ConstShlTo { target, amount },
+    ConstShrTo { target, amount },
+    ConstSarTo { target, amount },
+    ConstRotateLeftTo { target, amount },
+    ConstRotateRightTo { target, amount },
+
+    Arithmetic { op, mode, lhs, rhs, target },
+    ConstArithmeticTo { op, mode, target, data },
+    ArithmeticByConst { op, mode, target, data },
+});
+
+syntax_enum!([] ArithmeticOp => { Add, Sub, Mul, Div, Rem });
+
+syntax_enum!([] OverflowMode => { Wrapping, Checked, Saturating });
+
+syntax_struct!([R: IRegisterAddr + Syntax] Instruction_Jump<R> => { target, condition });
+
+syntax_enum!([R: IRegisterAddr + Syntax] JumpCondition<R> => {
+    Unconditional,
+    If(x),
+    IfCheckSucceeds(x),
+    IfCheckFails(x),
+});
+
+syntax_struct!([R: IRegisterAddr + Syntax] Instruction_Switch<R> => { value, targets, default });
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_StackAllocate<R> => {
+    Dynamic { out, size, align },
+    DynamicZeroed { out, size, align },
+    Static { out, size, align },
+    StaticZeroed { out, size, align },
+});
+
+syntax_struct!([R: IRegisterAddr + Syntax] Instruction_Convert<R> => {
+    from,
+    to,
+    mode,
+    source,
+    target,
+});
+
+syntax_enum!([] PrimitiveKind => {
+    U8,
+    U16,
+    U32,
+    U64,
+    USize,
+
+    I8,
+    I16,
+    I32,
+    I64,
+    ISize,
+
+    F32,
+    F64,
+});
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_Cast<TypeToken, R> => {
+    IsInstanceOf { ty, val, output },
+    CastClass { ty, val, output },
+    Box { ty, value, output },
+    Unbox { ty, obj, output },
+});
+
+syntax_struct!([R: IRegisterAddr + Syntax] Instruction_Move<R> => { from, to });
+
+syntax_enum!([R: IRegisterAddr + Syntax] Instruction_Array<R> => {
+    LoadElement { array, index, output },
+    StoreElement { array, index, value },
+    LoadElementAddress { array, index, output },
+    ArrayLength { array, output },
+});
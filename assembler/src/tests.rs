@@ -0,0 +1,200 @@
+use std::io::Cursor;
+
+use binary::assembly::AssemblyBuilder;
+use binary_core::Version;
+
+use crate::{ErrorKind, Span, assemble, disassemble};
+
+const SOURCE: &str = r#".assembly "Test"
+
+.typeref "!" 0
+.typeref "!" 1
+.typeref "!" 2
+
+.debug_info
+.document "test.pl"
+
+.class Public "Test::Test"
+    .extends typeref[0]
+    .field Private [Static] "Count" typeref[2]
+
+    .method Public [Static] "Loop" (typeref[2]) -> typeref[2]
+        .locals [typeref[2]]
+        .arg_names ["count"]
+        .local_names ["i"]
+        .line 0 1 1
+        Load { addr: %0, content: Arg 0 }
+    L1:
+        .line 0 2 5
+        Calculate U64 SubOne { target: %0 }
+        Jump { target: @L1, condition: IfCheckFails IsAllZero %0 }
+    L3:
+        ReturnVal { register_addr: %0 }
+    L4:
+        Throw { exception_addr: %0 }
+    L5:
+        .try @L1 @L3 catch typeref[1] @L4 @L5
+    .end
+.end
+
+.struct AssemblyOnly [Ref] "Test::Pair"
+    .generics Exact 2
+    .field Public "First" type_generic[0]
+    .field Public "Second" type_generic[1]
+
+    .method Public "Swap" ([ByRef] typedef[1]) -> typeref[1]
+        Switch { value: %0, targets: [@L1, @L2], default: @L2 }
+    L1:
+        Nop
+    L2:
+        Rethrow
+    .end
+.end
+"#;
+
+#[test]
+fn round_trip_text() {
+    let assembly = assemble(SOURCE).unwrap();
+    assert_eq!(disassemble(&assembly).unwrap(), SOURCE);
+}
+
+#[test]
+fn round_trip_binary() -> binary_core::BinaryResult<()> {
+    let mut bytes = Cursor::new(Vec::new());
+    assemble(SOURCE).unwrap().write_to(&mut bytes)?;
+    let assembly = AssemblyBuilder::from_bytes(bytes.into_inner())?;
+    assert_eq!(disassemble(&assembly)?, SOURCE);
+    Ok(())
+}
+
+#[test]
+fn named_labels() {
+    let source = r#".assembly "Test"
+
+.class Public "Test::Test"
+
+    .method Public [Static] "F" () -> typeref[0]
+    start:
+        Nop
+        Jump { target: @start, condition: Unconditional }
+    .end
+.end
+"#;
+    let assembly = assemble(source).unwrap();
+    let text = disassemble(&assembly).unwrap();
+    assert!(text.contains("    L0:\n        Nop\n"));
+    assert!(text.contains("Jump { target: @L0, condition: Unconditional }"));
+}
+
+#[test]
+fn unknown_label() {
+    let source = r#".assembly "Test"
+.class Public "Test::Test"
+    .method Public "F" () -> typeref[0]
+        Jump { target: @nowhere, condition: Unconditional }
+    .end
+.end
+"#;
+    let err = assemble(source).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownLabel("nowhere".to_owned()));
+    assert_eq!(
+        err.span,
+        Span {
+            line: 4,
+            column: 25
+        }
+    );
+}
+
+#[test]
+fn unknown_variant() {
+    let source = r#".assembly "Test"
+.class Public "Test::Test"
+    .method Public "F" () -> typeref[0]
+        Jmp
+    .end
+.end
+"#;
+    let err = assemble(source).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownVariant("Jmp".to_owned()));
+    assert_eq!(err.span, Span { line: 4, column: 9 });
+}
+
+#[test]
+fn min_runtime() {
+    let source = r#".assembly "Test"
+.min_runtime 0.3.1
+
+.typeref "!" 0
+
+.class Public "Test::Test"
+.end
+"#;
+    let assembly = assemble(source).unwrap();
+    assert_eq!(
+        assembly.extra_header.min_runtime_version,
+        Version::new(0, 3, 1)
+    );
+    assert_eq!(disassemble(&assembly).unwrap(), source);
+
+    let err = assemble(&source.replace("0.3.1", "0.3")).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidVersion("0.3".to_owned()));
+}
+
+#[test]
+fn assembly_refs() {
+    let source = r#".assembly "Test"
+.version 1.2.0
+
+.assembly_ref "Core" AtLeast 0.1.0
+.assembly_ref "Lib" Compatible 0.3.1
+.typeref "Core" 0
+
+.class Public "Test::Test"
+.end
+"#;
+    let assembly = assemble(source).unwrap();
+    assert_eq!(assembly.extra_header.version, Version::new(1, 2, 0));
+    let requirements = assembly
+        .assembly_refs
+        .iter()
+        .map(|x| {
+            (
+                assembly.get_string(x.name).unwrap().to_owned(),
+                x.requirement.to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        requirements,
+        [
+            ("Core".to_owned(), ">=0.1.0".to_owned()),
+            ("Lib".to_owned(), "^0.3.1".to_owned()),
+        ]
+    );
+    assert_eq!(disassemble(&assembly).unwrap(), source);
+
+    let err = assemble(&source.replace("AtLeast", "Above")).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownVariant("Above".to_owned()));
+}
+
+#[test]
+fn custom_attributes() -> binary_core::BinaryResult<()> {
+    let source = r#".assembly "Test"
+
+.typeref "!" 0
+.typeref "!" 1
+.custom_attribute { ty: typeref[0], ctor_name: method[0], positional_args: [Single 1.5, Double -0.25, Object { ty: typeref[1], val: Integer Int 3 }, Array [Boolean true, Char 'a']], named_args: [Field { name: "Count", value: Double 2.0 }, Property { name: "Tags", value: Array [String "x", String "y"] }] }
+
+.class Public "Test::Test"
+.end
+"#;
+    let assembly = assemble(source).unwrap();
+    assert_eq!(disassemble(&assembly)?, source);
+
+    let mut bytes = Cursor::new(Vec::new());
+    assembly.write_to(&mut bytes)?;
+    let assembly = AssemblyBuilder::from_bytes(bytes.into_inner())?;
+    assert_eq!(disassemble(&assembly)?, source);
+    Ok(())
+}
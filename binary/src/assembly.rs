@@ -1,20 +1,26 @@
 use std::{
+    borrow::Cow,
     io::{Cursor, Seek, Write},
     path::Path,
+    range::Range,
 };
 
 use binary_core::{
-    Error,
+    Error, Version, VersionRequirement,
     file::{File, FileBuilder, PredefinedSectionId},
     section::{Section, SectionBuilder},
     traits::{ReadFromSection as _, StringRef, WriteToSection},
 };
 use derive_more::Debug;
+use enumflags2::{BitFlags, bitflags};
 use proc_macros::{ReadFromSection, WriteToSection};
 
 use crate::{
     custom_attribute::CustomAttribute,
-    ty::{MethodSpec, TypeDef, TypeRef, TypeSpec},
+    debug_info::DebugInfo,
+    item_token::{TypeToken, TypeType},
+    lazy::LazyAssembly,
+    ty::{Method, MethodSpec, TypeDef, TypeRef, TypeSpec},
 };
 
 #[derive(Debug)]
@@ -29,11 +35,13 @@ pub struct Assembly<'a> {
     pub extra_header: ExtraHeader,
     #[debug(skip)]
     pub string_section: &'a Section,
-    pub custom_attributes: Vec<CustomAttribute>,
-    pub type_refs: Vec<TypeRef>,
-    pub type_specs: Vec<TypeSpec>,
-    pub method_specs: Vec<MethodSpec>,
-    pub type_defs: Vec<TypeDef>,
+    pub custom_attributes: Cow<'a, [CustomAttribute]>,
+    pub type_refs: Cow<'a, [TypeRef]>,
+    pub type_specs: Cow<'a, [TypeSpec]>,
+    pub method_specs: Cow<'a, [MethodSpec]>,
+    pub type_defs: Cow<'a, [TypeDef]>,
+    pub debug_info: Option<Cow<'a, DebugInfo>>,
+    pub assembly_refs: Cow<'a, [AssemblyRef]>,
 }
 
 #[derive(Debug)]
@@ -53,63 +61,178 @@ pub struct AssemblyBuilder {
     pub type_specs: Vec<TypeSpec>,
     pub method_specs: Vec<MethodSpec>,
     pub type_defs: Vec<TypeDef>,
+    pub debug_info: Option<DebugInfo>,
+    pub assembly_refs: Vec<AssemblyRef>,
 }
 
 #[derive(ReadFromSection, WriteToSection, Debug, Clone, Copy)]
 pub struct ExtraHeader {
     pub name: StringRef,
+    pub version: Version,
+    /// The oldest runtime able to load the assembly
+    pub min_runtime_version: Version,
+    /// Bits of [`AssemblyFeature`], kept raw so that features unknown to the reader are
+    /// reported instead of dropped
+    pub features: u32,
+}
+
+impl ExtraHeader {
+    /// The first runtime reading the current format
+    pub const DEFAULT_MIN_RUNTIME_VERSION: Version = Version::new(0, 1, 0);
+
+    pub fn new(name: StringRef) -> Self {
+        Self {
+            name,
+            version: Version::default(),
+            min_runtime_version: Self::DEFAULT_MIN_RUNTIME_VERSION,
+            features: 0,
+        }
+    }
+    pub fn features(&self) -> binary_core::BinaryResult<BitFlags<AssemblyFeature>> {
+        BitFlags::from_bits(self.features)
+            .map_err(|err| Error::UnsupportedFeatures(err.invalid_bits()))
+    }
+    pub fn check_runtime_version(&self, current: Version) -> binary_core::BinaryResult<()> {
+        if self.min_runtime_version > current {
+            return Err(Error::UnsupportedRuntimeVersion {
+                required: self.min_runtime_version,
+                current,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// An assembly which the type refs may point to, checked before any of them is loaded
+#[derive(ReadFromSection, WriteToSection, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssemblyRef {
+    pub name: StringRef,
+    pub requirement: VersionRequirement,
+}
+
+/// Where a type def and the instructions of its methods are stored,
+/// so that they can be decoded separately
+#[derive(ReadFromSection, WriteToSection, Debug, Clone)]
+pub struct TypeDefIndex {
+    /// Offset of the type def in the `TypeDefs` section
+    pub offset: u64,
+    /// Range of the instructions of each method in the `MethodBodies` section
+    pub method_bodies: Vec<Range<u64>>,
+}
+
+/// The tables which the tokens in type defs and instructions point into,
+/// and the type defs themselves, whether decoded up front or on demand
+pub trait AssemblyTables {
+    fn get_string(&self, string_ref: StringRef) -> binary_core::BinaryResult<&str>;
+    fn extra_header(&self) -> &ExtraHeader;
+    fn type_refs(&self) -> &[TypeRef];
+    fn type_specs(&self) -> &[TypeSpec];
+    fn method_specs(&self) -> &[MethodSpec];
+    fn assembly_refs(&self) -> &[AssemblyRef];
+    fn debug_info(&self) -> Option<&DebugInfo>;
+    fn type_def_count(&self) -> u32;
+    /// See [`LazyAssembly::type_def`] for the type defs which are decoded on demand
+    fn type_def(&self, index: u32) -> binary_core::BinaryResult<&TypeDef>;
+}
+
+impl<T: AssemblyTables + ?Sized> AssemblyTables for &T {
+    fn get_string(&self, string_ref: StringRef) -> binary_core::BinaryResult<&str> {
+        T::get_string(self, string_ref)
+    }
+    fn extra_header(&self) -> &ExtraHeader {
+        T::extra_header(self)
+    }
+    fn type_refs(&self) -> &[TypeRef] {
+        T::type_refs(self)
+    }
+    fn type_specs(&self) -> &[TypeSpec] {
+        T::type_specs(self)
+    }
+    fn method_specs(&self) -> &[MethodSpec] {
+        T::method_specs(self)
+    }
+    fn assembly_refs(&self) -> &[AssemblyRef] {
+        T::assembly_refs(self)
+    }
+    fn debug_info(&self) -> Option<&DebugInfo> {
+        T::debug_info(self)
+    }
+    fn type_def_count(&self) -> u32 {
+        T::type_def_count(self)
+    }
+    fn type_def(&self, index: u32) -> binary_core::BinaryResult<&TypeDef> {
+        T::type_def(self, index)
+    }
+}
+
+/// Optional parts of the format used by an assembly
+#[bitflags]
+#[repr(u32)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AssemblyFeature {
+    /// The `DebugInfo` section is not empty
+    DebugInfo,
 }
 
 #[repr(usize)]
-enum AssemblySectionId {
+pub(crate) enum AssemblySectionId {
     ExtraHeaderId = PredefinedSectionId::FirstNonStandard as usize,
     CustomAttributes,
     TypeRefs,
     TypeSpecs,
     MethodSpecs,
     TypeDefs,
+    DebugInfo,
+    AssemblyRefs,
+    /// The instructions of the methods are moved out of `TypeDefs`,
+    /// so that type defs can be decoded without them
+    TypeDefIndex,
+    MethodBodies,
 }
 
+/// Names of the sections of an assembly, indexed by their ids
+pub const SECTION_NAMES: [&str; AssemblySectionId::MethodBodies as usize + 1] = [
+    "String",
+    "ExtraHeader",
+    "CustomAttributes",
+    "TypeRefs",
+    "TypeSpecs",
+    "MethodSpecs",
+    "TypeDefs",
+    "DebugInfo",
+    "AssemblyRefs",
+    "TypeDefIndex",
+    "MethodBodies",
+];
+
 impl<'a> Assembly<'a> {
+    /// Borrows everything from the builder instead of cloning
     pub fn from_builder(assem: &'a AssemblyBuilder) -> Self {
         Self {
             extra_header: assem.extra_header,
             string_section: &assem.string_section,
-            custom_attributes: assem.custom_attributes.clone(),
-            type_refs: assem.type_refs.clone(),
-            type_specs: assem.type_specs.clone(),
-            method_specs: assem.method_specs.clone(),
-            type_defs: assem.type_defs.clone(),
+            custom_attributes: Cow::Borrowed(&assem.custom_attributes),
+            type_refs: Cow::Borrowed(&assem.type_refs),
+            type_specs: Cow::Borrowed(&assem.type_specs),
+            method_specs: Cow::Borrowed(&assem.method_specs),
+            type_defs: Cow::Borrowed(&assem.type_defs),
+            debug_info: assem.debug_info.as_ref().map(Cow::Borrowed),
+            assembly_refs: Cow::Borrowed(&assem.assembly_refs),
         }
     }
+    /// Decodes everything, see [`LazyAssembly`] for decoding on demand
     pub fn from_file(file: File<'a>) -> binary_core::BinaryResult<Self> {
-        let string_section = file
-            .get_section(PredefinedSectionId::String as usize)
-            .unwrap();
-        let extra_header_section = file
-            .get_section(AssemblySectionId::ExtraHeaderId as _)
-            .ok_or(Error::UnknownSection(AssemblySectionId::ExtraHeaderId as _))?;
-        let extra_header = ExtraHeader::read_from_section(&mut Cursor::new(extra_header_section))?;
-        let custom_attributes =
-            file.read_all::<CustomAttribute>(AssemblySectionId::CustomAttributes as _)?;
-        let type_refs = file.read_all::<TypeRef>(AssemblySectionId::TypeRefs as _)?;
-        let type_specs = file.read_all::<TypeSpec>(AssemblySectionId::TypeSpecs as _)?;
-        let method_specs = file.read_all::<MethodSpec>(AssemblySectionId::MethodSpecs as _)?;
-        let type_defs = file.read_all::<TypeDef>(AssemblySectionId::TypeDefs as _)?;
-
-        Ok(Self {
-            string_section: string_section,
-            extra_header,
-            custom_attributes,
-            type_refs,
-            type_specs,
-            method_specs,
-            type_defs,
-        })
+        LazyAssembly::from_file(file)?.into_assembly()
     }
     pub fn from_bytes(bytes: &'a [u8]) -> binary_core::BinaryResult<Self> {
         File::from_bytes(bytes).and_then(|file| Assembly::from_file(file))
     }
+    pub fn from_bytes_with(
+        bytes: &'a [u8],
+        verify_checksums: bool,
+    ) -> binary_core::BinaryResult<Self> {
+        File::from_bytes_with(bytes, verify_checksums).and_then(|file| Assembly::from_file(file))
+    }
     // pub fn from_path<P: AsRef<Path>>(p: P) -> binary_core::BinaryResult<(Assembly, Vec<u8>)> {
     //     let bytes = std::fs::read(p)?;
     //     let this = Assembly::from_bytes(&bytes)?;
@@ -121,51 +244,136 @@ impl<'a> Assembly<'a> {
             .get_string(string_ref)
             .ok_or_else(|| Error::UnknownStringRef(string_ref))
     }
+    /// The index in `type_defs` if the type is defined in this assembly
+    pub fn local_type_def(&self, ty: TypeToken) -> Option<u32> {
+        match ty.ty() {
+            TypeType::TypeDef => Some(ty.index()),
+            TypeType::TypeSpec => {
+                let spec = self.type_specs.get(ty.index() as usize)?;
+                self.local_type_def(spec.ty)
+            }
+            TypeType::TypeRef | TypeType::MethodGeneric | TypeType::TypeGeneric => None,
+        }
+    }
+    /// Methods are indexed the same as the method table built by the runtime,
+    /// where the instance methods of the parent come first
+    ///
+    /// `None` if the parent is in another assembly
+    pub fn method_table(&self, type_def: u32) -> Option<Vec<&Method>> {
+        self.method_table_with_depth(type_def, 0)
+    }
+    fn method_table_with_depth(&self, type_def: u32, depth: usize) -> Option<Vec<&Method>> {
+        // Cyclic inheritance
+        if depth > self.type_defs.len() {
+            return None;
+        }
+        let def = match self.type_defs.get(type_def as usize)? {
+            TypeDef::Class(def) => def,
+            TypeDef::Struct(def) => return Some(def.method_table.iter().collect()),
+            TypeDef::Interface(def) => return Some(def.method_table.iter().collect()),
+        };
+        let mut methods = match def.parent {
+            None => Vec::new(),
+            Some(parent) => {
+                let parent = self.local_type_def(parent)?;
+                let mut methods = self.method_table_with_depth(parent, depth + 1)?;
+                // The runtime stops on the first static method
+                let instance_count = methods.iter().take_while(|x| !x.attr.is_static()).count();
+                methods.truncate(instance_count);
+                methods
+            }
+        };
+        for method in &def.method_table {
+            match method.attr.overrides {
+                Some(overrides) => *methods.get_mut(overrides as usize)? = method,
+                None => methods.push(method),
+            }
+        }
+        Some(methods)
+    }
+}
+
+impl AssemblyTables for Assembly<'_> {
+    fn get_string(&self, string_ref: StringRef) -> binary_core::BinaryResult<&str> {
+        Assembly::get_string(self, string_ref)
+    }
+    fn extra_header(&self) -> &ExtraHeader {
+        &self.extra_header
+    }
+    fn type_refs(&self) -> &[TypeRef] {
+        &self.type_refs
+    }
+    fn type_specs(&self) -> &[TypeSpec] {
+        &self.type_specs
+    }
+    fn method_specs(&self) -> &[MethodSpec] {
+        &self.method_specs
+    }
+    fn assembly_refs(&self) -> &[AssemblyRef] {
+        &self.assembly_refs
+    }
+    fn debug_info(&self) -> Option<&DebugInfo> {
+        self.debug_info.as_deref()
+    }
+    fn type_def_count(&self) -> u32 {
+        self.type_defs.len() as u32
+    }
+    fn type_def(&self, index: u32) -> binary_core::BinaryResult<&TypeDef> {
+        self.type_defs
+            .get(index as usize)
+            .ok_or(Error::IndexOutOfRange)
+    }
 }
 
 impl AssemblyBuilder {
     pub fn new(name: &str) -> Self {
         let mut string_section = SectionBuilder::new();
         Self {
-            extra_header: ExtraHeader {
-                name: string_section.as_string_section_mut().add_string(name),
-            },
+            extra_header: ExtraHeader::new(string_section.as_string_section_mut().add_string(name)),
             string_section,
             custom_attributes: Vec::new(),
             type_refs: Vec::new(),
             type_specs: Vec::new(),
             method_specs: Vec::new(),
             type_defs: Vec::new(),
+            debug_info: None,
+            assembly_refs: Vec::new(),
         }
     }
     pub fn from_file(file: FileBuilder) -> binary_core::BinaryResult<Self> {
         let string_section = file
             .get_predefined_section(PredefinedSectionId::String)
             .unwrap();
-        let extra_header_section = file
-            .get_section(AssemblySectionId::ExtraHeaderId as _)
-            .ok_or(Error::UnknownSection(AssemblySectionId::ExtraHeaderId as _))?;
-        let extra_header = ExtraHeader::read_from_section(&mut Cursor::new(extra_header_section))?;
-        let custom_attributes =
-            file.read_all::<CustomAttribute>(AssemblySectionId::CustomAttributes as _)?;
-        let type_refs = file.read_all::<TypeRef>(AssemblySectionId::TypeRefs as _)?;
-        let type_specs = file.read_all::<TypeSpec>(AssemblySectionId::TypeSpecs as _)?;
-        let method_specs = file.read_all::<MethodSpec>(AssemblySectionId::MethodSpecs as _)?;
-        let type_defs = file.read_all::<TypeDef>(AssemblySectionId::TypeDefs as _)?;
+        let lazy =
+            LazyAssembly::from_sections(file.version, |id| file.get_section(id).map(|x| &**x))?;
+        let type_defs = lazy.decode_type_defs()?;
+        // So that strings added later are deduplicated against the existing ones
+        let mut string_section = string_section.clone();
+        string_section.as_string_section_mut().build_index();
 
         Ok(Self {
-            string_section: string_section.clone(),
-            extra_header,
-            custom_attributes,
-            type_refs,
-            type_specs,
-            method_specs,
+            string_section,
+            extra_header: lazy.extra_header,
+            custom_attributes: lazy.custom_attributes,
+            type_refs: lazy.type_refs,
+            type_specs: lazy.type_specs,
+            method_specs: lazy.method_specs,
             type_defs,
+            debug_info: lazy.debug_info,
+            assembly_refs: lazy.assembly_refs,
         })
     }
     pub fn from_bytes(bytes: Vec<u8>) -> binary_core::BinaryResult<Self> {
         FileBuilder::from_bytes(bytes).and_then(|file| AssemblyBuilder::from_file(file))
     }
+    /// See [`binary_core::file::FileParser::from_bytes_with`]
+    pub fn from_bytes_with(
+        bytes: Vec<u8>,
+        verify_checksums: bool,
+    ) -> binary_core::BinaryResult<Self> {
+        FileBuilder::from_bytes_with(bytes, verify_checksums)
+            .and_then(|file| AssemblyBuilder::from_file(file))
+    }
     pub fn from_path<P: AsRef<Path>>(p: P) -> binary_core::BinaryResult<Self> {
         let bytes = std::fs::read(p)?;
         AssemblyBuilder::from_bytes(bytes)
@@ -189,21 +397,46 @@ impl AssemblyBuilder {
             (self.type_refs.len() - 1) as u32
         }
     }
+    /// Returns the index of the existing ref if `name` is already referenced
+    pub fn add_assembly_ref(&mut self, name: &str, requirement: VersionRequirement) -> u32 {
+        let name = self.add_string(name);
+        if let Some(pos) = self.assembly_refs.iter().position(|x| x.name == name) {
+            pos as u32
+        } else {
+            self.assembly_refs.push(AssemblyRef { name, requirement });
+            (self.assembly_refs.len() - 1) as u32
+        }
+    }
     pub fn into_file(self) -> binary_core::BinaryResult<FileBuilder> {
         let mut file = FileBuilder::new();
         unsafe {
             file.set_string_section(self.string_section);
         }
+        let mut extra_header = self.extra_header;
+        if self.debug_info.is_some() {
+            extra_header.features |= AssemblyFeature::DebugInfo as u32;
+        }
         let mut extra_header_section = SectionBuilder::new();
-        self.extra_header
-            .write_to_section(&mut extra_header_section.construct_mut_vec_cursor())?;
+        extra_header.write_to_section(&mut extra_header_section.construct_mut_vec_cursor())?;
         file.add_section(extra_header_section);
 
         file.add_section(SectionBuilder::new()); // CustomAttributes
         file.add_section(SectionBuilder::new()); // TypeRefs
         file.add_section(SectionBuilder::new()); // TypeSpecs
         file.add_section(SectionBuilder::new()); // MethodSpecs
-        file.add_section(SectionBuilder::new()); // TypeDefs
+        let (type_defs_section, type_def_index_section, method_bodies_section) =
+            write_type_defs(self.type_defs)?;
+        file.add_section(type_defs_section);
+
+        // Always added so that the ids of the sections after it are kept
+        let mut debug_info_section = SectionBuilder::new();
+        if let Some(debug_info) = &self.debug_info {
+            debug_info.write_to_section(&mut debug_info_section.construct_mut_vec_cursor())?;
+        }
+        file.add_section(debug_info_section);
+        file.add_section(SectionBuilder::new()); // AssemblyRefs
+        file.add_section(type_def_index_section);
+        file.add_section(method_bodies_section);
 
         file.write_all(
             AssemblySectionId::CustomAttributes as usize,
@@ -212,7 +445,7 @@ impl AssemblyBuilder {
         file.write_all(AssemblySectionId::TypeRefs as _, &self.type_refs)?;
         file.write_all(AssemblySectionId::TypeSpecs as _, &self.type_specs)?;
         file.write_all(AssemblySectionId::MethodSpecs as _, &self.method_specs)?;
-        file.write_all(AssemblySectionId::TypeDefs as _, &self.type_defs)?;
+        file.write_all(AssemblySectionId::AssemblyRefs as _, &self.assembly_refs)?;
 
         Ok(file)
     }
@@ -221,3 +454,53 @@ impl AssemblyBuilder {
         file.write_to(w).map_err(From::from)
     }
 }
+
+/// Returns the `TypeDefs`, `TypeDefIndex` and `MethodBodies` sections
+fn write_type_defs(
+    type_defs: Vec<TypeDef>,
+) -> binary_core::BinaryResult<(SectionBuilder, SectionBuilder, SectionBuilder)> {
+    let mut type_defs_section = SectionBuilder::new();
+    let mut type_def_index_section = SectionBuilder::new();
+    let mut method_bodies_section = SectionBuilder::new();
+    let mut type_defs_cursor = type_defs_section.construct_mut_vec_cursor();
+    let mut type_def_index_cursor = type_def_index_section.construct_mut_vec_cursor();
+    let mut method_bodies_cursor = method_bodies_section.construct_mut_vec_cursor();
+    for mut type_def in type_defs {
+        let method_bodies = type_def
+            .method_table_mut()
+            .iter_mut()
+            .map(|method| {
+                let start = method_bodies_cursor.position();
+                std::mem::take(&mut method.instructions)
+                    .write_to_section(&mut method_bodies_cursor)?;
+                Ok(Range {
+                    start,
+                    end: method_bodies_cursor.position(),
+                })
+            })
+            .collect::<binary_core::BinaryResult<Vec<_>>>()?;
+        TypeDefIndex {
+            offset: type_defs_cursor.position(),
+            method_bodies,
+        }
+        .write_to_section(&mut type_def_index_cursor)?;
+        type_def.write_to_section(&mut type_defs_cursor)?;
+    }
+    Ok((
+        type_defs_section,
+        type_def_index_section,
+        method_bodies_section,
+    ))
+}
+
+/// The section is missing in assemblies emitted without debug info
+pub(crate) fn read_debug_info(
+    section: Option<&Section>,
+) -> binary_core::BinaryResult<Option<DebugInfo>> {
+    match section {
+        Some(section) if section.len() != 0 => {
+            DebugInfo::read_from_section(&mut Cursor::new(section)).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
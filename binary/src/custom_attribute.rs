@@ -1,4 +1,8 @@
-use binary_core::traits::StringRef;
+use binary_core::{
+    BinaryResult, Error,
+    section::Section,
+    traits::{ReadFromSection, StringRef},
+};
 use global::WithType;
 use proc_macros::{ReadFromSection, WriteToSection};
 
@@ -18,17 +22,101 @@ pub enum Integer {
     ULong(u64),
 }
 
-#[derive(Debug, Clone, WithType, ReadFromSection, WriteToSection)]
+/// Read by hand to bound the nesting of objects and arrays
+#[derive(Debug, Clone, WithType, WriteToSection)]
 #[with_type(repr = u8)]
 #[with_type(derive = (Clone, Copy, ReadFromSection, WriteToSection))]
 pub enum CustomAttributeValue {
     Boolean(bool),
     Char(char),
     Integer(Integer),
-    // TODO: float, double and System.Object are not supported yet
     String(StringRef),
     SystemType(TypeToken),
-    PureEnum { ty: TypeToken, val: Integer },
+    PureEnum {
+        ty: TypeToken,
+        val: Integer,
+    },
+    Single(f32),
+    Double(f64),
+    /// Passed as `System.Object`, `ty` is the type of the boxed value
+    Object {
+        ty: TypeToken,
+        val: Box<Self>,
+    },
+    Array(Vec<CustomAttributeValue>),
+}
+
+impl CustomAttributeValue {
+    /// Deeper values are rejected when reading,
+    /// so that a malformed file cannot overflow the stack
+    pub const MAX_DEPTH: usize = 64;
+
+    fn read_with_depth(cursor: &mut std::io::Cursor<&Section>, depth: usize) -> BinaryResult<Self> {
+        if depth > Self::MAX_DEPTH {
+            return Err(Error::WrongFormat);
+        }
+        let value = match CustomAttributeValueType::read_from_section(cursor)? {
+            CustomAttributeValueType::Boolean => Self::Boolean(bool::read_from_section(cursor)?),
+            CustomAttributeValueType::Char => Self::Char(char::read_from_section(cursor)?),
+            CustomAttributeValueType::Integer => Self::Integer(Integer::read_from_section(cursor)?),
+            CustomAttributeValueType::String => Self::String(StringRef::read_from_section(cursor)?),
+            CustomAttributeValueType::SystemType => {
+                Self::SystemType(TypeToken::read_from_section(cursor)?)
+            }
+            CustomAttributeValueType::PureEnum => Self::PureEnum {
+                ty: TypeToken::read_from_section(cursor)?,
+                val: Integer::read_from_section(cursor)?,
+            },
+            CustomAttributeValueType::Single => Self::Single(f32::read_from_section(cursor)?),
+            CustomAttributeValueType::Double => Self::Double(f64::read_from_section(cursor)?),
+            CustomAttributeValueType::Object => Self::Object {
+                ty: TypeToken::read_from_section(cursor)?,
+                val: Box::new(Self::read_with_depth(cursor, depth + 1)?),
+            },
+            CustomAttributeValueType::Array => {
+                let len = u64::read_from_section(cursor)?;
+                let values = (0..len)
+                    .map(|_| Self::read_with_depth(cursor, depth + 1))
+                    .collect::<BinaryResult<_>>()?;
+                Self::Array(values)
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl ReadFromSection for CustomAttributeValue {
+    fn read_from_section(cursor: &mut std::io::Cursor<&Section>) -> BinaryResult<Self> {
+        Self::read_with_depth(cursor, 0)
+    }
+}
+
+/// Sets a field or a property of the attribute after it is constructed
+#[derive(Debug, Clone, WithType, ReadFromSection, WriteToSection)]
+#[with_type(repr = u8)]
+#[with_type(derive = (Clone, Copy, ReadFromSection, WriteToSection))]
+pub enum NamedArgument {
+    Field {
+        name: StringRef,
+        value: CustomAttributeValue,
+    },
+    Property {
+        name: StringRef,
+        value: CustomAttributeValue,
+    },
+}
+
+impl NamedArgument {
+    pub fn name(&self) -> StringRef {
+        match self {
+            Self::Field { name, .. } | Self::Property { name, .. } => *name,
+        }
+    }
+    pub fn value(&self) -> &CustomAttributeValue {
+        match self {
+            Self::Field { value, .. } | Self::Property { value, .. } => value,
+        }
+    }
 }
 
 #[derive(Debug, Clone, ReadFromSection, WriteToSection)]
@@ -36,6 +124,24 @@ pub struct CustomAttribute {
     pub ty: TypeToken,
     pub ctor_name: MethodToken,
     pub positional_args: Vec<CustomAttributeValue>,
+    pub named_args: Vec<NamedArgument>,
+}
+
+/// Custom attributes carry named arguments since 0.6 of the file format
+pub(crate) const NAMED_ARGS_VERSION: [u8; 2] = [0x00, 0x06];
+
+/// The layout of [`CustomAttribute`] before [`NAMED_ARGS_VERSION`]
+#[derive(Debug, Clone, ReadFromSection, WriteToSection)]
+pub(crate) struct CustomAttributeWithoutNamedArgs {
+    pub ty: TypeToken,
+    pub ctor_name: MethodToken,
+    pub positional_args: Vec<CustomAttributeValue>,
+}
+
+impl From<CustomAttributeWithoutNamedArgs> for CustomAttribute {
+    fn from(value: CustomAttributeWithoutNamedArgs) -> Self {
+        Self::new(value.ty, value.ctor_name, value.positional_args, Vec::new())
+    }
 }
 
 impl CustomAttribute {
@@ -43,11 +149,13 @@ impl CustomAttribute {
         ty: TypeToken,
         ctor_name: MethodToken,
         positional_args: Vec<CustomAttributeValue>,
+        named_args: Vec<NamedArgument>,
     ) -> Self {
         Self {
             ty,
             ctor_name,
             positional_args,
+            named_args,
         }
     }
 }
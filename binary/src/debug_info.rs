@@ -0,0 +1,69 @@
+use binary_core::traits::StringRef;
+use proc_macros::{ReadFromSection, WriteToSection};
+
+/// Source-level information of an assembly, which is optional
+/// and only used for diagnostics
+#[derive(Clone, Debug, Default, ReadFromSection, WriteToSection)]
+pub struct DebugInfo {
+    pub documents: Vec<Document>,
+    pub methods: Vec<MethodDebugInfo>,
+}
+
+/// A source file
+#[derive(Clone, Debug, ReadFromSection, WriteToSection, PartialEq)]
+pub struct Document {
+    pub path: StringRef,
+}
+
+#[derive(Clone, Debug, ReadFromSection, WriteToSection)]
+pub struct MethodDebugInfo {
+    /// Index of the type in `type_defs`
+    pub type_def: u32,
+    /// Index of the method in the method table of the type
+    pub method: u32,
+
+    /// Sorted by `instruction`
+    pub sequence_points: Vec<SequencePoint>,
+    /// Indexed the same as the args
+    pub arg_names: Vec<StringRef>,
+    /// Indexed the same as the registers
+    pub local_names: Vec<StringRef>,
+}
+
+/// Instructions from `instruction` up to the next sequence point
+/// are compiled from this position
+#[derive(Clone, Copy, Debug, ReadFromSection, WriteToSection, PartialEq, Eq)]
+pub struct SequencePoint {
+    pub instruction: u64,
+    /// Index in [`DebugInfo::documents`]
+    pub document: u32,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl DebugInfo {
+    pub fn add_document(&mut self, doc: Document) -> u32 {
+        if let Some(pos) = self.documents.iter().position(|x| x == &doc) {
+            pos as u32
+        } else {
+            self.documents.push(doc);
+            (self.documents.len() - 1) as u32
+        }
+    }
+
+    pub fn get_method(&self, type_def: u32, method: u32) -> Option<&MethodDebugInfo> {
+        self.methods
+            .iter()
+            .find(|x| x.type_def == type_def && x.method == method)
+    }
+}
+
+impl MethodDebugInfo {
+    /// The sequence point covering the instruction at `pc`
+    pub fn sequence_point_at(&self, pc: u64) -> Option<&SequencePoint> {
+        let pos = self
+            .sequence_points
+            .partition_point(|x| x.instruction <= pc);
+        pos.checked_sub(1).map(|x| &self.sequence_points[x])
+    }
+}
@@ -0,0 +1,153 @@
+//! Checks of the [`AssemblyRef`](crate::assembly::AssemblyRef)s of a set of assemblies,
+//! so that missing or mismatched assemblies are reported before any of them is loaded
+
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt::Display,
+};
+
+use binary_core::{BinaryResult, Version, VersionRequirement};
+use derive_more::Display;
+
+use crate::assembly::AssemblyTables;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug, PartialEq, Eq, Display)]
+pub enum DependencyProblem {
+    #[display("{assembly} requires {dependency} {requirement}, which is not provided")]
+    Missing {
+        assembly: String,
+        dependency: String,
+        requirement: VersionRequirement,
+    },
+    #[display("{assembly} requires {dependency} {requirement}, found {found}")]
+    Unsatisfied {
+        assembly: String,
+        dependency: String,
+        requirement: VersionRequirement,
+        found: Version,
+    },
+    #[display("{name} is provided twice, as {first} and {second}")]
+    Conflict {
+        name: String,
+        first: Version,
+        second: Version,
+    },
+}
+
+/// All the problems found in the dependencies
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyError(pub Vec<DependencyProblem>);
+
+impl Display for DependencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} problems found in the dependencies:", self.0.len())?;
+        for problem in &self.0 {
+            writeln!(f, "    {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+impl From<DependencyError> for binary_core::Error {
+    fn from(value: DependencyError) -> Self {
+        Self::Custom(value.into())
+    }
+}
+
+/// `loaded` are the names and versions of the assemblies which are already available,
+/// they provide dependencies the same way as `assemblies`
+///
+/// Type refs into other assemblies must be covered by the assembly refs,
+/// except the ones into `core_assembly`, which every assembly depends on
+pub fn find_dependency_problems(
+    assemblies: &[impl AssemblyTables],
+    loaded: impl IntoIterator<Item = (String, Version)>,
+    core_assembly: &str,
+) -> BinaryResult<Vec<DependencyProblem>> {
+    let mut problems = Vec::new();
+    let mut provided: HashMap<String, Version> = loaded.into_iter().collect();
+    for assembly in assemblies {
+        let name = assembly.get_string(assembly.extra_header().name)?;
+        let version = assembly.extra_header().version;
+        match provided.entry(name.to_owned()) {
+            Entry::Occupied(entry) => problems.push(DependencyProblem::Conflict {
+                name: name.to_owned(),
+                first: *entry.get(),
+                second: version,
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(version);
+            }
+        }
+    }
+
+    for assembly in assemblies {
+        let name = assembly.get_string(assembly.extra_header().name)?;
+        for assembly_ref in assembly.assembly_refs() {
+            let dependency = assembly.get_string(assembly_ref.name)?;
+            let requirement = assembly_ref.requirement;
+            match provided.get(dependency) {
+                None => problems.push(DependencyProblem::Missing {
+                    assembly: name.to_owned(),
+                    dependency: dependency.to_owned(),
+                    requirement,
+                }),
+                Some(&found) if !requirement.matches(found) => {
+                    problems.push(DependencyProblem::Unsatisfied {
+                        assembly: name.to_owned(),
+                        dependency: dependency.to_owned(),
+                        requirement,
+                        found,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut undeclared = Vec::new();
+        for type_ref in assembly.type_refs() {
+            let dependency = assembly.get_string(type_ref.assembly)?;
+            if dependency == name || dependency == core_assembly || undeclared.contains(&dependency)
+            {
+                continue;
+            }
+            if !assembly
+                .assembly_refs()
+                .iter()
+                .any(|x| assembly.get_string(x.name).is_ok_and(|x| x == dependency))
+            {
+                undeclared.push(dependency);
+            }
+        }
+        problems.extend(
+            undeclared
+                .into_iter()
+                .map(|dependency| DependencyProblem::Missing {
+                    assembly: name.to_owned(),
+                    dependency: dependency.to_owned(),
+                    requirement: VersionRequirement::ANY,
+                }),
+        );
+    }
+
+    Ok(problems)
+}
+
+/// Same as [`find_dependency_problems`], but the problems are returned as a [`DependencyError`]
+pub fn check_dependencies(
+    assemblies: &[impl AssemblyTables],
+    loaded: impl IntoIterator<Item = (String, Version)>,
+    core_assembly: &str,
+) -> BinaryResult<()> {
+    let problems = find_dependency_problems(assemblies, loaded, core_assembly)?;
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(DependencyError(problems).into())
+    }
+}
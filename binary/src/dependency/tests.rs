@@ -0,0 +1,122 @@
+use binary_core::{Version, VersionRequirement, VersionRequirementKind};
+
+use crate::{
+    assembly::{Assembly, AssemblyBuilder},
+    ty::TypeRef,
+};
+
+use super::{DependencyProblem, find_dependency_problems};
+
+fn assembly(name: &str, version: Version, refs: &[(&str, &str)]) -> AssemblyBuilder {
+    let mut assembly = AssemblyBuilder::new(name);
+    assembly.extra_header.version = version;
+    for (name, requirement) in refs {
+        assembly.add_assembly_ref(name, requirement.parse().unwrap());
+    }
+    assembly
+}
+
+fn problems(assemblies: &[AssemblyBuilder], loaded: &[(&str, Version)]) -> Vec<DependencyProblem> {
+    let assemblies = assemblies
+        .iter()
+        .map(Assembly::from_builder)
+        .collect::<Vec<_>>();
+    let loaded = loaded
+        .iter()
+        .map(|(name, version)| (name.to_string(), *version));
+    find_dependency_problems(&assemblies, loaded, "!").unwrap()
+}
+
+#[test]
+fn satisfied() {
+    let assemblies = [
+        assembly(
+            "A",
+            Version::new(1, 0, 0),
+            &[("B", "^0.2.0"), ("Core", ">=0.1.0")],
+        ),
+        assembly("B", Version::new(0, 2, 5), &[("Core", "=0.1.0")]),
+    ];
+    assert_eq!(
+        problems(&assemblies, &[("Core", Version::new(0, 1, 0))]),
+        vec![]
+    );
+}
+
+#[test]
+fn missing_and_unsatisfied() {
+    let assemblies = [
+        assembly(
+            "A",
+            Version::new(1, 0, 0),
+            &[("B", "^0.3.0"), ("C", "1.0.0")],
+        ),
+        assembly("B", Version::new(0, 2, 5), &[]),
+    ];
+    assert_eq!(
+        problems(&assemblies, &[]),
+        vec![
+            DependencyProblem::Unsatisfied {
+                assembly: "A".to_owned(),
+                dependency: "B".to_owned(),
+                requirement: VersionRequirement::new(
+                    VersionRequirementKind::Compatible,
+                    Version::new(0, 3, 0)
+                ),
+                found: Version::new(0, 2, 5),
+            },
+            DependencyProblem::Missing {
+                assembly: "A".to_owned(),
+                dependency: "C".to_owned(),
+                requirement: VersionRequirement::new(
+                    VersionRequirementKind::Compatible,
+                    Version::new(1, 0, 0)
+                ),
+            },
+        ]
+    );
+}
+
+#[test]
+fn conflict() {
+    let assemblies = [
+        assembly("A", Version::new(1, 0, 0), &[]),
+        assembly("A", Version::new(2, 0, 0), &[]),
+        assembly("Core", Version::new(0, 1, 0), &[]),
+    ];
+    assert_eq!(
+        problems(&assemblies, &[("Core", Version::new(0, 1, 0))]),
+        vec![
+            DependencyProblem::Conflict {
+                name: "A".to_owned(),
+                first: Version::new(1, 0, 0),
+                second: Version::new(2, 0, 0),
+            },
+            DependencyProblem::Conflict {
+                name: "Core".to_owned(),
+                first: Version::new(0, 1, 0),
+                second: Version::new(0, 1, 0),
+            },
+        ]
+    );
+}
+
+#[test]
+fn undeclared_type_ref() {
+    let mut a = assembly("A", Version::new(1, 0, 0), &[("B", "^0.2.0")]);
+    for name in ["!", "A", "B", "C", "C"] {
+        let type_ref = TypeRef {
+            assembly: a.add_string(name),
+            index: 0,
+        };
+        a.type_refs.push(type_ref);
+    }
+    assert_eq!(
+        problems(&[a], &[("B", Version::new(0, 2, 0))]),
+        vec![DependencyProblem::Missing {
+            assembly: "A".to_owned(),
+            dependency: "C".to_owned(),
+            requirement: VersionRequirement::ANY,
+        }]
+    );
+}
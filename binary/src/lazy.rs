@@ -0,0 +1,265 @@
+//! Reading an assembly without decoding all of it up front
+//!
+//! Everything is borrowed from the bytes of the file, which could be memory-mapped,
+//! or owned along with it by a [`SharedLazyAssembly`].
+//! Type defs are decoded the first time they are requested, without the instructions
+//! of their methods, which are only decoded by [`LazyAssembly::instructions`]
+
+use std::{
+    borrow::Cow,
+    io::Cursor,
+    range::Range,
+    sync::{Arc, OnceLock},
+};
+
+use binary_core::{
+    BinaryResult, Error,
+    file::{File, PredefinedSectionId},
+    section::Section,
+    traits::{ReadFromSection, StringRef},
+};
+use derive_more::Debug;
+
+use crate::{
+    assembly::{
+        Assembly, AssemblyRef, AssemblySectionId, AssemblyTables, ExtraHeader, TypeDefIndex,
+        read_debug_info,
+    },
+    custom_attribute::{CustomAttribute, CustomAttributeWithoutNamedArgs, NAMED_ARGS_VERSION},
+    debug_info::DebugInfo,
+    ty::{BinaryInstruction, Method, MethodSpec, TypeDef, TypeRef, TypeSpec},
+};
+
+#[derive(Debug)]
+pub struct LazyAssembly<'a> {
+    pub extra_header: ExtraHeader,
+    pub string_section: &'a Section,
+    pub custom_attributes: Vec<CustomAttribute>,
+    pub type_refs: Vec<TypeRef>,
+    pub type_specs: Vec<TypeSpec>,
+    pub method_specs: Vec<MethodSpec>,
+    pub debug_info: Option<DebugInfo>,
+    pub assembly_refs: Vec<AssemblyRef>,
+
+    type_defs_section: &'a Section,
+    method_bodies_section: &'a Section,
+    type_def_index: Vec<TypeDefIndex>,
+    type_defs: Vec<OnceLock<TypeDef>>,
+}
+
+impl<'a> LazyAssembly<'a> {
+    pub fn from_file(file: File<'a>) -> BinaryResult<Self> {
+        Self::from_sections(file.version(), |id| file.get_section(id))
+    }
+    pub fn from_bytes(bytes: &'a [u8]) -> BinaryResult<Self> {
+        File::from_bytes(bytes).and_then(Self::from_file)
+    }
+    /// See [`binary_core::file::FileParser::from_bytes_with`]
+    pub fn from_bytes_with(bytes: &'a [u8], verify_checksums: bool) -> BinaryResult<Self> {
+        File::from_bytes_with(bytes, verify_checksums).and_then(Self::from_file)
+    }
+    /// `version` is the version of the file format, see [`File::version`]
+    pub(crate) fn from_sections(
+        version: [u8; 2],
+        get_section: impl Fn(usize) -> Option<&'a Section>,
+    ) -> BinaryResult<Self> {
+        let section = |id: AssemblySectionId| {
+            let id = id as usize;
+            get_section(id).ok_or(Error::UnknownSection(id))
+        };
+        let string_section = get_section(PredefinedSectionId::String as usize).unwrap();
+        let extra_header = ExtraHeader::read_from_section(&mut Cursor::new(section(
+            AssemblySectionId::ExtraHeaderId,
+        )?))?;
+        extra_header.features()?;
+        let type_def_index =
+            section(AssemblySectionId::TypeDefIndex)?.read_all::<TypeDefIndex>()?;
+        let custom_attributes = section(AssemblySectionId::CustomAttributes)?;
+        let custom_attributes = if version < NAMED_ARGS_VERSION {
+            custom_attributes
+                .read_all::<CustomAttributeWithoutNamedArgs>()?
+                .into_iter()
+                .map(From::from)
+                .collect()
+        } else {
+            custom_attributes.read_all()?
+        };
+
+        Ok(Self {
+            extra_header,
+            string_section,
+            custom_attributes,
+            type_refs: section(AssemblySectionId::TypeRefs)?.read_all()?,
+            type_specs: section(AssemblySectionId::TypeSpecs)?.read_all()?,
+            method_specs: section(AssemblySectionId::MethodSpecs)?.read_all()?,
+            debug_info: read_debug_info(get_section(AssemblySectionId::DebugInfo as usize))?,
+            assembly_refs: section(AssemblySectionId::AssemblyRefs)?.read_all()?,
+
+            type_defs_section: section(AssemblySectionId::TypeDefs)?,
+            method_bodies_section: section(AssemblySectionId::MethodBodies)?,
+            type_defs: std::iter::repeat_with(OnceLock::new)
+                .take(type_def_index.len())
+                .collect(),
+            type_def_index,
+        })
+    }
+
+    pub fn get_string(&self, string_ref: StringRef) -> BinaryResult<&'a str> {
+        self.string_section
+            .as_string_section()
+            .get_string(string_ref)
+            .ok_or_else(|| Error::UnknownStringRef(string_ref))
+    }
+
+    pub fn type_def_count(&self) -> u32 {
+        self.type_def_index.len() as u32
+    }
+    /// Decoded the first time it is requested,
+    /// the instructions of its methods are left empty
+    pub fn type_def(&self, index: u32) -> BinaryResult<&TypeDef> {
+        let cell = self
+            .type_defs
+            .get(index as usize)
+            .ok_or(Error::IndexOutOfRange)?;
+        if let Some(type_def) = cell.get() {
+            return Ok(type_def);
+        }
+        let type_def = self.decode_type_def(index)?;
+        Ok(cell.get_or_init(|| type_def))
+    }
+    /// The instructions are left empty, see [`Self::instructions`]
+    pub fn method(&self, type_def: u32, method: u32) -> BinaryResult<&Method> {
+        self.type_def(type_def)?
+            .method_table()
+            .get(method as usize)
+            .ok_or(Error::IndexOutOfRange)
+    }
+    /// The encoded instructions of the method, without copying them
+    pub fn method_body(&self, type_def: u32, method: u32) -> BinaryResult<&'a Section> {
+        let range = self
+            .type_def_index
+            .get(type_def as usize)
+            .and_then(|x| x.method_bodies.get(method as usize))
+            .ok_or(Error::IndexOutOfRange)?;
+        method_body(self.method_bodies_section, *range)
+    }
+    /// Decodes the instructions of the method every time it is called
+    pub fn instructions(&self, type_def: u32, method: u32) -> BinaryResult<Vec<BinaryInstruction>> {
+        Vec::read_from_section(&mut Cursor::new(self.method_body(type_def, method)?))
+    }
+
+    /// Decodes everything, including the instructions
+    pub fn into_assembly(self) -> BinaryResult<Assembly<'a>> {
+        let type_defs = self.decode_type_defs()?;
+        Ok(Assembly {
+            extra_header: self.extra_header,
+            string_section: self.string_section,
+            custom_attributes: Cow::Owned(self.custom_attributes),
+            type_refs: Cow::Owned(self.type_refs),
+            type_specs: Cow::Owned(self.type_specs),
+            method_specs: Cow::Owned(self.method_specs),
+            type_defs: Cow::Owned(type_defs),
+            debug_info: self.debug_info.map(Cow::Owned),
+            assembly_refs: Cow::Owned(self.assembly_refs),
+        })
+    }
+
+    /// Not cached, unlike [`Self::type_def`]
+    fn decode_type_def(&self, index: u32) -> BinaryResult<TypeDef> {
+        let offset = self
+            .type_def_index
+            .get(index as usize)
+            .ok_or(Error::IndexOutOfRange)?
+            .offset;
+        let mut cursor = Cursor::new(self.type_defs_section);
+        cursor.set_position(offset);
+        TypeDef::read_from_section(&mut cursor)
+    }
+    /// With the instructions of the methods
+    pub(crate) fn decode_type_defs(&self) -> BinaryResult<Vec<TypeDef>> {
+        (0..self.type_def_count())
+            .map(|index| {
+                let mut type_def = self.decode_type_def(index)?;
+                for (method, m) in type_def.method_table_mut().iter_mut().enumerate() {
+                    m.instructions = self.instructions(index, method as u32)?;
+                }
+                Ok(type_def)
+            })
+            .collect()
+    }
+}
+
+impl AssemblyTables for LazyAssembly<'_> {
+    fn get_string(&self, string_ref: StringRef) -> BinaryResult<&str> {
+        LazyAssembly::get_string(self, string_ref)
+    }
+    fn extra_header(&self) -> &ExtraHeader {
+        &self.extra_header
+    }
+    fn type_refs(&self) -> &[TypeRef] {
+        &self.type_refs
+    }
+    fn type_specs(&self) -> &[TypeSpec] {
+        &self.type_specs
+    }
+    fn method_specs(&self) -> &[MethodSpec] {
+        &self.method_specs
+    }
+    fn assembly_refs(&self) -> &[AssemblyRef] {
+        &self.assembly_refs
+    }
+    fn debug_info(&self) -> Option<&DebugInfo> {
+        self.debug_info.as_ref()
+    }
+    fn type_def_count(&self) -> u32 {
+        LazyAssembly::type_def_count(self)
+    }
+    fn type_def(&self, index: u32) -> BinaryResult<&TypeDef> {
+        LazyAssembly::type_def(self, index)
+    }
+}
+
+/// A [`LazyAssembly`] which owns the bytes it is read from,
+/// so that it can be kept around without leaking them
+#[derive(Debug)]
+pub struct SharedLazyAssembly {
+    /// Borrows from `bytes`, declared first to be dropped before them
+    lazy: LazyAssembly<'static>,
+    #[debug(skip)]
+    bytes: Arc<[u8]>,
+}
+
+impl SharedLazyAssembly {
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> BinaryResult<Self> {
+        Self::from_bytes_with(bytes, true)
+    }
+    /// See [`binary_core::file::FileParser::from_bytes_with`]
+    pub fn from_bytes_with(
+        bytes: impl Into<Arc<[u8]>>,
+        verify_checksums: bool,
+    ) -> BinaryResult<Self> {
+        let bytes = bytes.into();
+        // SAFETY: The bytes stay at the same place as long as `self.bytes` is alive,
+        // and `lazy` is only lent out for the lifetime of `self`
+        let borrowed = unsafe { &*Arc::as_ptr(&bytes) };
+        Ok(Self {
+            lazy: LazyAssembly::from_bytes_with(borrowed, verify_checksums)?,
+            bytes,
+        })
+    }
+
+    pub fn get(&self) -> &LazyAssembly<'_> {
+        &self.lazy
+    }
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+fn method_body(method_bodies: &Section, range: Range<u64>) -> BinaryResult<&Section> {
+    method_bodies
+        .as_bytes()
+        .get((range.start as usize)..(range.end as usize))
+        .map(Section::with_bytes)
+        .ok_or(Error::IndexOutOfRange)
+}
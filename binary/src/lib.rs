@@ -13,7 +13,11 @@ pub mod item_token;
 
 pub mod assembly;
 pub mod custom_attribute;
+pub mod debug_info;
+pub mod dependency;
+pub mod lazy;
 pub mod ty;
+pub mod verifier;
 
 #[cfg(test)]
 mod tests;
@@ -25,7 +29,9 @@ pub mod prelude {
     };
 
     pub use crate::assembly::Assembly;
-    pub use crate::custom_attribute::{CustomAttribute, CustomAttributeValue, Integer};
+    pub use crate::custom_attribute::{
+        CustomAttribute, CustomAttributeValue, Integer, NamedArgument,
+    };
     pub use crate::ty::{ClassDef, StructDef, TypeDef, TypeRef, TypeSpec};
     pub use binary_core::{BinaryResult, Error};
 }
@@ -11,7 +11,13 @@ use global::{
 use stdlib_header::{CoreTypeId, System};
 
 use crate::{
-    assembly::{AssemblyBuilder, ExtraHeader},
+    assembly::{Assembly, AssemblyBuilder, AssemblySectionId, ExtraHeader},
+    custom_attribute::{
+        CustomAttribute, CustomAttributeValue, CustomAttributeWithoutNamedArgs, Integer,
+        NamedArgument,
+    },
+    debug_info::{DebugInfo, Document, MethodDebugInfo, SequencePoint},
+    lazy::{LazyAssembly, SharedLazyAssembly},
     prelude::{MethodTokenBuilder, MethodType, TypeTokenBuilder, TypeType},
     ty::{ClassDef, GenericCountRequirement, Method, TypeDef, TypeRef},
 };
@@ -41,9 +47,7 @@ impl_into_method_token!(System::Exception::MethodId);
 fn emit_test_normal_f() -> binary_core::BinaryResult<()> {
     let mut section = SectionBuilder::new();
     let assembly = AssemblyBuilder {
-        extra_header: ExtraHeader {
-            name: section.as_string_section_mut().add_string("TestNormalF"),
-        },
+        extra_header: ExtraHeader::new(section.as_string_section_mut().add_string("TestNormalF")),
         custom_attributes: Vec::new(),
         type_refs: vec![
             core_type_ref!(section => System_Object),
@@ -185,6 +189,8 @@ fn emit_test_normal_f() -> binary_core::BinaryResult<()> {
 
             interfaces: vec![],
         })],
+        debug_info: None,
+        assembly_refs: Vec::new(),
 
         string_section: section,
     };
@@ -203,3 +209,266 @@ fn emit_test_normal_f() -> binary_core::BinaryResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn debug_info_round_trip() -> binary_core::BinaryResult<()> {
+    let mut assembly = AssemblyBuilder::new("TestDebugInfo");
+    let mut debug_info = DebugInfo::default();
+    let document = debug_info.add_document(Document {
+        path: assembly.add_string("test.pl"),
+    });
+    debug_info.methods.push(MethodDebugInfo {
+        type_def: 0,
+        method: 1,
+        sequence_points: vec![
+            SequencePoint {
+                instruction: 0,
+                document,
+                line: 3,
+                column: 5,
+            },
+            SequencePoint {
+                instruction: 2,
+                document,
+                line: 4,
+                column: 5,
+            },
+        ],
+        arg_names: vec![assembly.add_string("x")],
+        local_names: Vec::new(),
+    });
+    assembly.debug_info = Some(debug_info);
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    assembly.write_to(&mut bytes)?;
+    let bytes = bytes.into_inner();
+    let assembly = Assembly::from_bytes(&bytes)?;
+
+    let debug_info = assembly.debug_info.as_ref().unwrap();
+    let m_debug_info = debug_info.get_method(0, 1).unwrap();
+    assert_eq!(m_debug_info.sequence_point_at(1).unwrap().line, 3);
+    assert_eq!(m_debug_info.sequence_point_at(7).unwrap().line, 4);
+    assert_eq!(
+        assembly.get_string(debug_info.documents[document as usize].path)?,
+        "test.pl"
+    );
+    assert!(debug_info.get_method(0, 0).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn custom_attribute_round_trip() -> binary_core::BinaryResult<()> {
+    let mut assembly = AssemblyBuilder::new("TestCustomAttribute");
+    let object = core_type_ref!(assembly.string_section => System_Object);
+    let int32 = core_type_ref!(assembly.string_section => System_Int32);
+    assembly.type_refs.extend([object, int32]);
+    let type_ref = |index| {
+        TypeTokenBuilder::new()
+            .with_ty(TypeType::TypeRef)
+            .with_index(index)
+            .build()
+    };
+    let field = assembly.add_string("Field");
+    let property = assembly.add_string("Property");
+    assembly.custom_attributes.push(CustomAttribute::new(
+        type_ref(0),
+        MethodTokenBuilder::new()
+            .with_ty(MethodType::Method)
+            .with_index(0)
+            .build(),
+        vec![
+            CustomAttributeValue::Single(1.5),
+            CustomAttributeValue::Double(-0.25),
+            CustomAttributeValue::Object {
+                ty: type_ref(1),
+                val: Box::new(CustomAttributeValue::Integer(Integer::Int(-7))),
+            },
+            CustomAttributeValue::Array(vec![
+                CustomAttributeValue::Boolean(true),
+                CustomAttributeValue::Array(vec![CustomAttributeValue::Char('a')]),
+            ]),
+        ],
+        vec![
+            NamedArgument::Field {
+                name: field,
+                value: CustomAttributeValue::Double(f64::MAX),
+            },
+            NamedArgument::Property {
+                name: property,
+                value: CustomAttributeValue::Array(Vec::new()),
+            },
+        ],
+    ));
+    let expected = format!("{:?}", assembly.custom_attributes);
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    assembly.write_to(&mut bytes)?;
+    let bytes = bytes.into_inner();
+    let assembly = Assembly::from_bytes(&bytes)?;
+
+    assert_eq!(format!("{:?}", assembly.custom_attributes), expected);
+    let named_args = &assembly.custom_attributes[0].named_args;
+    assert_eq!(assembly.get_string(named_args[0].name())?, "Field");
+    assert_eq!(assembly.get_string(named_args[1].name())?, "Property");
+
+    Ok(())
+}
+
+#[test]
+fn custom_attribute_without_named_args() -> binary_core::BinaryResult<()> {
+    let mut assembly = AssemblyBuilder::new("TestCustomAttributeWithoutNamedArgs");
+    let object = core_type_ref!(assembly.string_section => System_Object);
+    assembly.type_refs.push(object);
+    let old = CustomAttributeWithoutNamedArgs {
+        ty: TypeTokenBuilder::new()
+            .with_ty(TypeType::TypeRef)
+            .with_index(0)
+            .build(),
+        ctor_name: MethodTokenBuilder::new()
+            .with_ty(MethodType::Method)
+            .with_index(0)
+            .build(),
+        positional_args: vec![CustomAttributeValue::Integer(Integer::Int(3))],
+    };
+    let expected = format!("{:?}", [CustomAttribute::from(old.clone())]);
+
+    // Written the way files of 0.5 were
+    let mut file = assembly.into_file()?;
+    file.version = [0x00, 0x05];
+    file.write_all(AssemblySectionId::CustomAttributes as usize, &[old])?;
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    file.write_to(&mut bytes)?;
+    let bytes = bytes.into_inner();
+
+    let assembly = Assembly::from_bytes(&bytes)?;
+    assert_eq!(format!("{:?}", assembly.custom_attributes), expected);
+    let assembly = AssemblyBuilder::from_bytes(bytes)?;
+    assert_eq!(format!("{:?}", assembly.custom_attributes), expected);
+
+    Ok(())
+}
+
+#[test]
+fn custom_attribute_max_depth() -> binary_core::BinaryResult<()> {
+    let write = |depth: usize| -> binary_core::BinaryResult<Vec<u8>> {
+        let mut assembly = AssemblyBuilder::new("TestCustomAttributeDepth");
+        let mut value = CustomAttributeValue::Boolean(true);
+        for _ in 0..depth {
+            value = CustomAttributeValue::Array(vec![value]);
+        }
+        assembly.custom_attributes.push(CustomAttribute::new(
+            TypeTokenBuilder::new().build(),
+            MethodTokenBuilder::new().build(),
+            vec![value],
+            Vec::new(),
+        ));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        assembly.write_to(&mut bytes)?;
+        Ok(bytes.into_inner())
+    };
+
+    let bytes = write(CustomAttributeValue::MAX_DEPTH)?;
+    assert!(Assembly::from_bytes(&bytes).is_ok());
+    let bytes = write(CustomAttributeValue::MAX_DEPTH + 1)?;
+    assert!(matches!(
+        Assembly::from_bytes(&bytes),
+        Err(binary_core::Error::WrongFormat)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn lazy_round_trip() -> binary_core::BinaryResult<()> {
+    let mut assembly = AssemblyBuilder::new("TestLazy");
+    let void = core_type_ref!(assembly.string_section => System_Void);
+    assembly.type_refs.push(void);
+    let method = |assembly: &mut AssemblyBuilder, name: &str, values: &[u64]| Method {
+        name: assembly.add_string(name),
+        attr: global::attr!(method Public {Static}),
+        generic_count_requirement: GenericCountRequirement::Exact(0),
+        args: Vec::new(),
+        return_type: TypeTokenBuilder::new()
+            .with_ty(TypeType::TypeRef)
+            .with_index(0)
+            .build(),
+        call_convention: CallConvention::PlatformDefault,
+        generic_bounds: None,
+        instructions: values
+            .iter()
+            .map(|value| {
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content: LoadContent::U64(*value),
+                })
+            })
+            .collect(),
+        exception_table: vec![],
+    };
+    for (name, methods) in [("TestLazy::A", [1, 2]), ("TestLazy::B", [3, 4])] {
+        let method_table = vec![
+            method(&mut assembly, "F1", &[methods[0]]),
+            method(&mut assembly, "F2", &[methods[0], methods[1]]),
+        ];
+        let name = assembly.add_string(name);
+        assembly.type_defs.push(TypeDef::Class(ClassDef {
+            main: None,
+            name,
+            attr: global::attr!(class Public {}),
+            generic_count_requirement: GenericCountRequirement::Exact(0),
+            parent: None,
+            method_table,
+            fields: Vec::new(),
+            sctor: None,
+            generic_bounds: None,
+            interfaces: vec![],
+        }));
+    }
+    let expected = assembly
+        .type_defs
+        .iter()
+        .map(|type_def| {
+            type_def
+                .method_table()
+                .iter()
+                .map(|method| format!("{:?}", method.instructions))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    assembly.write_to(&mut bytes)?;
+    let bytes = bytes.into_inner();
+    let lazy = LazyAssembly::from_bytes(&bytes)?;
+
+    assert_eq!(lazy.type_def_count(), 2);
+    // Decoded in any order, without the instructions
+    let b = lazy.type_def(1)?;
+    assert_eq!(lazy.get_string(*b.name())?, "TestLazy::B");
+    assert!(lazy.method(1, 1)?.instructions.is_empty());
+    for (type_def, methods) in expected.iter().enumerate() {
+        for (method, instructions) in methods.iter().enumerate() {
+            assert_eq!(
+                &format!("{:?}", lazy.instructions(type_def as u32, method as u32)?),
+                instructions
+            );
+        }
+    }
+    assert!(lazy.method_body(0, 2).is_err());
+
+    let assembly = lazy.into_assembly()?;
+    for (type_def, methods) in assembly.type_defs.iter().zip(&expected) {
+        for (method, instructions) in type_def.method_table().iter().zip(methods) {
+            assert_eq!(&format!("{:?}", method.instructions), instructions);
+        }
+    }
+
+    // Owning the bytes, type defs are decoded once and kept
+    let shared = SharedLazyAssembly::from_bytes(bytes)?;
+    let lazy = shared.get();
+    assert!(std::ptr::eq(lazy.type_def(1)?, lazy.type_def(1)?));
+    assert_eq!(format!("{:?}", lazy.instructions(1, 1)?), expected[1][1]);
+
+    Ok(())
+}
@@ -44,6 +44,28 @@ impl TypeDef {
             TypeDef::Interface(interface_def) => interface_def.attr,
         }
     }
+    pub fn method_table(&self) -> &[Method] {
+        match self {
+            TypeDef::Class(class_def) => &class_def.method_table,
+            TypeDef::Struct(struct_def) => &struct_def.method_table,
+            TypeDef::Interface(interface_def) => &interface_def.method_table,
+        }
+    }
+    pub fn method_table_mut(&mut self) -> &mut [Method] {
+        match self {
+            TypeDef::Class(class_def) => &mut class_def.method_table,
+            TypeDef::Struct(struct_def) => &mut struct_def.method_table,
+            TypeDef::Interface(interface_def) => &mut interface_def.method_table,
+        }
+    }
+    /// Interfaces do not have fields
+    pub fn fields(&self) -> &[Field] {
+        match self {
+            TypeDef::Class(class_def) => &class_def.fields,
+            TypeDef::Struct(struct_def) => &struct_def.fields,
+            TypeDef::Interface(_) => &[],
+        }
+    }
 }
 
 #[derive(Clone, Debug, Getters, ReadFromSection, WriteToSection, PartialEq)]
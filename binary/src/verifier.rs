@@ -0,0 +1,920 @@
+//! Static checks of the method bodies in an assembly,
+//! so that malformed bytecode is rejected before it is executed
+//!
+//! Other assemblies are not loaded here, so checks which depend on them are skipped,
+//! such as resolving a method of a class inheriting from a class of another assembly
+
+use std::{fmt::Display, range::Range};
+
+use derive_more::Display;
+use global::instruction::{
+    CommonReadPointerTo, CommonWritePointer, IRegisterAddr, Instruction, Instruction_Array,
+    Instruction_Calculate, Instruction_Call, Instruction_Cast, Instruction_CommonCheck,
+    Instruction_Convert, Instruction_Jump, Instruction_Load, Instruction_Move, Instruction_New,
+    Instruction_Set, Instruction_StackAllocate, Instruction_Switch, Instruction_UntypedCalculate,
+    JumpCondition, JumpTarget, JumpTargetType, LoadContent, ToCheckContent,
+};
+
+use crate::{
+    assembly::Assembly,
+    item_token::{MethodToken, MethodType, TypeToken, TypeTokenBuilder, TypeType},
+    ty::{BinaryInstruction, Field, Method, TypeDef},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A problem found in a method
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Index of the type in `type_defs`
+    pub type_def: u32,
+    /// Index of the method in the method table of the type
+    pub method: u32,
+    /// `None` if the problem is not caused by a single instruction
+    pub instruction: Option<u64>,
+    pub kind: DiagnosticKind,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "typedef[{}] method[{}]", self.type_def, self.method)?;
+        if let Some(instruction) = self.instruction {
+            write!(f, " at instruction {instruction}")?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Display)]
+pub enum DiagnosticKind {
+    #[display("register {register} is out of range, the method has {count} registers")]
+    RegisterOutOfRange { register: u64, count: u64 },
+    #[display("arg {arg} is out of range, the method has {count} args")]
+    ArgOutOfRange { arg: u64, count: u64 },
+    #[display("jump target {_0} is out of range")]
+    JumpOutOfRange(JumpTarget),
+    #[display("range {start}..{end} of the exception table is out of range")]
+    ExceptionRangeOutOfRange { start: u64, end: u64 },
+    #[display("method {method} of {ty} does not exist")]
+    UnknownMethod { ty: TypeToken, method: MethodToken },
+    #[display("field {field} of {ty} does not exist")]
+    UnknownField { ty: TypeToken, field: u32 },
+    #[display("expected {expected} args, found {found}")]
+    ArgCountMismatch { expected: usize, found: usize },
+    #[display("expected {expected} for arg {arg}, found {found}")]
+    ArgTypeMismatch {
+        arg: usize,
+        expected: TypeToken,
+        found: TypeToken,
+    },
+    #[display("expected {expected} for field {field}, found {found}")]
+    FieldTypeMismatch {
+        field: u32,
+        expected: TypeToken,
+        found: TypeToken,
+    },
+    #[display("the method can reach its end without returning")]
+    MissingReturn,
+    #[display("the tail call returns {found} instead of {expected}")]
+    TailCallReturnTypeMismatch {
+        expected: TypeToken,
+        found: TypeToken,
+    },
+}
+
+/// All the problems found in an assembly
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyError(pub Vec<Diagnostic>);
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} problems found when verifying:", self.0.len())?;
+        for diagnostic in &self.0 {
+            writeln!(f, "    {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<VerifyError> for binary_core::Error {
+    fn from(value: VerifyError) -> Self {
+        Self::Custom(value.into())
+    }
+}
+
+/// Verifies all the methods in the assembly
+///
+/// `void` is the index of `System::Void` in `core_assembly`,
+/// methods returning it can reach the end without returning
+pub fn verify(assembly: &Assembly<'_>, core_assembly: &str, void: u32) -> Result<(), VerifyError> {
+    let diagnostics: Vec<_> =
+        assembly
+            .type_defs
+            .iter()
+            .enumerate()
+            .flat_map(|(type_index, type_def)| {
+                type_def.method_table().iter().enumerate().flat_map(
+                    move |(method_index, method)| {
+                        verify_method(
+                            assembly,
+                            core_assembly,
+                            void,
+                            type_index as u32,
+                            method_index as u32,
+                            method,
+                        )
+                    },
+                )
+            })
+            .collect();
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(VerifyError(diagnostics))
+    }
+}
+
+/// Verifies a method of `type_defs[type_def]` in the assembly
+///
+/// Methods without instructions are implemented by the runtime, so only their signatures are used
+pub fn verify_method(
+    assembly: &Assembly<'_>,
+    core_assembly: &str,
+    void: u32,
+    type_def: u32,
+    method_index: u32,
+    method: &Method,
+) -> Vec<Diagnostic> {
+    let mut verifier = MethodVerifier {
+        assembly,
+        core_assembly,
+        void,
+        type_def,
+        method_index,
+        method,
+        pc: None,
+        diagnostics: Vec::new(),
+    };
+    verifier.verify();
+    verifier.diagnostics
+}
+
+struct MethodVerifier<'a> {
+    assembly: &'a Assembly<'a>,
+    core_assembly: &'a str,
+    void: u32,
+    type_def: u32,
+    method_index: u32,
+    method: &'a Method,
+    /// The instruction being verified
+    pc: Option<u64>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> MethodVerifier<'a> {
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic {
+            type_def: self.type_def,
+            method: self.method_index,
+            instruction: self.pc,
+            kind,
+        });
+    }
+
+    fn len(&self) -> u64 {
+        self.method.instructions.len() as u64
+    }
+
+    fn verify(&mut self) {
+        for (pc, ins) in self.method.instructions.iter().enumerate() {
+            self.pc = Some(pc as u64);
+            self.verify_instruction(ins);
+        }
+        self.pc = None;
+
+        for entry in &self.method.exception_table {
+            let ranges = [
+                Some(entry.range),
+                Some(entry.catch),
+                entry.finally,
+                entry.fault,
+            ];
+            for range in ranges.into_iter().flatten() {
+                if range.start > range.end || range.end > self.len() {
+                    self.report(DiagnosticKind::ExceptionRangeOutOfRange {
+                        start: range.start,
+                        end: range.end,
+                    });
+                }
+            }
+        }
+
+        if !self.method.instructions.is_empty() && !self.is_void(self.method.return_type) {
+            self.verify_returns();
+        }
+    }
+
+    fn verify_instruction(&mut self, ins: &BinaryInstruction) {
+        let mut registers = Vec::new();
+        instruction_registers(ins, &mut registers);
+        let count = self.method.attr.local_variable_types.len() as u64;
+        for register in registers {
+            if register >= count {
+                self.report(DiagnosticKind::RegisterOutOfRange { register, count });
+            }
+        }
+
+        let (_, targets) = control_flow(ins);
+        for target in targets {
+            if self.resolve_jump(target).is_none() {
+                self.report(DiagnosticKind::JumpOutOfRange(target));
+            }
+        }
+
+        match ins {
+            Instruction::Load(ins) => self.verify_load(ins),
+            Instruction::SLoad(ins) => self.verify_load(ins),
+            Instruction::New(ins) => self.verify_new(ins),
+            Instruction::SNew(ins) => self.verify_new(ins),
+            Instruction::Call(ins) => self.verify_call(ins),
+            Instruction::SCall(ins) => self.verify_call(ins),
+            Instruction::Set(ins) => self.verify_set(ins),
+            Instruction::SSet(ins) => self.verify_set(ins),
+            _ => {}
+        }
+    }
+
+    fn verify_load<R: IRegisterAddr>(
+        &mut self,
+        ins: &Instruction_Load<binary_core::traits::StringRef, TypeToken, MethodToken, u32, R>,
+    ) {
+        match &ins.content {
+            LoadContent::Arg(arg) | LoadContent::ArgRef(arg) | LoadContent::ArgValue(arg) => {
+                let count = self.method.args.len() as u64;
+                if *arg >= count {
+                    self.report(DiagnosticKind::ArgOutOfRange { arg: *arg, count });
+                }
+            }
+            LoadContent::AddressOfStatic { ty, field } | LoadContent::Static { ty, field } => {
+                self.field(*ty, *field);
+            }
+            LoadContent::AddressOfField { container, field }
+            | LoadContent::Field { container, field } => {
+                if let Some(ty) = self.register_type(*container) {
+                    self.field(ty, *field);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn verify_new<R: IRegisterAddr>(&mut self, ins: &Instruction_New<TypeToken, MethodToken, R>) {
+        if let Instruction_New::NewObject {
+            ty,
+            ctor_name,
+            args,
+            output: _,
+        } = ins
+            && let Some(ctor) = self.callee(*ty, *ctor_name)
+        {
+            self.verify_args(ctor, args);
+        }
+    }
+
+    fn verify_call<R: IRegisterAddr>(&mut self, ins: &Instruction_Call<TypeToken, MethodToken, R>) {
+        let (ty, method, args) = match ins {
+            Instruction_Call::InstanceCall {
+                val, method, args, ..
+            }
+            | Instruction_Call::TailInstanceCall { val, method, args } => {
+                let Some(ty) = self.register_type(*val) else {
+                    return;
+                };
+                (ty, method, args)
+            }
+            Instruction_Call::StaticCall {
+                ty, method, args, ..
+            }
+            | Instruction_Call::TailStaticCall { ty, method, args } => (*ty, method, args),
+            Instruction_Call::InterfaceCall {
+                interface,
+                method,
+                args,
+                ..
+            }
+            | Instruction_Call::TailInterfaceCall {
+                interface,
+                method,
+                args,
+                ..
+            } => (*interface, method, args),
+            Instruction_Call::StaticNonPurusCall { .. }
+            | Instruction_Call::DynamicNonPurusCall { .. }
+            | Instruction_Call::IndirectCall { .. } => return,
+        };
+        let Some(callee) = self.callee(ty, *method) else {
+            return;
+        };
+        self.verify_args(callee, args);
+        // The callee of a tail call returns into the return buffer of the caller
+        if is_tail_call(ins) && !self.is_same_type(self.method.return_type, callee.return_type) {
+            self.report(DiagnosticKind::TailCallReturnTypeMismatch {
+                expected: self.method.return_type,
+                found: callee.return_type,
+            });
+        }
+    }
+
+    fn verify_set<R: IRegisterAddr>(&mut self, ins: &Instruction_Set<TypeToken, u32, R>) {
+        let (val, ty, field_index) = match ins {
+            Instruction_Set::Common {
+                val,
+                container,
+                field,
+            } => {
+                let Some(ty) = self.register_type(*container) else {
+                    return;
+                };
+                (val, ty, *field)
+            }
+            Instruction_Set::This { val, field } => (
+                val,
+                TypeTokenBuilder::new()
+                    .with_ty(TypeType::TypeDef)
+                    .with_index(self.type_def)
+                    .build(),
+                *field,
+            ),
+            Instruction_Set::Static { val, ty, field } => (val, *ty, *field),
+        };
+        if let Some(field) = self.field(ty, field_index)
+            && let Some(found) = self.register_type(*val)
+            && !self.is_compatible(field.ty, found)
+        {
+            self.report(DiagnosticKind::FieldTypeMismatch {
+                field: field_index,
+                expected: field.ty,
+                found,
+            });
+        }
+    }
+
+    fn verify_args<R: IRegisterAddr>(&mut self, callee: &Method, args: &[R]) {
+        let expected = callee.args.len();
+        let found = args.len();
+        if found < expected || (found > expected && !callee.attr.allow_extra_args()) {
+            self.report(DiagnosticKind::ArgCountMismatch { expected, found });
+        }
+        for (arg, (param, register)) in callee.args.iter().zip(args).enumerate() {
+            if let Some(found) = self.register_type(*register)
+                && !self.is_compatible(param.ty, found)
+            {
+                self.report(DiagnosticKind::ArgTypeMismatch {
+                    arg,
+                    expected: param.ty,
+                    found,
+                });
+            }
+        }
+    }
+
+    /// Every path from the entry or a handler must end with a return, a throw or a tail call
+    ///
+    /// Reaching the end of a handler continues with the next handler of the entry,
+    /// or with the try block after a catch
+    fn verify_returns(&mut self) {
+        let len = self.len();
+        let mut visited = vec![false; len as usize];
+        let mut pending = vec![0];
+        // The ends of the handlers, with where the runtime continues from them
+        let mut handler_ends: Vec<(u64, Vec<u64>)> = Vec::new();
+        let is_valid = |x: &Range<u64>| x.start < x.end && x.end <= len;
+        for entry in &self.method.exception_table {
+            let (catch, finally, fault) = (
+                Some(entry.catch).filter(is_valid),
+                entry.finally.filter(is_valid),
+                entry.fault.filter(is_valid),
+            );
+            pending.extend(
+                [catch, finally, fault]
+                    .into_iter()
+                    .flatten()
+                    .map(|x| x.start),
+            );
+            if let Some(catch) = catch {
+                handler_ends.push((
+                    catch.end,
+                    (entry.range.start..entry.range.end.min(len)).collect(),
+                ));
+            }
+            if let Some(finally) = finally {
+                let then = fault.or(catch).map(|x| x.start);
+                handler_ends.push((finally.end, then.into_iter().collect()));
+            }
+            if let Some(fault) = fault {
+                handler_ends.push((fault.end, catch.map(|x| x.start).into_iter().collect()));
+            }
+        }
+
+        while let Some(pc) = pending.pop() {
+            if std::mem::replace(&mut visited[pc as usize], true) {
+                continue;
+            }
+            self.pc = Some(pc);
+            let (falls_through, targets) = control_flow(&self.method.instructions[pc as usize]);
+            if falls_through {
+                let mut ends_handler = false;
+                for (_, then) in handler_ends.iter().filter(|(end, _)| *end == pc + 1) {
+                    ends_handler = true;
+                    pending.extend(then);
+                }
+                if pc + 1 < len {
+                    pending.push(pc + 1);
+                } else if !ends_handler {
+                    self.report(DiagnosticKind::MissingReturn);
+                }
+            }
+            pending.extend(targets.into_iter().filter_map(|x| self.resolve_jump(x)));
+        }
+        self.pc = None;
+    }
+
+    fn resolve_jump(&self, target: JumpTarget) -> Option<u64> {
+        let pc = self.pc?;
+        let index = match target.ty() {
+            JumpTargetType::Absolute => Some(target.val()),
+            JumpTargetType::Forward => pc.checked_add(target.val()),
+            JumpTargetType::Backward => pc.checked_sub(target.val()),
+            JumpTargetType::Unknown => None,
+        }?;
+        (index < self.len()).then_some(index)
+    }
+
+    fn register_type<R: IRegisterAddr>(&self, register: R) -> Option<TypeToken> {
+        self.method
+            .attr
+            .local_variable_types
+            .get(register.get_usize())
+            .copied()
+    }
+
+    fn is_void(&self, ty: TypeToken) -> bool {
+        let TypeType::TypeRef = ty.ty() else {
+            return false;
+        };
+        self.assembly
+            .type_refs
+            .get(ty.index() as usize)
+            .is_some_and(|type_ref| {
+                type_ref.index == self.void
+                    && self
+                        .assembly
+                        .get_string(type_ref.assembly)
+                        .is_ok_and(|x| x == self.core_assembly)
+            })
+    }
+
+    /// Type refs are compared by the types they refer to,
+    /// generics and type specs are assumed to be the same
+    fn is_same_type(&self, lhs: TypeToken, rhs: TypeToken) -> bool {
+        if lhs == rhs {
+            return true;
+        }
+        match (lhs.ty(), rhs.ty()) {
+            (TypeType::TypeRef, TypeType::TypeRef) => {
+                let type_refs = &self.assembly.type_refs;
+                let (Some(lhs), Some(rhs)) = (
+                    type_refs.get(lhs.index() as usize),
+                    type_refs.get(rhs.index() as usize),
+                ) else {
+                    return true;
+                };
+                lhs.index == rhs.index
+                    && self.assembly.get_string(lhs.assembly).ok()
+                        == self.assembly.get_string(rhs.assembly).ok()
+            }
+            (TypeType::TypeDef | TypeType::TypeRef, TypeType::TypeDef | TypeType::TypeRef) => false,
+            _ => true,
+        }
+    }
+
+    /// `None` if the method does not exist or cannot be resolved in this assembly
+    fn callee(&mut self, ty: TypeToken, method: MethodToken) -> Option<&'a Method> {
+        let type_def = self.assembly.local_type_def(ty)?;
+        let index = match method.ty() {
+            MethodType::Method => method.index(),
+            MethodType::MethodSpec => match self.assembly.method_specs.get(method.index() as usize)
+            {
+                Some(spec) => spec.m,
+                None => {
+                    self.report(DiagnosticKind::UnknownMethod { ty, method });
+                    return None;
+                }
+            },
+            MethodType::MethodByRuntime => return None,
+        };
+        let callee = self
+            .assembly
+            .method_table(type_def)?
+            .get(index as usize)
+            .copied();
+        if callee.is_none() {
+            self.report(DiagnosticKind::UnknownMethod { ty, method });
+        }
+        callee
+    }
+
+    /// `None` if the field does not exist or the type is not in this assembly
+    fn field(&mut self, ty: TypeToken, field: u32) -> Option<&'a Field> {
+        let type_def = self.assembly.local_type_def(ty)?;
+        let result = self
+            .assembly
+            .type_defs
+            .get(type_def as usize)?
+            .fields()
+            .get(field as usize);
+        if result.is_none() {
+            self.report(DiagnosticKind::UnknownField { ty, field });
+        }
+        result
+    }
+
+    /// Only types defined in this assembly are compared,
+    /// others are assumed to be compatible
+    fn is_compatible(&self, expected: TypeToken, found: TypeToken) -> bool {
+        if expected == found {
+            return true;
+        }
+        let (TypeType::TypeDef, TypeType::TypeDef) = (expected.ty(), found.ty()) else {
+            return true;
+        };
+        let type_defs = &self.assembly.type_defs;
+        let (Some(expected_def), Some(found_def)) = (
+            type_defs.get(expected.index() as usize),
+            type_defs.get(found.index() as usize),
+        ) else {
+            return true;
+        };
+        match (expected_def, found_def) {
+            // Implementations of interfaces are checked by the runtime
+            (TypeDef::Interface(_), _) => true,
+            (TypeDef::Class(_), TypeDef::Class(def)) => {
+                let mut parent = def.parent;
+                // Bounded in case of cyclic inheritance
+                for _ in 0..type_defs.len() {
+                    let Some(ty) = parent else {
+                        return false;
+                    };
+                    if ty == expected {
+                        return true;
+                    }
+                    let TypeType::TypeDef = ty.ty() else {
+                        return true;
+                    };
+                    let Some(TypeDef::Class(def)) = type_defs.get(ty.index() as usize) else {
+                        return true;
+                    };
+                    parent = def.parent;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Whether the execution can go to the next instruction, and the jump targets
+fn control_flow(ins: &BinaryInstruction) -> (bool, Vec<JumpTarget>) {
+    fn jump<R: IRegisterAddr>(ins: &Instruction_Jump<R>) -> (bool, Vec<JumpTarget>) {
+        let is_conditional = !matches!(ins.condition, JumpCondition::Unconditional);
+        (is_conditional, vec![ins.target])
+    }
+    fn switch<R: IRegisterAddr>(ins: &Instruction_Switch<R>) -> (bool, Vec<JumpTarget>) {
+        let mut targets = ins.targets.clone();
+        targets.push(ins.default);
+        (false, targets)
+    }
+    match ins {
+        Instruction::Jump(ins) => jump(ins),
+        Instruction::SJump(ins) => jump(ins),
+        Instruction::Switch(ins) => switch(ins),
+        Instruction::SSwitch(ins) => switch(ins),
+        // Tail calls in a try range are made as normal calls by the runtime,
+        // but the caller still returns what they return
+        Instruction::Call(ins) => (!is_tail_call(ins), Vec::new()),
+        Instruction::SCall(ins) => (!is_tail_call(ins), Vec::new()),
+        Instruction::ReturnVal { .. }
+        | Instruction::SReturnVal { .. }
+        | Instruction::Throw { .. }
+        | Instruction::SThrow { .. }
+        | Instruction::Rethrow => (false, Vec::new()),
+        _ => (true, Vec::new()),
+    }
+}
+
+fn is_tail_call<R: IRegisterAddr>(ins: &Instruction_Call<TypeToken, MethodToken, R>) -> bool {
+    matches!(
+        ins,
+        Instruction_Call::TailInstanceCall { .. }
+            | Instruction_Call::TailStaticCall { .. }
+            | Instruction_Call::TailInterfaceCall { .. }
+    )
+}
+
+fn push<R: IRegisterAddr>(out: &mut Vec<u64>, registers: &[R]) {
+    out.extend(registers.iter().map(|x| x.get()));
+}
+
+fn instruction_registers(ins: &BinaryInstruction, out: &mut Vec<u64>) {
+    match ins {
+        Instruction::Nop | Instruction::Rethrow => {}
+        Instruction::Load(ins) => load_registers(ins, out),
+        Instruction::SLoad(ins) => load_registers(ins, out),
+        Instruction::ReadPointerTo(ins) => read_pointer_registers(ins, out),
+        Instruction::SReadPointerTo(ins) => read_pointer_registers(ins, out),
+        Instruction::WritePointer(ins) => write_pointer_registers(ins, out),
+        Instruction::SWritePointer(ins) => write_pointer_registers(ins, out),
+        Instruction::Check(ins) => check_registers(ins, out),
+        Instruction::SCheck(ins) => check_registers(ins, out),
+        Instruction::New(ins) => new_registers(ins, out),
+        Instruction::SNew(ins) => new_registers(ins, out),
+        Instruction::Call(ins) => call_registers(ins, out),
+        Instruction::SCall(ins) => call_registers(ins, out),
+        Instruction::Set(ins) => set_registers(ins, out),
+        Instruction::SSet(ins) => set_registers(ins, out),
+        Instruction::Calculate(ins) => calculate_registers(ins, out),
+        Instruction::SCalculate(ins) => calculate_registers(ins, out),
+        Instruction::Throw { exception_addr } => push(out, &[*exception_addr]),
+        Instruction::SThrow { exception_addr } => push(out, &[*exception_addr]),
+        Instruction::ReturnVal { register_addr } => push(out, &[*register_addr]),
+        Instruction::SReturnVal { register_addr } => push(out, &[*register_addr]),
+        Instruction::Jump(ins) => jump_registers(ins, out),
+        Instruction::SJump(ins) => jump_registers(ins, out),
+        Instruction::StackAllocate(ins) => stack_allocate_registers(ins, out),
+        Instruction::SStackAllocate(ins) => stack_allocate_registers(ins, out),
+        Instruction::Convert(ins) => convert_registers(ins, out),
+        Instruction::SConvert(ins) => convert_registers(ins, out),
+        Instruction::Cast(ins) => cast_registers(ins, out),
+        Instruction::SCast(ins) => cast_registers(ins, out),
+        Instruction::Switch(ins) => push(out, &[ins.value]),
+        Instruction::SSwitch(ins) => push(out, &[ins.value]),
+        Instruction::Move(ins) => move_registers(ins, out),
+        Instruction::SMove(ins) => move_registers(ins, out),
+        Instruction::ZeroInit { target } => push(out, &[*target]),
+        Instruction::SZeroInit { target } => push(out, &[*target]),
+        Instruction::Array(ins) => array_registers(ins, out),
+        Instruction::SArray(ins) => array_registers(ins, out),
+    }
+}
+
+fn load_registers<TString, TTypeRef, TMethodRef, TFieldRef, R: IRegisterAddr>(
+    ins: &Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, R>,
+    out: &mut Vec<u64>,
+) {
+    push(out, &[ins.addr]);
+    match &ins.content {
+        LoadContent::AddressOfRegister(register) => push(out, &[*register]),
+        LoadContent::AddressOfField { container, .. } | LoadContent::Field { container, .. } => {
+            push(out, &[*container])
+        }
+        _ => {}
+    }
+}
+
+fn read_pointer_registers<R: IRegisterAddr>(ins: &CommonReadPointerTo<R>, out: &mut Vec<u64>) {
+    push(out, &[ins.ptr, ins.size, ins.destination]);
+}
+
+fn write_pointer_registers<R: IRegisterAddr>(ins: &CommonWritePointer<R>, out: &mut Vec<u64>) {
+    push(out, &[ins.source, ins.size, ins.ptr]);
+}
+
+fn check_registers<R: IRegisterAddr>(ins: &Instruction_CommonCheck<R>, out: &mut Vec<u64>) {
+    push(out, &[ins.output]);
+    to_check_registers(&ins.content, out);
+}
+
+fn to_check_registers<R: IRegisterAddr>(content: &ToCheckContent<R>, out: &mut Vec<u64>) {
+    match content {
+        ToCheckContent::IsAllZero(register) => push(out, &[*register]),
+        ToCheckContent::Compare { lhs, rhs, .. } => push(out, &[*lhs, *rhs]),
+        ToCheckContent::CompareWithConst { lhs, .. } => push(out, &[*lhs]),
+    }
+}
+
+fn new_registers<TTypeRef, TMethodRef, R: IRegisterAddr>(
+    ins: &Instruction_New<TTypeRef, TMethodRef, R>,
+    out: &mut Vec<u64>,
+) {
+    match ins {
+        Instruction_New::NewObject { args, output, .. } => {
+            push(out, args);
+            push(out, &[*output]);
+        }
+        Instruction_New::NewArray { output, .. } => push(out, &[*output]),
+        Instruction_New::NewDynamicArray {
+            len_addr, output, ..
+        } => push(out, &[*len_addr, *output]),
+    }
+}
+
+fn call_registers<TTypeRef, TMethodRef, R: IRegisterAddr>(
+    ins: &Instruction_Call<TTypeRef, TMethodRef, R>,
+    out: &mut Vec<u64>,
+) {
+    match ins {
+        Instruction_Call::InstanceCall {
+            val, args, ret_at, ..
+        }
+        | Instruction_Call::InterfaceCall {
+            val, args, ret_at, ..
+        } => {
+            push(out, &[*val, *ret_at]);
+            push(out, args);
+        }
+        Instruction_Call::StaticCall { args, ret_at, .. } => {
+            push(out, &[*ret_at]);
+            push(out, args);
+        }
+        Instruction_Call::StaticNonPurusCall {
+            f_pointer,
+            args,
+            ret_at,
+            ..
+        }
+        | Instruction_Call::IndirectCall {
+            f_pointer,
+            args,
+            ret_at,
+        } => {
+            push(out, &[*f_pointer, *ret_at]);
+            push(out, args);
+        }
+        Instruction_Call::DynamicNonPurusCall {
+            f_pointer,
+            config,
+            args,
+            ret_at,
+        } => {
+            push(out, &[*f_pointer, *config, *ret_at]);
+            push(out, args);
+        }
+        Instruction_Call::TailInstanceCall { val, args, .. }
+        | Instruction_Call::TailInterfaceCall { val, args, .. } => {
+            push(out, &[*val]);
+            push(out, args);
+        }
+        Instruction_Call::TailStaticCall { args, .. } => push(out, args),
+    }
+}
+
+fn set_registers<TTypeRef, TFieldRef, R: IRegisterAddr>(
+    ins: &Instruction_Set<TTypeRef, TFieldRef, R>,
+    out: &mut Vec<u64>,
+) {
+    match ins {
+        Instruction_Set::Common { val, container, .. } => push(out, &[*val, *container]),
+        Instruction_Set::This { val, .. } | Instruction_Set::Static { val, .. } => {
+            push(out, &[*val])
+        }
+    }
+}
+
+fn calculate_registers<R: IRegisterAddr>(ins: &Instruction_Calculate<R>, out: &mut Vec<u64>) {
+    match ins {
+        Instruction_Calculate::U8(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::U16(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::U32(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::U64(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::I8(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::I16(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::I32(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::I64(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::F32(ins) => untyped_calculate_registers(ins, out),
+        Instruction_Calculate::F64(ins) => untyped_calculate_registers(ins, out),
+    }
+}
+
+fn untyped_calculate_registers<R: IRegisterAddr, T: Copy>(
+    ins: &Instruction_UntypedCalculate<R, T>,
+    out: &mut Vec<u64>,
+) {
+    use Instruction_UntypedCalculate::*;
+    match ins {
+        Add { lhs, rhs, target }
+        | Sub { lhs, rhs, target }
+        | Mul { lhs, rhs, target }
+        | Div { lhs, rhs, target }
+        | Rem { lhs, rhs, target }
+        | And { lhs, rhs, target }
+        | Or { lhs, rhs, target }
+        | Xor { lhs, rhs, target }
+        | Shl { lhs, rhs, target }
+        | Shr { lhs, rhs, target }
+        | Sar { lhs, rhs, target }
+        | RotateLeft { lhs, rhs, target }
+        | RotateRight { lhs, rhs, target }
+        | Arithmetic {
+            lhs, rhs, target, ..
+        } => push(out, &[*lhs, *rhs, *target]),
+        Not { source, target } => push(out, &[*source, *target]),
+        ConstAddTo { target, .. }
+        | ConstSubTo { target, .. }
+        | ConstMulTo { target, .. }
+        | ConstDivTo { target, .. }
+        | ConstRemTo { target, .. }
+        | SubByConst { target, .. }
+        | DivByConst { target, .. }
+        | RemByConst { target, .. }
+        | AddOne { target }
+        | SubOne { target }
+        | ConstAndTo { target, .. }
+        | ConstOrTo { target, .. }
+        | ConstXorTo { target, .. }
+        | ConstShlTo { target, .. }
+        | ConstShrTo { target, .. }
+        | ConstSarTo { target, .. }
+        | ConstRotateLeftTo { target, .. }
+        | ConstRotateRightTo { target, .. }
+        | ConstArithmeticTo { target, .. }
+        | ArithmeticByConst { target, .. } => push(out, &[*target]),
+    }
+}
+
+fn jump_registers<R: IRegisterAddr>(ins: &Instruction_Jump<R>, out: &mut Vec<u64>) {
+    match &ins.condition {
+        JumpCondition::Unconditional => {}
+        JumpCondition::If(register) => push(out, &[*register]),
+        JumpCondition::IfCheckSucceeds(content) | JumpCondition::IfCheckFails(content) => {
+            to_check_registers(content, out)
+        }
+    }
+}
+
+fn stack_allocate_registers<R: IRegisterAddr>(
+    ins: &Instruction_StackAllocate<R>,
+    out: &mut Vec<u64>,
+) {
+    match ins {
+        Instruction_StackAllocate::Dynamic {
+            out: ptr,
+            size,
+            align,
+        }
+        | Instruction_StackAllocate::DynamicZeroed {
+            out: ptr,
+            size,
+            align,
+        } => push(out, &[*ptr, *size, *align]),
+        Instruction_StackAllocate::Static { out: ptr, .. }
+        | Instruction_StackAllocate::StaticZeroed { out: ptr, .. } => push(out, &[*ptr]),
+    }
+}
+
+fn convert_registers<R: IRegisterAddr>(ins: &Instruction_Convert<R>, out: &mut Vec<u64>) {
+    push(out, &[ins.source, ins.target]);
+}
+
+fn cast_registers<TTypeRef, R: IRegisterAddr>(
+    ins: &Instruction_Cast<TTypeRef, R>,
+    out: &mut Vec<u64>,
+) {
+    match ins {
+        Instruction_Cast::IsInstanceOf { val, output, .. }
+        | Instruction_Cast::CastClass { val, output, .. }
+        | Instruction_Cast::Box {
+            value: val, output, ..
+        }
+        | Instruction_Cast::Unbox {
+            obj: val, output, ..
+        } => push(out, &[*val, *output]),
+    }
+}
+
+fn move_registers<R: IRegisterAddr>(ins: &Instruction_Move<R>, out: &mut Vec<u64>) {
+    push(out, &[ins.from, ins.to]);
+}
+
+fn array_registers<R: IRegisterAddr>(ins: &Instruction_Array<R>, out: &mut Vec<u64>) {
+    match ins {
+        Instruction_Array::LoadElement {
+            array,
+            index,
+            output,
+        }
+        | Instruction_Array::LoadElementAddress {
+            array,
+            index,
+            output,
+        } => push(out, &[*array, *index, *output]),
+        Instruction_Array::StoreElement {
+            array,
+            index,
+            value,
+        } => push(out, &[*array, *index, *value]),
+        Instruction_Array::ArrayLength { array, output } => push(out, &[*array, *output]),
+    }
+}
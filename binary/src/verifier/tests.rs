@@ -0,0 +1,401 @@
+use std::range::Range;
+
+use global::{
+    attrs::CallConvention,
+    instruction::{
+        IRegisterAddr, Instruction, Instruction_Call, Instruction_Jump, Instruction_Load,
+        Instruction_Set, JumpCondition, JumpTarget, LoadContent, RegisterAddr,
+    },
+};
+use stdlib_header::{CORE_ASSEMBLY_NAME, CoreTypeId};
+
+use crate::{
+    assembly::{Assembly, AssemblyBuilder},
+    item_token::{MethodTokenBuilder, MethodType, TypeToken, TypeTokenBuilder, TypeType},
+    ty::{
+        BinaryInstruction, ClassDef, ExceptionTableEntry, Field, GenericCountRequirement, Method,
+        Parameter, StructDef, TypeDef, TypeRef,
+    },
+};
+
+use super::{Diagnostic, DiagnosticKind, verify};
+
+fn token(ty: TypeType, index: u32) -> TypeToken {
+    TypeTokenBuilder::new()
+        .with_ty(ty)
+        .with_index(index)
+        .build()
+}
+
+fn void() -> TypeToken {
+    token(TypeType::TypeRef, 0)
+}
+
+fn int() -> TypeToken {
+    token(TypeType::TypeRef, 1)
+}
+
+fn method(
+    assembly: &mut AssemblyBuilder,
+    args: Vec<TypeToken>,
+    return_type: TypeToken,
+    locals: Vec<TypeToken>,
+    instructions: Vec<BinaryInstruction>,
+) -> Method {
+    let mut attr = global::attr!(method Public {Static});
+    attr.local_variable_types = locals;
+    Method {
+        name: assembly.add_string("F"),
+        attr,
+        generic_count_requirement: GenericCountRequirement::Exact(0),
+        args: args
+            .into_iter()
+            .map(|ty| Parameter {
+                ty,
+                attr: global::attr!(parameter {}),
+            })
+            .collect(),
+        return_type,
+        call_convention: CallConvention::PlatformDefault,
+        generic_bounds: None,
+        instructions,
+        exception_table: Vec::new(),
+    }
+}
+
+/// `typedef[0]` is a class containing `methods`,
+/// `typedef[1]` and `typedef[2]` are structs with a field of themselves
+fn verify_methods(build: impl FnOnce(&mut AssemblyBuilder) -> Vec<Method>) -> Vec<Diagnostic> {
+    let mut assembly = AssemblyBuilder::new("Test");
+    for id in [CoreTypeId::System_Void, CoreTypeId::System_Int32] {
+        let type_ref = TypeRef {
+            assembly: assembly.add_string(CORE_ASSEMBLY_NAME),
+            index: id as _,
+        };
+        assembly.add_type_ref(type_ref);
+    }
+    let method_table = build(&mut assembly);
+    let class = TypeDef::Class(ClassDef {
+        main: None,
+        name: assembly.add_string("Test::Test"),
+        attr: global::attr!(class Public {}),
+        generic_count_requirement: GenericCountRequirement::Exact(0),
+        parent: None,
+        method_table,
+        fields: Vec::new(),
+        sctor: None,
+        generic_bounds: None,
+        interfaces: Vec::new(),
+    });
+    assembly.type_defs.push(class);
+    for (index, name) in [(1, "Test::A"), (2, "Test::B")] {
+        let r#struct = TypeDef::Struct(StructDef {
+            name: assembly.add_string(name),
+            attr: global::attr!(
+                struct Public {}
+            ),
+            generic_count_requirement: GenericCountRequirement::Exact(0),
+            method_table: Vec::new(),
+            fields: vec![Field {
+                name: assembly.add_string("Value"),
+                attr: global::attr!(field Public {}),
+                ty: token(TypeType::TypeDef, index),
+            }],
+            sctor: None,
+            generic_bounds: None,
+        });
+        assembly.type_defs.push(r#struct);
+    }
+    match verify(
+        &Assembly::from_builder(&assembly),
+        CORE_ASSEMBLY_NAME,
+        CoreTypeId::System_Void as u32,
+    ) {
+        Ok(()) => Vec::new(),
+        Err(err) => err.0,
+    }
+}
+
+fn kinds(diagnostics: Vec<Diagnostic>) -> Vec<(Option<u64>, DiagnosticKind)> {
+    diagnostics
+        .into_iter()
+        .map(|x| (x.instruction, x.kind))
+        .collect()
+}
+
+fn load_arg(addr: u64, arg: u64) -> BinaryInstruction {
+    Instruction::Load(Instruction_Load {
+        addr: RegisterAddr::new(addr),
+        content: LoadContent::Arg(arg),
+    })
+}
+
+fn return_val(addr: u64) -> BinaryInstruction {
+    Instruction::ReturnVal {
+        register_addr: RegisterAddr::new(addr),
+    }
+}
+
+#[test]
+fn valid_methods() {
+    let diagnostics = verify_methods(|assembly| {
+        vec![
+            method(
+                assembly,
+                vec![int()],
+                int(),
+                vec![int()],
+                vec![load_arg(0, 0), return_val(0)],
+            ),
+            // Void methods can reach the end
+            method(
+                assembly,
+                vec![int()],
+                void(),
+                vec![int()],
+                vec![load_arg(0, 0)],
+            ),
+            // Implemented by the runtime
+            method(assembly, vec![], int(), vec![], vec![]),
+        ]
+    });
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn registers_and_args() {
+    let diagnostics = verify_methods(|assembly| {
+        vec![method(
+            assembly,
+            vec![int()],
+            int(),
+            vec![int()],
+            vec![load_arg(1, 1), return_val(0)],
+        )]
+    });
+    assert_eq!(
+        kinds(diagnostics),
+        vec![
+            (
+                Some(0),
+                DiagnosticKind::RegisterOutOfRange {
+                    register: 1,
+                    count: 1
+                }
+            ),
+            (Some(0), DiagnosticKind::ArgOutOfRange { arg: 1, count: 1 }),
+        ]
+    );
+}
+
+#[test]
+fn control_flow() {
+    let diagnostics = verify_methods(|assembly| {
+        let mut m = method(
+            assembly,
+            vec![int()],
+            int(),
+            vec![int()],
+            vec![
+                load_arg(0, 0),
+                Instruction::Jump(Instruction_Jump {
+                    target: JumpTarget::forward(2),
+                    condition: JumpCondition::IfCheckFails(
+                        global::instruction::ToCheckContent::IsAllZero(RegisterAddr::new(0)),
+                    ),
+                }),
+                Instruction::Jump(Instruction_Jump {
+                    target: JumpTarget::absolute(5),
+                    condition: JumpCondition::Unconditional,
+                }),
+                Instruction::Nop,
+            ],
+        );
+        m.exception_table.push(ExceptionTableEntry {
+            range: Range { start: 0, end: 2 },
+            exception_type: int(),
+            filter: None,
+            catch: Range { start: 3, end: 5 },
+            finally: None,
+            fault: None,
+        });
+        vec![m]
+    });
+    assert_eq!(
+        kinds(diagnostics),
+        vec![
+            (
+                Some(2),
+                DiagnosticKind::JumpOutOfRange(JumpTarget::absolute(5))
+            ),
+            (
+                None,
+                DiagnosticKind::ExceptionRangeOutOfRange { start: 3, end: 5 }
+            ),
+            (Some(3), DiagnosticKind::MissingReturn),
+        ]
+    );
+}
+
+#[test]
+fn handler_fall_through() {
+    let diagnostics = verify_methods(|assembly| {
+        // The runtime continues with the try block after the catch,
+        // and with the catch after the finally
+        let handlers = [
+            (Range { start: 2, end: 3 }, None),
+            (Range { start: 3, end: 4 }, Some(Range { start: 2, end: 3 })),
+        ];
+        handlers
+            .into_iter()
+            .map(|(catch, finally)| {
+                let mut m = method(
+                    assembly,
+                    vec![int()],
+                    int(),
+                    vec![int()],
+                    vec![
+                        load_arg(0, 0),
+                        return_val(0),
+                        Instruction::Nop,
+                        Instruction::Nop,
+                    ],
+                );
+                m.instructions.truncate(catch.end as usize);
+                m.exception_table.push(ExceptionTableEntry {
+                    range: Range { start: 0, end: 2 },
+                    exception_type: int(),
+                    filter: None,
+                    catch,
+                    finally,
+                    fault: None,
+                });
+                m
+            })
+            .collect()
+    });
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn calls_and_fields() {
+    let a = token(TypeType::TypeDef, 1);
+    let b = token(TypeType::TypeDef, 2);
+    let diagnostics = verify_methods(|assembly| {
+        let call = |method: u32, args: &[u64]| {
+            Instruction::Call(Instruction_Call::StaticCall {
+                ty: token(TypeType::TypeDef, 0),
+                method: MethodTokenBuilder::new()
+                    .with_ty(MethodType::Method)
+                    .with_index(method)
+                    .build(),
+                args: args.iter().map(|x| RegisterAddr::new(*x)).collect(),
+                ret_at: RegisterAddr::new(0),
+            })
+        };
+        vec![
+            method(assembly, vec![a], void(), vec![], vec![]),
+            method(
+                assembly,
+                vec![],
+                void(),
+                vec![a, b],
+                vec![
+                    call(0, &[0]),
+                    call(0, &[1]),
+                    call(0, &[]),
+                    call(2, &[]),
+                    Instruction::Set(Instruction_Set::Common {
+                        val: RegisterAddr::new(0),
+                        container: RegisterAddr::new(1),
+                        field: 0,
+                    }),
+                    Instruction::Set(Instruction_Set::Common {
+                        val: RegisterAddr::new(0),
+                        container: RegisterAddr::new(0),
+                        field: 1,
+                    }),
+                ],
+            ),
+        ]
+    });
+    assert_eq!(
+        kinds(diagnostics),
+        vec![
+            (
+                Some(1),
+                DiagnosticKind::ArgTypeMismatch {
+                    arg: 0,
+                    expected: a,
+                    found: b
+                }
+            ),
+            (
+                Some(2),
+                DiagnosticKind::ArgCountMismatch {
+                    expected: 1,
+                    found: 0
+                }
+            ),
+            (
+                Some(3),
+                DiagnosticKind::UnknownMethod {
+                    ty: token(TypeType::TypeDef, 0),
+                    method: MethodTokenBuilder::new()
+                        .with_ty(MethodType::Method)
+                        .with_index(2)
+                        .build()
+                }
+            ),
+            (
+                Some(4),
+                DiagnosticKind::FieldTypeMismatch {
+                    field: 0,
+                    expected: b,
+                    found: a
+                }
+            ),
+            (Some(5), DiagnosticKind::UnknownField { ty: a, field: 1 }),
+        ]
+    );
+}
+
+#[test]
+fn tail_calls() {
+    let diagnostics = verify_methods(|assembly| {
+        // Another reference to System::Int32, `add_type_ref` would reuse the first one
+        let other_int = token(TypeType::TypeRef, assembly.type_refs.len() as u32);
+        let type_ref = TypeRef {
+            assembly: assembly.add_string(CORE_ASSEMBLY_NAME),
+            index: CoreTypeId::System_Int32 as _,
+        };
+        assembly.type_refs.push(type_ref);
+
+        let tail_call = Instruction::Call(Instruction_Call::TailStaticCall {
+            ty: token(TypeType::TypeDef, 0),
+            method: MethodTokenBuilder::new()
+                .with_ty(MethodType::Method)
+                .with_index(0)
+                .build(),
+            args: vec![],
+        });
+        vec![
+            method(assembly, vec![], int(), vec![], vec![]),
+            method(assembly, vec![], void(), vec![], vec![tail_call.clone()]),
+            method(assembly, vec![], int(), vec![], vec![tail_call.clone()]),
+            method(assembly, vec![], other_int, vec![], vec![tail_call]),
+        ]
+    });
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic {
+            type_def: 0,
+            method: 1,
+            instruction: Some(0),
+            kind: DiagnosticKind::TailCallReturnTypeMismatch {
+                expected: void(),
+                found: int()
+            }
+        }]
+    );
+}
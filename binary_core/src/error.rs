@@ -3,7 +3,7 @@ use std::{
     io::{ErrorKind, const_error},
 };
 
-use crate::traits::StringRef;
+use crate::{Version, traits::StringRef};
 
 #[derive(Debug)]
 pub enum Error {
@@ -20,6 +20,17 @@ pub enum Error {
     UnknownType(u32),
     IoError(std::io::Error),
     EnumOutOfBounds(&'static str),
+    UnsupportedEncoding(u8),
+    UnsupportedVersion([u8; 2]),
+    UnsupportedFeatures(u32),
+    UnsupportedRuntimeVersion {
+        required: Version,
+        current: Version,
+    },
+    /// `section` is [`None`] if only the header is corrupted
+    ChecksumMismatch {
+        section: Option<usize>,
+    },
     Custom(global_errors::Error),
 }
 
@@ -53,6 +64,22 @@ impl Display for Error {
             Self::UnknownType(id) => f.write_fmt(format_args!("UnknownType: {id}")),
             Self::IoError(error) => <_ as Display>::fmt(error, f),
             Self::EnumOutOfBounds(ty) => f.write_fmt(format_args!("EnumOutOfBounds: {ty}")),
+            Self::UnsupportedEncoding(encoding) => {
+                f.write_fmt(format_args!("UnsupportedEncoding: {encoding}"))
+            }
+            Self::UnsupportedVersion([major, minor]) => {
+                f.write_fmt(format_args!("UnsupportedVersion: {major}.{minor}"))
+            }
+            Self::UnsupportedFeatures(bits) => {
+                f.write_fmt(format_args!("UnsupportedFeatures: {bits:#x}"))
+            }
+            Self::UnsupportedRuntimeVersion { required, current } => f.write_fmt(format_args!(
+                "UnsupportedRuntimeVersion: requires {required}, found {current}"
+            )),
+            Self::ChecksumMismatch { section: None } => f.write_str("ChecksumMismatch"),
+            Self::ChecksumMismatch {
+                section: Some(section),
+            } => f.write_fmt(format_args!("ChecksumMismatch: section {section}")),
             Self::Custom(error) => <_ as Display>::fmt(error, f),
         }
     }
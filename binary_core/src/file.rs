@@ -1,4 +1,4 @@
-use std::io::{Cursor, Seek, Write};
+use std::io::Write;
 
 use crate::{
     error::Error,
@@ -16,6 +16,9 @@ pub struct FileParser<'a> {
 pub struct Header {
     magic: [u8; 2],
     version: [u8; 2],
+    encoding: u8,
+    /// CRC32 of everything after it
+    checksum: u32,
     section_info_len: u32,
     section_infos: [SectionInfo],
 }
@@ -25,18 +28,44 @@ impl Header {
         std::mem::offset_of!(Self, section_info_len) + size_of::<u32>();
 
     const SIZE_WITHOUT_SECTION_INFO: usize = Self::SECTION_INFOS_OFFSET;
+
+    const CHECKSUMMED_OFFSET: usize = std::mem::offset_of!(Self, checksum) + size_of::<u32>();
+
+    pub const fn version(&self) -> [u8; 2] {
+        self.version
+    }
+    pub const fn encoding(&self) -> u8 {
+        self.encoding
+    }
 }
 
 const CURRENT_MAGIC: [u8; 2] = *b"PL";
-const CURRENT_VERSION: [u8; 2] = [0x00, 0x00];
+/// `[major, minor]`, files outside of `MIN_SUPPORTED_VERSION..=CURRENT_VERSION`
+/// are rejected instead of being misread
+const CURRENT_VERSION: [u8; 2] = [0x00, 0x06];
+/// Method bodies are stored in their own section since 0.5,
+/// older files would have to be decoded in another way
+const MIN_SUPPORTED_VERSION: [u8; 2] = [0x00, 0x05];
+/// Version of the encoding of fields marked `#[varint]`:
+/// - `0`: fixed width
+/// - `1`: LEB128
+pub const CURRENT_ENCODING: u8 = 1;
 
 impl<'a> FileParser<'a> {
     pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, Error> {
+        Self::from_bytes_with(bytes, true)
+    }
+    /// Skipping the checksums saves hashing the whole file,
+    /// but corrupted files are then only noticed if they fail to decode
+    pub fn from_bytes_with(bytes: &'a [u8], verify_checksums: bool) -> Result<Self, Error> {
         let mut this = Self {
             bytes,
             content_index: 0,
         };
         this.render_header()?;
+        if verify_checksums {
+            this.verify_checksums()?;
+        }
         Ok(this)
     }
 
@@ -53,6 +82,18 @@ impl<'a> FileParser<'a> {
             }
             return Err(Error::WrongFileSize);
         }
+        if self.bytes[..CURRENT_MAGIC.len()] != CURRENT_MAGIC {
+            return Err(Error::WrongFormat);
+        }
+        let version_offset = std::mem::offset_of!(Header, version);
+        let version = [self.bytes[version_offset], self.bytes[version_offset + 1]];
+        if !(MIN_SUPPORTED_VERSION..=CURRENT_VERSION).contains(&version) {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let encoding = self.bytes[std::mem::offset_of!(Header, encoding)];
+        if encoding != CURRENT_ENCODING {
+            return Err(Error::UnsupportedEncoding(encoding));
+        }
 
         let section_info_len = u32::from_le_bytes(
             *self.bytes[std::mem::offset_of!(Header, section_info_len)
@@ -60,7 +101,8 @@ impl<'a> FileParser<'a> {
                 .as_array::<{ size_of::<u32>() }>()
                 .unwrap(),
         );
-        let size = Header::SIZE_WITHOUT_SECTION_INFO + (section_info_len as usize);
+        let size = Header::SIZE_WITHOUT_SECTION_INFO
+            + (section_info_len as usize) * size_of::<SectionInfo>();
         if self.bytes.len() < size {
             #[cfg(debug_assertions)]
             {
@@ -68,10 +110,32 @@ impl<'a> FileParser<'a> {
             }
             return Err(Error::WrongFileSize);
         }
+        for info in &self.get_header().section_infos {
+            let (offset, len) = (info.offset, info.len);
+            if offset
+                .checked_add(len)
+                .is_none_or(|end| end > self.bytes.len() as u64)
+            {
+                return Err(Error::WrongFileSize);
+            }
+        }
         self.content_index = size;
         Ok(())
     }
 
+    /// The checksums of the sections are only used to tell which section is corrupted
+    fn verify_checksums(&self) -> Result<(), Error> {
+        let header = self.get_header();
+        if crc32fast::hash(&self.bytes[Header::CHECKSUMMED_OFFSET..]) == header.checksum {
+            return Ok(());
+        }
+        let section = header.section_infos.iter().position(|info| {
+            let bytes = &self.bytes[(info.offset as usize)..((info.offset + info.len) as usize)];
+            crc32fast::hash(bytes) != info.checksum
+        });
+        Err(Error::ChecksumMismatch { section })
+    }
+
     pub const fn get_header(&self) -> &'a Header {
         let section_info_len = u32::from_le_bytes(
             *self.bytes[std::mem::offset_of!(Header, section_info_len)
@@ -139,11 +203,21 @@ pub enum PredefinedSectionId {
 
 impl<'a> File<'a> {
     pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, Error> {
+        Self::from_bytes_with(bytes, true)
+    }
+    /// See [`FileParser::from_bytes_with`]
+    pub fn from_bytes_with(bytes: &'a [u8], verify_checksums: bool) -> Result<Self, Error> {
         Ok(Self {
-            raw: FileParser::from_bytes(bytes)?,
+            raw: FileParser::from_bytes_with(bytes, verify_checksums)?,
         })
     }
 
+    /// `[major, minor]` of the format the file is written in,
+    /// readers adapt the sections whose layout changed since then
+    pub const fn version(&self) -> [u8; 2] {
+        self.raw.get_header().version()
+    }
+
     pub const fn get_section(&self, index: usize) -> Option<&'a Section> {
         let info = self.raw.get_header().section_infos.get(index)?;
         Some(Section::with_bytes(
@@ -164,18 +238,9 @@ impl<'a> File<'a> {
         &self,
         section_id: usize,
     ) -> Result<Vec<T>, crate::error::Error> {
-        let section = self
-            .get_section(section_id)
-            .ok_or(Error::UnknownSection(section_id))?;
-        let mut cursor = Cursor::new(section);
-        let mut result = Vec::new();
-
-        loop {
-            if cursor.position() >= section.len() {
-                break Ok(result);
-            }
-            result.push(T::read_from_section(&mut cursor)?);
-        }
+        self.get_section(section_id)
+            .ok_or(Error::UnknownSection(section_id))?
+            .read_all()
     }
 }
 
@@ -195,7 +260,11 @@ impl FileBuilder {
     }
 
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
-        let section_iter = FileParser::from_bytes(&bytes)?.section_iter()?;
+        Self::from_bytes_with(bytes, true)
+    }
+    /// See [`FileParser::from_bytes_with`]
+    pub fn from_bytes_with(bytes: Vec<u8>, verify_checksums: bool) -> Result<Self, Error> {
+        let section_iter = FileParser::from_bytes_with(&bytes, verify_checksums)?.section_iter()?;
 
         Ok(Self {
             version: section_iter.header.version,
@@ -221,19 +290,10 @@ impl FileBuilder {
         &self,
         section_id: usize,
     ) -> Result<Vec<T>, crate::error::Error> {
-        let section = self
-            .sections
+        self.sections
             .get(section_id)
-            .ok_or(Error::UnknownSection(section_id))?;
-        let mut cursor = Cursor::new(&**section);
-        let mut result = Vec::new();
-
-        loop {
-            if cursor.position() >= section.len() {
-                break Ok(result);
-            }
-            result.push(T::read_from_section(&mut cursor)?);
-        }
+            .ok_or(Error::UnknownSection(section_id))?
+            .read_all()
     }
 
     /// # Safety
@@ -255,20 +315,31 @@ impl FileBuilder {
         self.sections.push(section);
     }
 
-    pub fn write_to<W: Write + Seek>(self, w: &mut W) -> std::io::Result<()> {
+    pub fn write_to<W: Write>(self, w: &mut W) -> std::io::Result<()> {
+        let section_info_len = (self.sections.len() as u32).to_le_bytes();
+        let mut section_infos = Vec::with_capacity(self.sections.len() * size_of::<SectionInfo>());
+        let mut offset = (Header::SIZE_WITHOUT_SECTION_INFO
+            + self.sections.len() * size_of::<SectionInfo>()) as u64;
+        for section in &self.sections {
+            let len = section.len();
+            let checksum = crc32fast::hash(section.as_bytes());
+            section_infos.extend_from_slice(SectionInfo::new(offset, len, checksum).as_bytes());
+            offset += len;
+        }
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&section_info_len);
+        hasher.update(&section_infos);
+        for section in &self.sections {
+            hasher.update(section.as_bytes());
+        }
+
         w.write_all(&CURRENT_MAGIC)?;
         w.write_all(&self.version)?;
-        w.write_all(&(self.sections.len() as u32).to_le_bytes())?;
-        let mut current_section_info_offset = w.stream_position()?;
-        w.write_all(&vec![0; self.sections.len() * size_of::<SectionInfo>()])?;
-        for section in self.sections {
-            let offset = w.stream_position()?;
-            let len = section.len();
-            w.seek(std::io::SeekFrom::Start(current_section_info_offset))?;
-            w.write_all(&offset.to_le_bytes())?;
-            w.write_all(&len.to_le_bytes())?;
-            current_section_info_offset = w.stream_position()?;
-            w.seek(std::io::SeekFrom::Start(offset))?;
+        w.write_all(&[CURRENT_ENCODING])?;
+        w.write_all(&hasher.finalize().to_le_bytes())?;
+        w.write_all(&section_info_len)?;
+        w.write_all(&section_infos)?;
+        for section in &self.sections {
             w.write_all(section.as_bytes())?;
         }
 
@@ -325,6 +396,98 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encoding() -> Result<(), Error> {
+        let mut bytes = Cursor::new(Vec::<u8>::new());
+        FileBuilder::new().write_to(&mut bytes)?;
+        let mut bytes = bytes.into_inner();
+        assert_eq!(
+            File::from_bytes(&bytes)?.raw.get_header().encoding(),
+            CURRENT_ENCODING
+        );
+        bytes[std::mem::offset_of!(Header, encoding)] = 0;
+        assert!(matches!(
+            File::from_bytes(&bytes),
+            Err(Error::UnsupportedEncoding(0))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_version() -> Result<(), Error> {
+        let mut bytes = Cursor::new(Vec::<u8>::new());
+        FileBuilder::new().write_to(&mut bytes)?;
+        let bytes = bytes.into_inner();
+        assert_eq!(
+            File::from_bytes(&bytes)?.raw.get_header().version(),
+            CURRENT_VERSION
+        );
+
+        let with_version = |version: [u8; 2]| {
+            let mut bytes = bytes.clone();
+            bytes[std::mem::offset_of!(Header, version)..][..2].copy_from_slice(&version);
+            bytes
+        };
+        assert_eq!(
+            File::from_bytes(&with_version(MIN_SUPPORTED_VERSION))?.version(),
+            MIN_SUPPORTED_VERSION
+        );
+        for version in [
+            [0, 0],
+            [0, MIN_SUPPORTED_VERSION[1] - 1],
+            [0, CURRENT_VERSION[1] + 1],
+            [1, 0],
+        ] {
+            assert!(matches!(
+                File::from_bytes(&with_version(version)),
+                Err(Error::UnsupportedVersion(found)) if found == version
+            ));
+        }
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(matches!(
+            File::from_bytes(&wrong_magic),
+            Err(Error::WrongFormat)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checksum() -> Result<(), Error> {
+        let mut file_builder = FileBuilder::new();
+        file_builder.add_section(SectionBuilder::with_bytes(vec![0, 1, 2, 3, 4]));
+        file_builder.add_section(SectionBuilder::with_bytes(vec![5, 6, 7]));
+        let mut bytes = Cursor::new(Vec::<u8>::new());
+        file_builder.write_to(&mut bytes)?;
+        let bytes = bytes.into_inner();
+        File::from_bytes(&bytes)?;
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 0xff;
+        assert!(matches!(
+            File::from_bytes(&corrupted),
+            Err(Error::ChecksumMismatch { section: Some(2) })
+        ));
+        File::from_bytes_with(&corrupted, false)?;
+
+        let mut corrupted = bytes.clone();
+        corrupted[std::mem::offset_of!(Header, checksum)] ^= 0xff;
+        assert!(matches!(
+            File::from_bytes(&corrupted),
+            Err(Error::ChecksumMismatch { section: None })
+        ));
+
+        assert!(matches!(
+            File::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::WrongFileSize)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_string() -> Result<(), Error> {
         let mut file = FileBuilder::new();
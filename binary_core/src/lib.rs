@@ -27,9 +27,11 @@ pub mod error;
 pub mod file;
 pub mod section;
 pub mod traits;
+pub mod version;
 
 pub use error::{BinaryResult, Error};
 pub use file::{File, FileParser};
+pub use version::{Version, VersionRequirement, VersionRequirementKind};
 
 mod integers;
 pub use integers::*;
@@ -1,10 +1,14 @@
 use std::{
     borrow::{Borrow, BorrowMut},
+    collections::HashMap,
     io::Cursor,
     ops::{Deref, DerefMut},
 };
 
-use crate::traits::StringRef;
+use crate::{
+    error::Error,
+    traits::{ReadFromSection, StringRef},
+};
 
 #[derive(Debug)]
 #[repr(transparent)]
@@ -15,6 +19,63 @@ pub struct Section {
 #[derive(Debug, Clone)]
 pub struct SectionBuilder {
     bytes: Vec<u8>,
+    /// Only created once the section is used as a string section
+    string_index: Option<StringIndex>,
+}
+
+/// Offsets of the strings in a string section, so that each of them is added only once
+#[derive(Clone, Default)]
+struct StringIndex {
+    offsets: HashMap<Box<[u8]>, u64>,
+    /// The strings before it are indexed
+    indexed_len: usize,
+    suffix_sharing: bool,
+}
+
+impl StringIndex {
+    /// Called when the bytes may have been changed in place
+    fn invalidate(&mut self) {
+        self.offsets.clear();
+        self.indexed_len = 0;
+    }
+    /// Indexes the strings appended since the last call
+    fn update(&mut self, bytes: &[u8]) {
+        let Some(end) = memchr::memrchr(0, &bytes[self.indexed_len..]) else {
+            return;
+        };
+        let end = self.indexed_len + end;
+        let mut offset = self.indexed_len;
+        for string in bytes[self.indexed_len..end].split(|x| *x == 0) {
+            self.insert(string, offset as u64);
+            offset += string.len() + 1;
+        }
+        self.indexed_len = end + 1;
+    }
+    fn insert(&mut self, string: &[u8], offset: u64) {
+        // The first occurrence is kept
+        if !self.offsets.contains_key(string) {
+            self.offsets.insert(string.into(), offset);
+        }
+        if self.suffix_sharing {
+            for (start, byte) in string.iter().enumerate().skip(1) {
+                // Suffixes starting in the middle of a char are never looked up
+                let is_char_boundary = (*byte as i8) >= -0x40;
+                if is_char_boundary && !self.offsets.contains_key(&string[start..]) {
+                    self.offsets
+                        .insert(string[start..].into(), offset + start as u64);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for StringIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StringIndex")
+            .field("strings", &self.offsets.len())
+            .field("suffix_sharing", &self.suffix_sharing)
+            .finish()
+    }
 }
 
 #[repr(transparent)]
@@ -51,19 +112,42 @@ impl StringSection {
 pub struct StringSectionBuilder(pub SectionBuilder);
 
 impl StringSectionBuilder {
+    /// Returns the existing string if it has been added
     pub fn add_string(&mut self, s: &str) -> StringRef {
-        let mut ind = 0;
-        for bytes in self.0.as_bytes_mut().split(|x| *x == 0) {
-            if bytes == s.as_bytes() {
-                return StringRef(ind as u64);
-            }
-            ind += bytes.len() + 1;
+        let SectionBuilder {
+            bytes,
+            string_index,
+        } = &mut self.0;
+        let index = string_index.get_or_insert_default();
+        index.update(bytes);
+        if let Some(offset) = index.offsets.get(s.as_bytes()) {
+            return StringRef(*offset);
         }
-        let pos = self.0.len();
-        self.0.extend_from_slice(s.as_bytes());
-        self.0.push(0);
+        let pos = bytes.len() as u64;
+        bytes.extend_from_slice(s.as_bytes());
+        bytes.push(0);
+        index.update(bytes);
         StringRef(pos)
     }
+    /// Indexes the strings already in the section,
+    /// which is otherwise done by the first [`Self::add_string`]
+    pub fn build_index(&mut self) {
+        self.0
+            .string_index
+            .get_or_insert_default()
+            .update(&self.0.bytes);
+    }
+    /// When enabled, a string which is the tail of an existing one is not added again,
+    /// but points into the existing one
+    ///
+    /// Indexing every suffix makes adding long strings slower
+    pub fn set_suffix_sharing(&mut self, enabled: bool) {
+        let index = self.0.string_index.get_or_insert_default();
+        if index.suffix_sharing != enabled {
+            index.suffix_sharing = enabled;
+            index.invalidate();
+        }
+    }
 }
 
 impl Section {
@@ -101,6 +185,16 @@ impl Section {
     pub const fn as_string_section_mut(&mut self) -> &mut StringSection {
         unsafe { &mut *(self as *mut Self as *mut StringSection) }
     }
+
+    /// Reads items until the end of the section
+    pub fn read_all<T: ReadFromSection>(&self) -> Result<Vec<T>, Error> {
+        let mut cursor = Cursor::new(self);
+        let mut result = Vec::new();
+        while cursor.position() < self.len() {
+            result.push(T::read_from_section(&mut cursor)?);
+        }
+        Ok(result)
+    }
 }
 
 impl SectionBuilder {
@@ -108,7 +202,10 @@ impl SectionBuilder {
         Self::with_bytes(Vec::new())
     }
     pub const fn with_bytes(bytes: Vec<u8>) -> Self {
-        Self { bytes }
+        Self {
+            bytes,
+            string_index: None,
+        }
     }
 
     #[allow(clippy::len_without_is_empty)]
@@ -119,6 +216,7 @@ impl SectionBuilder {
         &self.bytes
     }
     pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.invalidate_string_index();
         &mut self.bytes
     }
     pub fn push(&mut self, v: u8) {
@@ -128,8 +226,14 @@ impl SectionBuilder {
         self.bytes.extend_from_slice(slice);
     }
     pub fn construct_mut_vec_cursor(&mut self) -> Cursor<&mut Vec<u8>> {
+        self.invalidate_string_index();
         Cursor::new(&mut self.bytes)
     }
+    fn invalidate_string_index(&mut self) {
+        if let Some(index) = &mut self.string_index {
+            index.invalidate();
+        }
+    }
 
     #[inline(always)]
     pub fn as_string_section(&self) -> &StringSectionBuilder {
@@ -151,12 +255,11 @@ impl ToOwned for Section {
     type Owned = SectionBuilder;
 
     fn to_owned(&self) -> Self::Owned {
-        Self::Owned {
-            bytes: self.bytes.to_owned(),
-        }
+        Self::Owned::with_bytes(self.bytes.to_owned())
     }
 
     fn clone_into(&self, target: &mut Self::Owned) {
+        target.invalidate_string_index();
         self.bytes.clone_into(&mut target.bytes);
     }
 }
@@ -175,6 +278,7 @@ impl Borrow<Section> for SectionBuilder {
 
 impl BorrowMut<Section> for SectionBuilder {
     fn borrow_mut(&mut self) -> &mut Section {
+        self.invalidate_string_index();
         Section::with_bytes_mut(&mut self.bytes)
     }
 }
@@ -188,6 +292,7 @@ impl Deref for SectionBuilder {
 
 impl DerefMut for SectionBuilder {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.invalidate_string_index();
         Section::with_bytes_mut(&mut self.bytes)
     }
 }
@@ -201,6 +306,7 @@ impl Deref for StringSectionBuilder {
 
 impl DerefMut for StringSectionBuilder {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.invalidate_string_index();
         Section::with_bytes_mut(&mut self.0.bytes).as_string_section_mut()
     }
 }
@@ -222,13 +328,64 @@ impl BorrowMut<StringSection> for StringSectionBuilder {
 pub struct SectionInfo {
     pub offset: u64,
     pub len: u64,
+    /// CRC32 of the bytes of the section
+    pub checksum: u32,
 }
 
 impl SectionInfo {
-    pub const fn new(offset: u64, len: u64) -> Self {
-        Self { offset, len }
+    pub const fn new(offset: u64, len: u64, checksum: u32) -> Self {
+        Self {
+            offset,
+            len,
+            checksum,
+        }
     }
     pub const fn as_bytes(&self) -> &[u8] {
         unsafe { &*std::ptr::from_raw_parts(self, size_of::<Self>()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_string() {
+        let mut section = SectionBuilder::new();
+        let strings = section.as_string_section_mut();
+        let a = strings.add_string("Foo::Bar");
+        let b = strings.add_string("Bar");
+        assert_eq!(strings.add_string("Foo::Bar"), a);
+        assert_eq!(strings.add_string("Bar"), b);
+        assert_eq!(strings.get_string(a), Some("Foo::Bar"));
+        assert_eq!(strings.get_string(b), Some("Bar"));
+        assert_eq!(section.as_bytes(), b"Foo::Bar\0Bar\0");
+
+        // Strings written without the index are found once it is rebuilt
+        let mut section = SectionBuilder::with_bytes(b"Foo\0Bar\0".to_vec());
+        let strings = section.as_string_section_mut();
+        strings.build_index();
+        assert_eq!(strings.add_string("Bar"), StringRef(4));
+        section.as_bytes_mut()[4] = b'C';
+        assert_eq!(
+            section.as_string_section_mut().add_string("Car"),
+            StringRef(4)
+        );
+        assert_eq!(section.len(), 8);
+    }
+
+    #[test]
+    fn test_suffix_sharing() {
+        let mut section = SectionBuilder::new();
+        let strings = section.as_string_section_mut();
+        let a = strings.add_string("Foo::Bär");
+        strings.set_suffix_sharing(true);
+        let b = strings.add_string("Bär");
+        assert_eq!(b, StringRef(a.0 + 5));
+        assert_eq!(strings.get_string(b), Some("Bär"));
+        assert_eq!(strings.add_string("r"), StringRef(a.0 + 8));
+        let c = strings.add_string("Baz");
+        assert_eq!(strings.add_string("az"), StringRef(c.0 + 1));
+        assert_eq!(section.as_bytes(), "Foo::Bär\0Baz\0".as_bytes());
+    }
+}
@@ -16,6 +16,16 @@ pub trait WriteToSection {
     fn write_to_section(&self, cursor: &mut Cursor<&mut Vec<u8>>) -> BinaryResult<()>;
 }
 
+/// LEB128 encoding of integers, used by fields marked `#[varint]` in the derive macros
+pub trait ReadVarIntFromSection: Sized {
+    fn read_varint_from_section(cursor: &mut Cursor<&Section>) -> BinaryResult<Self>;
+}
+
+/// See [`ReadVarIntFromSection`]
+pub trait WriteVarIntToSection {
+    fn write_varint_to_section(&self, cursor: &mut Cursor<&mut Vec<u8>>) -> BinaryResult<()>;
+}
+
 macro_rules! tuple_read_impl {
     () => {
         impl ReadFromSection for () {
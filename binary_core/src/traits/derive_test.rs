@@ -1,9 +1,63 @@
 #![allow(dead_code)]
 
+use std::io::Cursor;
+
 use proc_macros::{ReadFromSection, WriteToSection};
 
+use crate::{
+    BinaryResult,
+    section::Section,
+    traits::{ReadFromSection, WriteToSection},
+};
+
 #[derive(ReadFromSection, WriteToSection)]
 struct Test1 {
     f: u8,
     f2: u16,
 }
+
+#[derive(ReadFromSection, WriteToSection, PartialEq, Debug)]
+struct Test2 {
+    #[varint]
+    f: u64,
+    #[varint]
+    f2: i32,
+    f3: u16,
+}
+
+#[test]
+fn varint() -> BinaryResult<()> {
+    for (value, expected) in [
+        (
+            Test2 {
+                f: 300,
+                f2: -65,
+                f3: 1,
+            },
+            vec![0xAC, 0x02, 0xBF, 0x7F, 0x01, 0x00],
+        ),
+        (
+            Test2 {
+                f: u64::MAX,
+                f2: i32::MIN,
+                f3: 0,
+            },
+            vec![
+                0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01, 0x80, 0x80, 0x80, 0x80,
+                0x78, 0x00, 0x00,
+            ],
+        ),
+    ] {
+        let mut bytes = Vec::new();
+        value.write_to_section(&mut Cursor::new(&mut bytes))?;
+        assert_eq!(bytes, expected);
+        let read = Test2::read_from_section(&mut Cursor::new(Section::with_bytes(&bytes)))?;
+        assert_eq!(read, value);
+    }
+
+    // Does not fit in a u64
+    let bytes = [0xFF; 10].into_iter().chain([0x02]).collect::<Vec<_>>();
+    assert!(Test2::read_from_section(&mut Cursor::new(Section::with_bytes(&bytes))).is_err());
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 #[doc(hidden)]
+mod boxed;
+#[doc(hidden)]
 mod iterators;
 #[doc(hidden)]
 mod option;
@@ -6,6 +8,8 @@ mod option;
 mod primitive;
 #[doc(hidden)]
 mod range;
+#[doc(hidden)]
+mod varint;
 
 mod string;
 
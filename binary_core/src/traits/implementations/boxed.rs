@@ -0,0 +1,18 @@
+use crate::traits::{ReadFromSection, WriteToSection};
+
+impl<T: ReadFromSection> ReadFromSection for Box<T> {
+    fn read_from_section(
+        cursor: &mut std::io::Cursor<&crate::section::Section>,
+    ) -> crate::BinaryResult<Self> {
+        T::read_from_section(cursor).map(Box::new)
+    }
+}
+
+impl<T: WriteToSection> WriteToSection for Box<T> {
+    fn write_to_section(
+        &self,
+        cursor: &mut std::io::Cursor<&mut Vec<u8>>,
+    ) -> crate::BinaryResult<()> {
+        T::write_to_section(self, cursor)
+    }
+}
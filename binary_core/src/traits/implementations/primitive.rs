@@ -80,6 +80,8 @@ _impl! {
     i16
     i32
     i64
+    f32
+    f64
 }
 
 impl ReadFromSection for bool {
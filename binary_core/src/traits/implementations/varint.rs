@@ -0,0 +1,96 @@
+use crate::traits::{ReadFromSection, ReadVarIntFromSection, WriteToSection, WriteVarIntToSection};
+
+/// Unsigned LEB128
+macro unsigned_impl($($T:ty)*) {$(
+	impl ReadVarIntFromSection for $T {
+		fn read_varint_from_section(
+			cursor: &mut std::io::Cursor<&crate::section::Section>,
+		) -> Result<Self, crate::error::Error> {
+			let mut value = 0u128;
+			let mut shift = 0;
+			loop {
+				if shift >= <$T>::BITS + 7 {
+					return Err(crate::error::Error::IntegerOutOfRange);
+				}
+				let byte = u8::read_from_section(cursor)?;
+				value |= ((byte & 0x7F) as u128) << shift;
+				shift += 7;
+				if byte & 0x80 == 0 {
+					break;
+				}
+			}
+			Self::try_from(value).map_err(|_| crate::error::Error::IntegerOutOfRange)
+		}
+	}
+	impl WriteVarIntToSection for $T {
+		fn write_varint_to_section(
+			&self,
+			cursor: &mut std::io::Cursor<&mut Vec<u8>>,
+		) -> Result<(), crate::error::Error> {
+			let mut value = *self;
+			loop {
+				let byte = (value & 0x7F) as u8;
+				value >>= 7;
+				if value == 0 {
+					break byte.write_to_section(cursor);
+				}
+				(byte | 0x80).write_to_section(cursor)?;
+			}
+		}
+	}
+)*}
+
+/// Signed LEB128
+macro signed_impl($($T:ty)*) {$(
+	impl ReadVarIntFromSection for $T {
+		fn read_varint_from_section(
+			cursor: &mut std::io::Cursor<&crate::section::Section>,
+		) -> Result<Self, crate::error::Error> {
+			let mut value = 0i128;
+			let mut shift = 0;
+			loop {
+				if shift >= <$T>::BITS + 7 {
+					return Err(crate::error::Error::IntegerOutOfRange);
+				}
+				let byte = u8::read_from_section(cursor)?;
+				value |= ((byte & 0x7F) as i128) << shift;
+				shift += 7;
+				if byte & 0x80 == 0 {
+					if byte & 0x40 != 0 {
+						value |= -1 << shift;
+					}
+					break;
+				}
+			}
+			Self::try_from(value).map_err(|_| crate::error::Error::IntegerOutOfRange)
+		}
+	}
+	impl WriteVarIntToSection for $T {
+		fn write_varint_to_section(
+			&self,
+			cursor: &mut std::io::Cursor<&mut Vec<u8>>,
+		) -> Result<(), crate::error::Error> {
+			let mut value = *self;
+			loop {
+				let byte = (value & 0x7F) as u8;
+				value >>= 7;
+				if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+					break byte.write_to_section(cursor);
+				}
+				(byte | 0x80).write_to_section(cursor)?;
+			}
+		}
+	}
+)*}
+
+unsigned_impl! {
+    u16
+    u32
+    u64
+}
+
+signed_impl! {
+    i16
+    i32
+    i64
+}
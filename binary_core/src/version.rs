@@ -0,0 +1,239 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::Error,
+    traits::{ReadFromSection, WriteToSection},
+};
+
+/// A `major.minor.patch` version
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl Version {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    /// Parses `major.minor.patch`, ignoring any `-pre` or `+build` suffix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.split(['-', '+']).next().unwrap_or_default();
+        let mut parts = s.split('.').map(|x| x.parse::<u16>());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => {
+                Ok(Self::new(major, minor, patch))
+            }
+            _ => Err(Error::WrongFormat),
+        }
+    }
+}
+
+impl ReadFromSection for Version {
+    fn read_from_section(
+        cursor: &mut std::io::Cursor<&crate::section::Section>,
+    ) -> Result<Self, crate::error::Error> {
+        Ok(Self {
+            major: u16::read_from_section(cursor)?,
+            minor: u16::read_from_section(cursor)?,
+            patch: u16::read_from_section(cursor)?,
+        })
+    }
+}
+
+impl WriteToSection for Version {
+    fn write_to_section(
+        &self,
+        cursor: &mut std::io::Cursor<&mut Vec<u8>>,
+    ) -> Result<(), crate::error::Error> {
+        self.major.write_to_section(cursor)?;
+        self.minor.write_to_section(cursor)?;
+        self.patch.write_to_section(cursor)
+    }
+}
+
+/// How [`VersionRequirement::version`] is compared
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VersionRequirementKind {
+    /// `=a.b.c`
+    Exact = 0,
+    /// `^a.b.c`, versions compatible with `a.b.c` by the rules of semver
+    Compatible = 1,
+    /// `>=a.b.c`
+    AtLeast = 2,
+}
+
+/// A requirement on the version of a referenced assembly
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VersionRequirement {
+    pub kind: VersionRequirementKind,
+    pub version: Version,
+}
+
+impl VersionRequirement {
+    /// Matched by every version
+    pub const ANY: Self = Self::new(VersionRequirementKind::AtLeast, Version::new(0, 0, 0));
+
+    pub const fn new(kind: VersionRequirementKind, version: Version) -> Self {
+        Self { kind, version }
+    }
+    pub fn matches(&self, version: Version) -> bool {
+        let required = self.version;
+        match self.kind {
+            VersionRequirementKind::Exact => version == required,
+            VersionRequirementKind::AtLeast => version >= required,
+            VersionRequirementKind::Compatible => {
+                version >= required
+                    && match (required.major, required.minor) {
+                        (0, 0) => version == required,
+                        (0, minor) => version.major == 0 && version.minor == minor,
+                        (major, _) => version.major == major,
+                    }
+            }
+        }
+    }
+}
+
+impl Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.kind {
+            VersionRequirementKind::Exact => "=",
+            VersionRequirementKind::Compatible => "^",
+            VersionRequirementKind::AtLeast => ">=",
+        };
+        write!(f, "{op}{}", self.version)
+    }
+}
+
+impl FromStr for VersionRequirement {
+    type Err = Error;
+
+    /// Parses `=a.b.c`, `^a.b.c` or `>=a.b.c`, a bare `a.b.c` is the same as `^a.b.c`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, version) = if let Some(version) = s.strip_prefix(">=") {
+            (VersionRequirementKind::AtLeast, version)
+        } else if let Some(version) = s.strip_prefix('=') {
+            (VersionRequirementKind::Exact, version)
+        } else {
+            (
+                VersionRequirementKind::Compatible,
+                s.strip_prefix('^').unwrap_or(s),
+            )
+        };
+        Ok(Self::new(kind, version.parse()?))
+    }
+}
+
+impl ReadFromSection for VersionRequirement {
+    fn read_from_section(
+        cursor: &mut std::io::Cursor<&crate::section::Section>,
+    ) -> Result<Self, crate::error::Error> {
+        let kind = match u8::read_from_section(cursor)? {
+            0 => VersionRequirementKind::Exact,
+            1 => VersionRequirementKind::Compatible,
+            2 => VersionRequirementKind::AtLeast,
+            _ => return Err(Error::enum_out_of_bounds::<VersionRequirementKind>()),
+        };
+        Ok(Self::new(kind, Version::read_from_section(cursor)?))
+    }
+}
+
+impl WriteToSection for VersionRequirement {
+    fn write_to_section(
+        &self,
+        cursor: &mut std::io::Cursor<&mut Vec<u8>>,
+    ) -> Result<(), crate::error::Error> {
+        (self.kind as u8).write_to_section(cursor)?;
+        self.version.write_to_section(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requirement() -> Result<(), Error> {
+        let cases = [
+            (
+                "^1.2.3",
+                [
+                    ("1.2.3", true),
+                    ("1.9.0", true),
+                    ("2.0.0", false),
+                    ("1.2.2", false),
+                ],
+            ),
+            (
+                "0.2.3",
+                [
+                    ("0.2.3", true),
+                    ("0.2.9", true),
+                    ("0.3.0", false),
+                    ("1.0.0", false),
+                ],
+            ),
+            (
+                "^0.0.3",
+                [
+                    ("0.0.3", true),
+                    ("0.0.4", false),
+                    ("0.1.0", false),
+                    ("0.0.2", false),
+                ],
+            ),
+            (
+                "=1.2.3",
+                [
+                    ("1.2.3", true),
+                    ("1.2.4", false),
+                    ("1.2.2", false),
+                    ("2.0.0", false),
+                ],
+            ),
+            (
+                ">=1.2.3",
+                [
+                    ("1.2.3", true),
+                    ("9.0.0", true),
+                    ("1.3.0", true),
+                    ("1.2.2", false),
+                ],
+            ),
+        ];
+        for (requirement, versions) in cases {
+            let requirement: VersionRequirement = requirement.parse()?;
+            assert_eq!(
+                requirement.to_string().parse::<VersionRequirement>()?,
+                requirement
+            );
+            for (version, expected) in versions {
+                assert_eq!(
+                    requirement.matches(version.parse()?),
+                    expected,
+                    "{requirement} {version}"
+                );
+            }
+        }
+        assert!("~1.2.3".parse::<VersionRequirement>().is_err());
+
+        Ok(())
+    }
+}
@@ -10,8 +10,13 @@ mod read_from_section;
 mod write_to_section;
 
 define_derive_macros! {
-    ReadFromSection[read_from_file_bounds] => derive_read_from_section_impl;
-    WriteToSection[] => derive_write_to_section_impl;
+    ReadFromSection[read_from_file_bounds, varint] => derive_read_from_section_impl;
+    WriteToSection[varint] => derive_write_to_section_impl;
+}
+
+/// Fields marked `#[varint]` are encoded with LEB128 instead of their fixed width
+fn is_varint(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|x| x.path().is_ident("varint"))
 }
 
 #[proc_macro]
@@ -9,6 +9,8 @@ use syn::{
     Data, DeriveInput, Expr, Path, PredicateType, Token, TraitBound, Type, TypePath, WhereClause,
 };
 
+use crate::is_varint;
+
 pub fn derive_read_from_section_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     let binary_core = PredefinedCrateName::BinaryCore.as_ident(Span::call_site());
     let data = &input.data;
@@ -89,9 +91,10 @@ pub fn derive_read_from_section_impl(input: DeriveInput) -> syn::Result<TokenStr
                     }
                 })
                 .collect::<Vec<_>>();
-            let per_ident_expr = idents.iter().map(|x| {
+            let per_ident_expr = idents.iter().zip(&s.fields).map(|(x, f)| {
+                let reader = field_reader(&binary_core, f);
                 quote_spanned! {
-                    x.span() => #x: #binary_core::traits::ReadFromSection::read_from_section(cursor)?,
+                    x.span() => #x: #reader(cursor)?,
                 }
             });
             Ok(quote! {
@@ -164,10 +167,15 @@ pub fn derive_read_from_section_impl(input: DeriveInput) -> syn::Result<TokenStr
                         }
                     })
                     .collect::<Vec<_>>();
+                let readers = v
+                    .fields
+                    .iter()
+                    .map(|f| field_reader(&binary_core, f))
+                    .collect::<Vec<_>>();
                 let v_name = &v.ident;
                 ts.extend(quote! {
                     #type_ident::#v_name => Ok(#name::#v_name {
-                        #(#f_idents: #binary_core::traits::ReadFromSection::read_from_section(cursor)?,)*
+                        #(#f_idents: #readers(cursor)?,)*
                     }),
                 });
             }
@@ -192,6 +200,14 @@ pub fn derive_read_from_section_impl(input: DeriveInput) -> syn::Result<TokenStr
     }
 }
 
+fn field_reader(binary_core: &Ident, field: &syn::Field) -> TokenStream {
+    if is_varint(field) {
+        quote!(#binary_core::traits::ReadVarIntFromSection::read_varint_from_section)
+    } else {
+        quote!(#binary_core::traits::ReadFromSection::read_from_section)
+    }
+}
+
 struct ReadFromFileForeignInput {
     t: syn::Type,
     i: syn::LitInt,
@@ -3,6 +3,8 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{ToTokens, quote};
 use syn::{Data, DeriveInput, spanned::Spanned};
 
+use crate::is_varint;
+
 pub fn derive_write_to_section_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     let binary_core = PredefinedCrateName::BinaryCore.as_ident(Span::call_site());
     let data = &input.data;
@@ -83,6 +85,11 @@ pub fn derive_write_to_section_impl(input: DeriveInput) -> syn::Result<TokenStre
                     }
                 })
                 .collect::<Vec<_>>();
+            let writers = s
+                .fields
+                .iter()
+                .map(|f| field_writer(&binary_core, f))
+                .collect::<Vec<_>>();
             Ok(quote! {
                 impl #impl_g #binary_core::traits::WriteToSection for #name #ty_g #wh {
                     fn write_to_section(
@@ -90,7 +97,7 @@ pub fn derive_write_to_section_impl(input: DeriveInput) -> syn::Result<TokenStre
                         cursor: &mut std::io::Cursor<&mut std::vec::Vec<u8>>,
                     ) -> Result<(), #binary_core::error::Error> {
                         #(
-                            #binary_core::traits::WriteToSection::write_to_section(&self.#idents, cursor)?;
+                            #writers(&self.#idents, cursor)?;
                         )*
                         Ok(())
                     }
@@ -151,6 +158,11 @@ pub fn derive_write_to_section_impl(input: DeriveInput) -> syn::Result<TokenStre
                 } else {
                     f_idents.clone()
                 };
+                let writers = v
+                    .fields
+                    .iter()
+                    .map(|f| field_writer(&binary_core, f))
+                    .collect::<Vec<_>>();
                 let v_name = &v.ident;
                 let matcher = if is_unnamed {
                     quote!(#name::#v_name(
@@ -165,7 +177,7 @@ pub fn derive_write_to_section_impl(input: DeriveInput) -> syn::Result<TokenStre
                 ts.extend(quote! {
                     #matcher => {
                         #(
-                            #binary_core::traits::WriteToSection::write_to_section(#out_idents, cursor)?;
+                            #writers(#out_idents, cursor)?;
                         )*
                     }
                 });
@@ -192,3 +204,11 @@ pub fn derive_write_to_section_impl(input: DeriveInput) -> syn::Result<TokenStre
         )),
     }
 }
+
+fn field_writer(binary_core: &Ident, field: &syn::Field) -> TokenStream {
+    if is_varint(field) {
+        quote!(#binary_core::traits::WriteVarIntToSection::write_varint_to_section)
+    } else {
+        quote!(#binary_core::traits::WriteToSection::write_to_section)
+    }
+}
@@ -3,7 +3,10 @@
 use std::path::PathBuf;
 
 use clap::Parser;
-use pura_lingua::{binary::prelude::Assembly, runtime::virtual_machine::cpu_manager::CpuID};
+use pura_lingua::{
+    binary::{lazy::SharedLazyAssembly, prelude::Assembly},
+    runtime::virtual_machine::cpu_manager::CpuID,
+};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -15,6 +18,15 @@ struct Cli {
     /// <class name> could be `.`, which means that class has the same name as the assembly
     #[arg(long, value_parser = parse_main)]
     main: (String, String, String),
+    /// Verify the methods of the assemblies before loading them
+    #[arg(long)]
+    verify: bool,
+    /// Skip verifying the checksums of the assemblies
+    #[arg(long)]
+    skip_checksums: bool,
+    /// Decode the instructions of each method when it is first invoked
+    #[arg(long, conflicts_with = "verify")]
+    lazy: bool,
     args: Vec<String>,
 }
 
@@ -64,20 +76,44 @@ fn main() -> pura_lingua::global::Result<()> {
     let cli = Cli::parse();
     let vm = pura_lingua::runtime::virtual_machine::global_vm();
 
-    let binaries = cli
-        .assemblies
-        .iter()
-        .map(|x: &String| -> pura_lingua::global::Result<_> {
-            let path = search_assembly(x)?;
-            let bytes = std::fs::read(path)?;
-            pura_lingua::binary::assembly::AssemblyBuilder::from_bytes(bytes).map_err(From::from)
-        })
-        .try_collect::<Vec<_>>()?;
-    let binary_refs = binaries
-        .iter()
-        .map(|x| Assembly::from_builder(x))
-        .collect::<Vec<_>>();
-    vm.assembly_manager().load_binaries(&binary_refs)?;
+    if cli.lazy {
+        let binaries = cli
+            .assemblies
+            .iter()
+            .map(|x: &String| -> pura_lingua::global::Result<_> {
+                let path = search_assembly(x)?;
+                let bytes = std::fs::read(path)?;
+                Ok(SharedLazyAssembly::from_bytes_with(
+                    bytes,
+                    !cli.skip_checksums,
+                )?)
+            })
+            .try_collect::<Vec<_>>()?;
+        vm.assembly_manager().load_lazy_binaries(binaries)?;
+    } else {
+        let binaries = cli
+            .assemblies
+            .iter()
+            .map(|x: &String| -> pura_lingua::global::Result<_> {
+                let path = search_assembly(x)?;
+                let bytes = std::fs::read(path)?;
+                pura_lingua::binary::assembly::AssemblyBuilder::from_bytes_with(
+                    bytes,
+                    !cli.skip_checksums,
+                )
+                .map_err(From::from)
+            })
+            .try_collect::<Vec<_>>()?;
+        let binary_refs = binaries
+            .iter()
+            .map(|x| Assembly::from_builder(x))
+            .collect::<Vec<_>>();
+        if cli.verify {
+            vm.assembly_manager().load_verified_binaries(&binary_refs)?;
+        } else {
+            vm.assembly_manager().load_binaries(&binary_refs)?;
+        }
+    }
 
     let (main_assembly, main_class, main_method) = cli.main;
     let main_assembly = widestring::Utf16String::from_str(&main_assembly);
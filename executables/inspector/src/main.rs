@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+
+mod report;
+mod text;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    /// Path of the assembly
+    path: PathBuf,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Skip verifying the checksums of the assembly
+    #[arg(long)]
+    skip_checksums: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> pura_lingua::global::Result<()> {
+    let cli = Cli::parse();
+    let bytes = std::fs::read(&cli.path)?;
+    let report = report::Report::new(&bytes, !cli.skip_checksums)?;
+    match cli.format {
+        Format::Text => print!("{report}"),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(())
+}
@@ -0,0 +1,451 @@
+//! Everything printed about an assembly, with the tokens resolved to names
+
+use pura_lingua::{
+    binary::{
+        assembly::{Assembly, SECTION_NAMES},
+        binary_core::{BinaryResult, file::FileParser, traits::StringRef},
+        custom_attribute::{CustomAttributeValue, Integer, NamedArgument},
+        prelude::{MethodToken, MethodType, TypeToken, TypeType},
+        ty::{self, TypeDef},
+    },
+    global::attrs::{CallConvention, FieldAttr, MethodAttr, ParameterAttr, TypeAttr},
+};
+use serde::Serialize;
+
+/// Nested type specs deeper than it are printed as tokens
+const MAX_TYPE_DEPTH: usize = 16;
+
+#[derive(Serialize)]
+pub struct Report {
+    pub name: String,
+    pub version: String,
+    pub min_runtime_version: String,
+    pub features: u32,
+    pub format_version: [u8; 2],
+    pub encoding: u8,
+    pub sections: Vec<SectionReport>,
+    pub strings: Vec<StringReport>,
+    pub assembly_refs: Vec<String>,
+    pub type_refs: Vec<String>,
+    pub type_specs: Vec<String>,
+    pub method_specs: Vec<String>,
+    pub custom_attributes: Vec<CustomAttributeReport>,
+    pub type_defs: Vec<TypeDefReport>,
+}
+
+#[derive(Serialize)]
+pub struct SectionReport {
+    pub id: usize,
+    /// Unknown sections are kept by newer writers
+    pub name: Option<&'static str>,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct StringReport {
+    pub offset: u64,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+pub struct CustomAttributeReport {
+    pub ty: String,
+    pub ctor: String,
+    pub args: Vec<String>,
+    pub named_args: Vec<NamedArgumentReport>,
+}
+
+#[derive(Serialize)]
+pub struct NamedArgumentReport {
+    pub kind: &'static str,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+pub struct TypeDefReport {
+    pub index: u32,
+    pub kind: &'static str,
+    pub name: String,
+    pub attr: TypeAttr,
+    pub parent: Option<String>,
+    pub interfaces: Vec<String>,
+    pub fields: Vec<FieldReport>,
+    pub methods: Vec<MethodReport>,
+}
+
+#[derive(Serialize)]
+pub struct FieldReport {
+    pub name: String,
+    pub ty: String,
+    pub attr: FieldAttr,
+}
+
+#[derive(Serialize)]
+pub struct MethodReport {
+    pub index: u32,
+    pub name: String,
+    pub attr: MethodAttr<String>,
+    pub args: Vec<ParameterReport>,
+    pub return_type: String,
+    pub call_convention: CallConvention,
+    pub instructions: Vec<String>,
+    pub exception_table: Vec<ExceptionTableEntryReport>,
+}
+
+#[derive(Serialize)]
+pub struct ParameterReport {
+    pub ty: String,
+    pub attr: ParameterAttr,
+}
+
+#[derive(Serialize)]
+pub struct ExceptionTableEntryReport {
+    pub range: [u64; 2],
+    pub exception_type: String,
+    pub filter: Option<String>,
+    pub catch: [u64; 2],
+    pub finally: Option<[u64; 2]>,
+    pub fault: Option<[u64; 2]>,
+}
+
+impl Report {
+    pub fn new(bytes: &[u8], verify_checksums: bool) -> BinaryResult<Self> {
+        let parser = FileParser::from_bytes_with(bytes, verify_checksums)?;
+        let header = parser.get_header();
+        let (format_version, encoding) = (header.version(), header.encoding());
+        let sections = parser
+            .section_iter()?
+            .enumerate()
+            .map(|(id, section)| SectionReport {
+                id,
+                name: SECTION_NAMES.get(id).copied(),
+                size: section.len(),
+            })
+            .collect();
+        // Checked above
+        let assembly = Assembly::from_bytes_with(bytes, false)?;
+        let names = Names {
+            assembly: &assembly,
+        };
+
+        Ok(Self {
+            name: names.string(assembly.extra_header.name),
+            version: assembly.extra_header.version.to_string(),
+            min_runtime_version: assembly.extra_header.min_runtime_version.to_string(),
+            features: assembly.extra_header.features,
+            format_version,
+            encoding,
+            sections,
+            strings: strings(assembly.string_section.as_bytes()),
+            assembly_refs: assembly
+                .assembly_refs
+                .iter()
+                .map(|x| format!("{} {}", names.string(x.name), x.requirement))
+                .collect(),
+            type_refs: (0..assembly.type_refs.len())
+                .map(|index| names.ty(token(TypeType::TypeRef, index)))
+                .collect(),
+            type_specs: (0..assembly.type_specs.len())
+                .map(|index| names.ty(token(TypeType::TypeSpec, index)))
+                .collect(),
+            method_specs: (0..assembly.method_specs.len())
+                .map(|index| names.method(method_token(MethodType::MethodSpec, index)))
+                .collect(),
+            custom_attributes: assembly
+                .custom_attributes
+                .iter()
+                .map(|attr| CustomAttributeReport {
+                    ty: names.ty(attr.ty),
+                    ctor: names.method_of(attr.ty, attr.ctor_name),
+                    args: attr
+                        .positional_args
+                        .iter()
+                        .map(|x| names.custom_attribute_value(x))
+                        .collect(),
+                    named_args: attr
+                        .named_args
+                        .iter()
+                        .map(|x| NamedArgumentReport {
+                            kind: match x {
+                                NamedArgument::Field { .. } => "field",
+                                NamedArgument::Property { .. } => "property",
+                            },
+                            name: names.string(x.name()),
+                            value: names.custom_attribute_value(x.value()),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            type_defs: assembly
+                .type_defs
+                .iter()
+                .enumerate()
+                .map(|(index, type_def)| names.type_def(index as u32, type_def))
+                .collect(),
+        })
+    }
+}
+
+/// The strings in the string section, with their offsets
+fn strings(bytes: &[u8]) -> Vec<StringReport> {
+    let mut offset = 0;
+    let mut result = Vec::new();
+    for string in bytes.split(|x| *x == 0) {
+        // The section ends with the terminator of the last string
+        if offset < bytes.len() {
+            result.push(StringReport {
+                offset: offset as u64,
+                value: String::from_utf8_lossy(string).into_owned(),
+            });
+        }
+        offset += string.len() + 1;
+    }
+    result
+}
+
+fn token(ty: TypeType, index: usize) -> TypeToken {
+    pura_lingua::binary::prelude::TypeTokenBuilder::new()
+        .with_ty(ty)
+        .with_index(index as u32)
+        .build()
+}
+
+fn method_token(ty: MethodType, index: usize) -> MethodToken {
+    pura_lingua::binary::prelude::MethodTokenBuilder::new()
+        .with_ty(ty)
+        .with_index(index as u32)
+        .build()
+}
+
+fn range(range: std::range::Range<u64>) -> [u64; 2] {
+    [range.start, range.end]
+}
+
+/// Resolves the tokens of an assembly, falling back to the tokens themselves
+/// if they are out of range
+struct Names<'a> {
+    assembly: &'a Assembly<'a>,
+}
+
+impl Names<'_> {
+    fn string(&self, string_ref: StringRef) -> String {
+        self.assembly
+            .get_string(string_ref)
+            .map_or_else(|_| format!("{string_ref:?}"), ToOwned::to_owned)
+    }
+    fn ty(&self, tt: TypeToken) -> String {
+        self.ty_with_depth(tt, 0)
+    }
+    fn ty_with_depth(&self, tt: TypeToken, depth: usize) -> String {
+        let index = tt.index() as usize;
+        let resolved = match tt.ty() {
+            TypeType::TypeDef => self
+                .assembly
+                .type_defs
+                .get(index)
+                .map(|x| self.string(*x.name())),
+            TypeType::TypeRef => self
+                .assembly
+                .type_refs
+                .get(index)
+                .map(|x| format!("[{}]{}", self.string(x.assembly), x.index)),
+            TypeType::TypeSpec if depth < MAX_TYPE_DEPTH => {
+                self.assembly.type_specs.get(index).map(|x| {
+                    format!(
+                        "{}<{}>",
+                        self.ty_with_depth(x.ty, depth + 1),
+                        self.types_with_depth(&x.generics, depth + 1)
+                    )
+                })
+            }
+            TypeType::TypeSpec => None,
+            TypeType::MethodGeneric => Some(format!("method_generic[{index}]")),
+            TypeType::TypeGeneric => Some(format!("type_generic[{index}]")),
+        };
+        resolved.unwrap_or_else(|| tt.to_string())
+    }
+    fn types_with_depth(&self, types: &[TypeToken], depth: usize) -> String {
+        types
+            .iter()
+            .map(|x| self.ty_with_depth(*x, depth))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+    /// Methods are only known by their indexes in the method tables of their types
+    fn method(&self, mt: MethodToken) -> String {
+        let index = mt.index() as usize;
+        let resolved = match mt.ty() {
+            MethodType::Method => Some(format!("method[{index}]")),
+            MethodType::MethodSpec => self
+                .assembly
+                .method_specs
+                .get(index)
+                .map(|x| format!("method[{}]<{}>", x.m, self.types_with_depth(&x.generics, 0))),
+            MethodType::MethodByRuntime => Some(format!("method_by_runtime[{index}]")),
+        };
+        resolved.unwrap_or_else(|| mt.to_string())
+    }
+    /// Like [`Self::method`], but resolved to the name of the method
+    /// if the method table of `ty` is known from this assembly
+    fn method_of(&self, ty: TypeToken, mt: MethodToken) -> String {
+        let index = mt.index() as usize;
+        let (m, generics) = match mt.ty() {
+            MethodType::Method => (index, None),
+            MethodType::MethodSpec => match self.assembly.method_specs.get(index) {
+                Some(spec) => (spec.m as usize, Some(&spec.generics)),
+                None => return self.method(mt),
+            },
+            MethodType::MethodByRuntime => return self.method(mt),
+        };
+        let Some(method) = self
+            .assembly
+            .local_type_def(ty)
+            .and_then(|type_def| self.assembly.method_table(type_def))
+            .and_then(|methods| methods.get(m).copied())
+        else {
+            return self.method(mt);
+        };
+        match generics {
+            Some(generics) => format!(
+                "{}<{}>",
+                self.string(method.name),
+                self.types_with_depth(generics, 0)
+            ),
+            None => self.string(method.name),
+        }
+    }
+    fn custom_attribute_value(&self, value: &CustomAttributeValue) -> String {
+        match value {
+            CustomAttributeValue::Boolean(x) => x.to_string(),
+            CustomAttributeValue::Char(x) => format!("{x:?}"),
+            CustomAttributeValue::Integer(x) => integer(x),
+            CustomAttributeValue::String(x) => format!("{:?}", self.string(*x)),
+            CustomAttributeValue::SystemType(x) => format!("typeof({})", self.ty(*x)),
+            CustomAttributeValue::PureEnum { ty, val } => {
+                format!("({}){}", self.ty(*ty), integer(val))
+            }
+            CustomAttributeValue::Single(x) => format!("{x:?}f"),
+            CustomAttributeValue::Double(x) => format!("{x:?}"),
+            CustomAttributeValue::Object { ty, val } => {
+                format!(
+                    "(object)({}){}",
+                    self.ty(*ty),
+                    self.custom_attribute_value(val)
+                )
+            }
+            CustomAttributeValue::Array(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|x| self.custom_attribute_value(x))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+    fn type_def(&self, index: u32, type_def: &TypeDef) -> TypeDefReport {
+        let (kind, parent, interfaces): (_, _, Vec<String>) = match type_def {
+            TypeDef::Class(class_def) => (
+                "class",
+                class_def.parent.map(|x| self.ty(x)),
+                class_def
+                    .interfaces
+                    .iter()
+                    .map(|x| self.ty(x.target))
+                    .collect(),
+            ),
+            TypeDef::Struct(_) => ("struct", None, Vec::new()),
+            TypeDef::Interface(interface_def) => (
+                "interface",
+                None,
+                interface_def
+                    .required_interfaces
+                    .iter()
+                    .map(|x| self.ty(*x))
+                    .collect(),
+            ),
+        };
+        TypeDefReport {
+            index,
+            kind,
+            name: self.string(*type_def.name()),
+            attr: type_def.attr(),
+            parent,
+            interfaces,
+            fields: type_def
+                .fields()
+                .iter()
+                .map(|field| FieldReport {
+                    name: self.string(field.name),
+                    ty: self.ty(field.ty),
+                    attr: field.attr,
+                })
+                .collect(),
+            methods: type_def
+                .method_table()
+                .iter()
+                .enumerate()
+                .map(|(index, method)| self.method_def(index as u32, method))
+                .collect(),
+        }
+    }
+    fn method_def(&self, index: u32, method: &ty::Method) -> MethodReport {
+        MethodReport {
+            index,
+            name: self.string(method.name),
+            attr: method.attr.clone().map_types(|x| self.ty(x)),
+            args: method
+                .args
+                .iter()
+                .map(|param| ParameterReport {
+                    ty: self.ty(param.ty),
+                    attr: param.attr,
+                })
+                .collect(),
+            return_type: self.ty(method.return_type),
+            call_convention: method.call_convention,
+            instructions: method
+                .instructions
+                .iter()
+                .map(|ins| {
+                    ins.clone()
+                        .map(
+                            |s| format!("{:?}", self.string(s)),
+                            |tt| self.ty(tt),
+                            |mt| self.method(mt),
+                            |field| field,
+                        )
+                        .to_string()
+                })
+                .collect(),
+            exception_table: method
+                .exception_table
+                .iter()
+                .map(|entry| ExceptionTableEntryReport {
+                    range: range(entry.range),
+                    exception_type: self.ty(entry.exception_type),
+                    filter: entry.filter.map(|(ty, method)| {
+                        format!("{}::{}", self.ty(ty), self.method_of(ty, method))
+                    }),
+                    catch: range(entry.catch),
+                    finally: entry.finally.map(range),
+                    fault: entry.fault.map(range),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn integer(value: &Integer) -> String {
+    match value {
+        Integer::Byte(x) => x.to_string(),
+        Integer::SByte(x) => x.to_string(),
+        Integer::Short(x) => x.to_string(),
+        Integer::UShort(x) => x.to_string(),
+        Integer::Int(x) => x.to_string(),
+        Integer::UInt(x) => x.to_string(),
+        Integer::Long(x) => x.to_string(),
+        Integer::ULong(x) => x.to_string(),
+    }
+}
@@ -0,0 +1,98 @@
+use std::io::Cursor;
+
+use crate::report::Report;
+
+const SOURCE: &str = r#".assembly "Test::Inspect"
+
+.typeref "!" 0
+.typeref "!" 2
+.typeref "!" 12
+.typespec typedef[1] [typeref[2]]
+.custom_attribute { ty: typedef[0], ctor_name: method[0], positional_args: [Integer Int 3], named_args: [Field { name: "Count", value: Integer Int 1 }] }
+.custom_attribute { ty: typespec[0], ctor_name: method[1], positional_args: [], named_args: [] }
+.custom_attribute { ty: typeref[0], ctor_name: method[0], positional_args: [], named_args: [] }
+
+.class Public "Test::Inspect::Attribute"
+    .field Public "Count" typeref[2]
+
+    .method Public ".ctor" (typeref[2]) -> typeref[1]
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+
+.class Public "Test::Inspect::Derived"
+    .generics Exact 1
+    .extends typedef[0]
+
+    .method Public "Get" (type_generic[0]) -> type_generic[0]
+        .locals [type_generic[0]]
+        Load { addr: %0, content: Arg 0 }
+    L1:
+        ReturnVal { register_addr: %0 }
+    L2:
+        .try @L1 @L2 catch typeref[0] @L1 @L2
+    .end
+.end
+"#;
+
+fn report() -> Report {
+    let mut bytes = Cursor::new(Vec::new());
+    assembler::assemble(SOURCE)
+        .unwrap()
+        .write_to(&mut bytes)
+        .unwrap();
+    Report::new(&bytes.into_inner(), true).unwrap()
+}
+
+#[test]
+fn resolving_names() {
+    let report = report();
+    assert_eq!(report.name, "Test::Inspect");
+    assert_eq!(report.type_refs, ["[!]0", "[!]2", "[!]12"]);
+    assert_eq!(report.type_specs, ["Test::Inspect::Derived<[!]12>"]);
+
+    let [attribute, derived] = &report.type_defs[..] else {
+        panic!("Expected two type defs");
+    };
+    assert_eq!(attribute.name, "Test::Inspect::Attribute");
+    assert_eq!(attribute.parent, None);
+    assert_eq!(attribute.fields[0].name, "Count");
+    assert_eq!(attribute.fields[0].ty, "[!]12");
+    assert_eq!(attribute.methods[0].name, ".ctor");
+    assert_eq!(attribute.methods[0].args[0].ty, "[!]12");
+    assert_eq!(attribute.methods[0].return_type, "[!]2");
+
+    assert_eq!(derived.parent.as_deref(), Some("Test::Inspect::Attribute"));
+    let get = &derived.methods[0];
+    assert_eq!(get.name, "Get");
+    assert_eq!(get.return_type, "type_generic[0]");
+    assert_eq!(get.exception_table[0].exception_type, "[!]0");
+
+    let ctors = report
+        .custom_attributes
+        .iter()
+        .map(|x| (x.ty.as_str(), x.ctor.as_str()))
+        .collect::<Vec<_>>();
+    // Methods of the parent come first,
+    // and methods of types in other assemblies cannot be resolved
+    assert_eq!(
+        ctors,
+        [
+            ("Test::Inspect::Attribute", ".ctor"),
+            ("Test::Inspect::Derived<[!]12>", "Get"),
+            ("[!]0", "method[0]"),
+        ]
+    );
+    assert_eq!(report.custom_attributes[0].args, ["3"]);
+    assert_eq!(report.custom_attributes[0].named_args[0].name, "Count");
+}
+
+#[test]
+fn json_snapshot() {
+    assert_eq!(
+        serde_json::to_string_pretty(&report()).unwrap(),
+        include_str!("tests/report.json").trim_end()
+    );
+}
@@ -0,0 +1,134 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::report::{MethodReport, Report, TypeDefReport};
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Assembly {} {}", self.name, self.version)?;
+        writeln!(f, "    min runtime: {}", self.min_runtime_version)?;
+        writeln!(f, "    features: {:#x}", self.features)?;
+        writeln!(
+            f,
+            "    format: {}.{}, encoding {}",
+            self.format_version[0], self.format_version[1], self.encoding
+        )?;
+
+        writeln!(f, "\nSections:")?;
+        for section in &self.sections {
+            writeln!(
+                f,
+                "    {:>3} {:<16} {} bytes",
+                section.id,
+                section.name.unwrap_or("<unknown>"),
+                section.size
+            )?;
+        }
+
+        writeln!(f, "\nStrings:")?;
+        for string in &self.strings {
+            writeln!(f, "    {:>8} {:?}", string.offset, string.value)?;
+        }
+
+        list(f, "Assembly refs", &self.assembly_refs)?;
+        list(f, "Type refs", &self.type_refs)?;
+        list(f, "Type specs", &self.type_specs)?;
+        list(f, "Method specs", &self.method_specs)?;
+
+        if !self.custom_attributes.is_empty() {
+            writeln!(f, "\nCustom attributes:")?;
+            for attr in &self.custom_attributes {
+                let named_args = attr
+                    .named_args
+                    .iter()
+                    .map(|x| format!("{} {} = {}", x.kind, x.name, x.value));
+                let args = attr
+                    .args
+                    .iter()
+                    .cloned()
+                    .chain(named_args)
+                    .collect::<Vec<_>>();
+                writeln!(f, "    {}::{}({})", attr.ty, attr.ctor, args.join(", "))?;
+            }
+        }
+
+        for type_def in &self.type_defs {
+            writeln!(f)?;
+            write!(f, "{type_def}")?;
+        }
+        Ok(())
+    }
+}
+
+fn list(f: &mut Formatter<'_>, title: &str, items: &[String]) -> Result {
+    if items.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "\n{title}:")?;
+    for (index, item) in items.iter().enumerate() {
+        writeln!(f, "    {index:>3} {item}")?;
+    }
+    Ok(())
+}
+
+impl Display for TypeDefReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} {} {}", self.index, self.kind, self.name)?;
+        if let Some(parent) = &self.parent {
+            write!(f, " : {parent}")?;
+        }
+        if !self.interfaces.is_empty() {
+            write!(f, " implements {}", self.interfaces.join(", "))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "    {:?}", self.attr)?;
+        for field in &self.fields {
+            writeln!(f, "    field {}: {} {:?}", field.name, field.ty, field.attr)?;
+        }
+        for method in &self.methods {
+            write!(f, "{method}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for MethodReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let args = self
+            .args
+            .iter()
+            .map(|x| x.ty.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            f,
+            "    method {} {}({}) -> {} [{}]",
+            self.index, self.name, args, self.return_type, self.call_convention
+        )?;
+        writeln!(f, "        {:?}", self.attr)?;
+        for (pc, ins) in self.instructions.iter().enumerate() {
+            writeln!(f, "        {pc:>4}: {ins}")?;
+        }
+        for entry in &self.exception_table {
+            write!(
+                f,
+                "        try {}..{} catch {} at {}..{}",
+                entry.range[0],
+                entry.range[1],
+                entry.exception_type,
+                entry.catch[0],
+                entry.catch[1]
+            )?;
+            if let Some(filter) = &entry.filter {
+                write!(f, " when {filter}")?;
+            }
+            if let Some([start, end]) = entry.finally {
+                write!(f, " finally {start}..{end}")?;
+            }
+            if let Some([start, end]) = entry.fault {
+                write!(f, " fault {start}..{end}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use binary_proc_macros::{ReadFromSection, WriteToSection};
+use global_proc_macros::WithType;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::{IRegisterAddr, RegisterAddr, ShortRegisterAddr};
+
+/// Accesses the elements of a `System::Array`1` without calling its methods
+///
+/// `index` is a `System::USize`,
+/// `System::IndexOutOfRangeException` is thrown if it is out of range
+#[derive(Clone, Copy, Debug, ReadFromSection, WriteToSection, WithType)]
+#[with_type(derive_const = (Clone, PartialEq, Eq))]
+#[with_type(repr = u8)]
+#[with_type(derive = (Copy, IntoPrimitive, TryFromPrimitive, Debug, ReadFromSection, WriteToSection))]
+pub enum Instruction_Array<TRegisterAddr>
+where
+    TRegisterAddr: IRegisterAddr,
+{
+    /// Copies the element to `output`
+    LoadElement {
+        array: TRegisterAddr,
+        index: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+    /// Copies `value` to the element
+    StoreElement {
+        array: TRegisterAddr,
+        index: TRegisterAddr,
+        value: TRegisterAddr,
+    },
+    /// Writes the pointer to the element to `output`
+    LoadElementAddress {
+        array: TRegisterAddr,
+        index: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+    /// Writes the length as `System::USize` to `output`
+    ArrayLength {
+        array: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+}
+
+impl Instruction_Array<RegisterAddr> {
+    pub fn try_into_short(self) -> Result<Instruction_Array<ShortRegisterAddr>, Self> {
+        match self {
+            Instruction_Array::LoadElement {
+                array,
+                index,
+                output,
+            } => {
+                if let Some(array) = array.try_into_short()
+                    && let Some(index) = index.try_into_short()
+                    && let Some(output) = output.try_into_short()
+                {
+                    Ok(Instruction_Array::LoadElement {
+                        array,
+                        index,
+                        output,
+                    })
+                } else {
+                    Err(self)
+                }
+            }
+            Instruction_Array::StoreElement {
+                array,
+                index,
+                value,
+            } => {
+                if let Some(array) = array.try_into_short()
+                    && let Some(index) = index.try_into_short()
+                    && let Some(value) = value.try_into_short()
+                {
+                    Ok(Instruction_Array::StoreElement {
+                        array,
+                        index,
+                        value,
+                    })
+                } else {
+                    Err(self)
+                }
+            }
+            Instruction_Array::LoadElementAddress {
+                array,
+                index,
+                output,
+            } => {
+                if let Some(array) = array.try_into_short()
+                    && let Some(index) = index.try_into_short()
+                    && let Some(output) = output.try_into_short()
+                {
+                    Ok(Instruction_Array::LoadElementAddress {
+                        array,
+                        index,
+                        output,
+                    })
+                } else {
+                    Err(self)
+                }
+            }
+            Instruction_Array::ArrayLength { array, output } => {
+                if let Some(array) = array.try_into_short()
+                    && let Some(output) = output.try_into_short()
+                {
+                    Ok(Instruction_Array::ArrayLength { array, output })
+                } else {
+                    Err(self)
+                }
+            }
+        }
+    }
+}
+
+impl<TRegisterAddr: IRegisterAddr> std::fmt::Display for Instruction_Array<TRegisterAddr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction_Array::LoadElement {
+                array,
+                index,
+                output,
+            } => write!(f, " LoadElement {array:#x}[{index:#x}] -> {output:#x}"),
+            Instruction_Array::StoreElement {
+                array,
+                index,
+                value,
+            } => write!(f, " StoreElement {value:#x} -> {array:#x}[{index:#x}]"),
+            Instruction_Array::LoadElementAddress {
+                array,
+                index,
+                output,
+            } => write!(
+                f,
+                " LoadElementAddress &{array:#x}[{index:#x}] -> {output:#x}"
+            ),
+            Instruction_Array::ArrayLength { array, output } => {
+                write!(f, " ArrayLength {array:#x} -> {output:#x}")
+            }
+        }
+    }
+}
@@ -23,6 +23,9 @@ where
     I16(Instruction_UntypedCalculate<TRegisterAddr, i16>),
     I32(Instruction_UntypedCalculate<TRegisterAddr, i32>),
     I64(Instruction_UntypedCalculate<TRegisterAddr, i64>),
+
+    F32(Instruction_UntypedCalculate<TRegisterAddr, f32>),
+    F64(Instruction_UntypedCalculate<TRegisterAddr, f64>),
 }
 
 #[allow(nonstandard_style)]
@@ -93,6 +96,15 @@ impl Instruction_Calculate<RegisterAddr> {
                 .try_into_short()
                 .map(Instruction_Calculate::I64)
                 .map_err(Self::I64),
+
+            Instruction_Calculate::F32(ins) => ins
+                .try_into_short()
+                .map(Instruction_Calculate::F32)
+                .map_err(Self::F32),
+            Instruction_Calculate::F64(ins) => ins
+                .try_into_short()
+                .map(Instruction_Calculate::F64)
+                .map_err(Self::F64),
         }
     }
 }
@@ -109,10 +121,15 @@ impl<TRegisterAddr: IRegisterAddr> Display for Instruction_Calculate<TRegisterAd
             Instruction_Calculate::I16(ins) => f.write_fmt(format_args!("I16{ins}")),
             Instruction_Calculate::I32(ins) => f.write_fmt(format_args!("I32{ins}")),
             Instruction_Calculate::I64(ins) => f.write_fmt(format_args!("I64{ins}")),
+
+            Instruction_Calculate::F32(ins) => f.write_fmt(format_args!("F32{ins}")),
+            Instruction_Calculate::F64(ins) => f.write_fmt(format_args!("F64{ins}")),
         }
     }
 }
 
+/// The plain arithmetic operations wrap on overflow,
+/// and integer division by zero throws `System::DivideByZeroException`
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, WithType, ReadFromSection, WriteToSection)]
 #[with_type(derive_const = (Clone, PartialEq, Eq))]
@@ -187,6 +204,183 @@ where
     SubOne {
         target: TRegisterAddr,
     },
+
+    And {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    Or {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    Xor {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    Not {
+        source: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+
+    /// The shift amount is taken modulo the bit width of the type
+    Shl {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    /// Logical right shift, which always fills the high bits with zeros
+    ///
+    /// The shift amount is taken modulo the bit width of the type
+    Shr {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    /// Arithmetic right shift, which fills the high bits with the sign bit
+    ///
+    /// The shift amount is taken modulo the bit width of the type
+    Sar {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    RotateLeft {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    RotateRight {
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+
+    ConstAndTo {
+        target: TRegisterAddr,
+        data: TRust,
+    },
+    ConstOrTo {
+        target: TRegisterAddr,
+        data: TRust,
+    },
+    ConstXorTo {
+        target: TRegisterAddr,
+        data: TRust,
+    },
+
+    ConstShlTo {
+        target: TRegisterAddr,
+        #[varint]
+        amount: u32,
+    },
+    ConstShrTo {
+        target: TRegisterAddr,
+        #[varint]
+        amount: u32,
+    },
+    ConstSarTo {
+        target: TRegisterAddr,
+        #[varint]
+        amount: u32,
+    },
+    ConstRotateLeftTo {
+        target: TRegisterAddr,
+        #[varint]
+        amount: u32,
+    },
+    ConstRotateRightTo {
+        target: TRegisterAddr,
+        #[varint]
+        amount: u32,
+    },
+
+    /// `lhs <op> rhs -> target`
+    Arithmetic {
+        op: ArithmeticOp,
+        mode: OverflowMode,
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+        target: TRegisterAddr,
+    },
+    /// `target <op> data -> target`
+    ConstArithmeticTo {
+        op: ArithmeticOp,
+        mode: OverflowMode,
+        target: TRegisterAddr,
+        data: TRust,
+    },
+    /// `data <op> target -> target`
+    ArithmeticByConst {
+        op: ArithmeticOp,
+        mode: OverflowMode,
+        target: TRegisterAddr,
+        data: TRust,
+    },
+}
+
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    ReadFromSection,
+    WriteToSection,
+)]
+pub enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Display for ArithmeticOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ArithmeticOp::Add => "+",
+            ArithmeticOp::Sub => "-",
+            ArithmeticOp::Mul => "*",
+            ArithmeticOp::Div => "/",
+            ArithmeticOp::Rem => "%",
+        })
+    }
+}
+
+/// How integer overflow is handled, floating-point calculations ignore it
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    ReadFromSection,
+    WriteToSection,
+)]
+pub enum OverflowMode {
+    Wrapping,
+    /// Throws `System::OverflowException` on overflow
+    Checked,
+    Saturating,
+}
+
+impl Display for OverflowMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OverflowMode::Wrapping => "wrapping",
+            OverflowMode::Checked => "checked",
+            OverflowMode::Saturating => "saturating",
+        })
+    }
 }
 
 impl<TRust: Copy> Instruction_UntypedCalculate<RegisterAddr, TRust> {
@@ -284,11 +478,211 @@ impl<TRust: Copy> Instruction_UntypedCalculate<RegisterAddr, TRust> {
                 .try_into_short()
                 .map(|target| SubOne { target })
                 .ok_or(self),
+
+            And { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| And { lhs, rhs, target })
+                })
+                .ok_or(self),
+            Or { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| Or { lhs, rhs, target })
+                })
+                .ok_or(self),
+            Xor { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| Xor { lhs, rhs, target })
+                })
+                .ok_or(self),
+            Not { source, target } => source
+                .try_into_short()
+                .and_then(|source| target.try_into_short().map(|target| Not { source, target }))
+                .ok_or(self),
+
+            Shl { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| Shl { lhs, rhs, target })
+                })
+                .ok_or(self),
+            Shr { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| Shr { lhs, rhs, target })
+                })
+                .ok_or(self),
+            Sar { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| Sar { lhs, rhs, target })
+                })
+                .ok_or(self),
+            RotateLeft { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| RotateLeft { lhs, rhs, target })
+                })
+                .ok_or(self),
+            RotateRight { lhs, rhs, target } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target
+                        .try_into_short()
+                        .map(|target| RotateRight { lhs, rhs, target })
+                })
+                .ok_or(self),
+
+            ConstAndTo { target, data } => target
+                .try_into_short()
+                .map(|target| ConstAndTo { target, data })
+                .ok_or(self),
+            ConstOrTo { target, data } => target
+                .try_into_short()
+                .map(|target| ConstOrTo { target, data })
+                .ok_or(self),
+            ConstXorTo { target, data } => target
+                .try_into_short()
+                .map(|target| ConstXorTo { target, data })
+                .ok_or(self),
+
+            ConstShlTo { target, amount } => target
+                .try_into_short()
+                .map(|target| ConstShlTo { target, amount })
+                .ok_or(self),
+            ConstShrTo { target, amount } => target
+                .try_into_short()
+                .map(|target| ConstShrTo { target, amount })
+                .ok_or(self),
+            ConstSarTo { target, amount } => target
+                .try_into_short()
+                .map(|target| ConstSarTo { target, amount })
+                .ok_or(self),
+            ConstRotateLeftTo { target, amount } => target
+                .try_into_short()
+                .map(|target| ConstRotateLeftTo { target, amount })
+                .ok_or(self),
+            ConstRotateRightTo { target, amount } => target
+                .try_into_short()
+                .map(|target| ConstRotateRightTo { target, amount })
+                .ok_or(self),
+
+            Arithmetic {
+                op,
+                mode,
+                lhs,
+                rhs,
+                target,
+            } => lhs
+                .try_into_short()
+                .and_then(|lhs| rhs.try_into_short().map(|rhs| (lhs, rhs)))
+                .and_then(|(lhs, rhs)| {
+                    target.try_into_short().map(|target| Arithmetic {
+                        op,
+                        mode,
+                        lhs,
+                        rhs,
+                        target,
+                    })
+                })
+                .ok_or(self),
+            ConstArithmeticTo {
+                op,
+                mode,
+                target,
+                data,
+            } => target
+                .try_into_short()
+                .map(|target| ConstArithmeticTo {
+                    op,
+                    mode,
+                    target,
+                    data,
+                })
+                .ok_or(self),
+            ArithmeticByConst {
+                op,
+                mode,
+                target,
+                data,
+            } => target
+                .try_into_short()
+                .map(|target| ArithmeticByConst {
+                    op,
+                    mode,
+                    target,
+                    data,
+                })
+                .ok_or(self),
         }
     }
 }
 
-impl<TRegisterAddr: IRegisterAddr, TRust: Display + std::fmt::LowerHex + Copy> Display
+/// Immediates that can be carried by [`Instruction_UntypedCalculate`]
+pub trait CalculateConst: Copy + Display {
+    /// The raw bits of the immediate, used to show it in hex
+    fn raw_bits(self) -> u64;
+}
+
+macro impl_calculate_const_for_integers($($T:ty => $U:ty),* $(,)?) {$(
+    impl CalculateConst for $T {
+        #[inline(always)]
+        fn raw_bits(self) -> u64 {
+            self as $U as u64
+        }
+    }
+)*}
+
+impl_calculate_const_for_integers!(
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+);
+
+impl CalculateConst for f32 {
+    #[inline(always)]
+    fn raw_bits(self) -> u64 {
+        self.to_bits() as u64
+    }
+}
+
+impl CalculateConst for f64 {
+    #[inline(always)]
+    fn raw_bits(self) -> u64 {
+        self.to_bits()
+    }
+}
+
+impl<TRegisterAddr: IRegisterAddr, TRust: CalculateConst> Display
     for Instruction_UntypedCalculate<TRegisterAddr, TRust>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -310,29 +704,61 @@ impl<TRegisterAddr: IRegisterAddr, TRust: Display + std::fmt::LowerHex + Copy> D
             }
 
             Instruction_UntypedCalculate::ConstAddTo { target, data } => {
-                write!(f, " {target:#x} + const{data}(0x{data:#x}) -> {target:#x}")
+                write!(
+                    f,
+                    " {target:#x} + const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
             }
             Instruction_UntypedCalculate::ConstSubTo { target, data } => {
-                write!(f, " {target:#x} - const{data}(0x{data:#x}) -> {target:#x}")
+                write!(
+                    f,
+                    " {target:#x} - const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
             }
             Instruction_UntypedCalculate::ConstMulTo { target, data } => {
-                write!(f, " {target:#x} * const{data}(0x{data:#x}) -> {target:#x}")
+                write!(
+                    f,
+                    " {target:#x} * const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
             }
             Instruction_UntypedCalculate::ConstDivTo { target, data } => {
-                write!(f, " {target:#x} / const{data}(0x{data:#x}) -> {target:#x}")
+                write!(
+                    f,
+                    " {target:#x} / const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
             }
             Instruction_UntypedCalculate::ConstRemTo { target, data } => {
-                write!(f, " {target:#x} % const{data}(0x{data:#x}) -> {target:#x}")
+                write!(
+                    f,
+                    " {target:#x} % const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
             }
 
             Instruction_UntypedCalculate::SubByConst { target, data } => {
-                write!(f, " const{data}(0x{data:#x}) - {target:#x} -> {target:#x}")
+                write!(
+                    f,
+                    " const{data}({:#x}) - {target:#x} -> {target:#x}",
+                    data.raw_bits()
+                )
             }
             Instruction_UntypedCalculate::DivByConst { target, data } => {
-                write!(f, " const{data}(0x{data:#x}) / {target:#x} -> {target:#x}")
+                write!(
+                    f,
+                    " const{data}({:#x}) / {target:#x} -> {target:#x}",
+                    data.raw_bits()
+                )
             }
             Instruction_UntypedCalculate::RemByConst { target, data } => {
-                write!(f, " const{data}(0x{data:#x}) % {target:#x} -> {target:#x}")
+                write!(
+                    f,
+                    " const{data}({:#x}) % {target:#x} -> {target:#x}",
+                    data.raw_bits()
+                )
             }
 
             Instruction_UntypedCalculate::AddOne { target } => {
@@ -341,6 +767,103 @@ impl<TRegisterAddr: IRegisterAddr, TRust: Display + std::fmt::LowerHex + Copy> D
             Instruction_UntypedCalculate::SubOne { target } => {
                 write!(f, " {target} - const1 -> {target:#x}")
             }
+
+            Instruction_UntypedCalculate::And { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} & {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::Or { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} | {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::Xor { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} ^ {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::Not { source, target } => {
+                f.write_fmt(format_args!(" !{source:#x} -> {target:#x}"))
+            }
+
+            Instruction_UntypedCalculate::Shl { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} << {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::Shr { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} >>> {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::Sar { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} >> {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::RotateLeft { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} rol {rhs:#x} -> {target:#x}"))
+            }
+            Instruction_UntypedCalculate::RotateRight { lhs, rhs, target } => {
+                f.write_fmt(format_args!(" {lhs:#x} ror {rhs:#x} -> {target:#x}"))
+            }
+
+            Instruction_UntypedCalculate::ConstAndTo { target, data } => {
+                write!(
+                    f,
+                    " {target:#x} & const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
+            }
+            Instruction_UntypedCalculate::ConstOrTo { target, data } => {
+                write!(
+                    f,
+                    " {target:#x} | const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
+            }
+            Instruction_UntypedCalculate::ConstXorTo { target, data } => {
+                write!(
+                    f,
+                    " {target:#x} ^ const{data}({:#x}) -> {target:#x}",
+                    data.raw_bits()
+                )
+            }
+
+            Instruction_UntypedCalculate::ConstShlTo { target, amount } => {
+                write!(f, " {target:#x} << const{amount} -> {target:#x}")
+            }
+            Instruction_UntypedCalculate::ConstShrTo { target, amount } => {
+                write!(f, " {target:#x} >>> const{amount} -> {target:#x}")
+            }
+            Instruction_UntypedCalculate::ConstSarTo { target, amount } => {
+                write!(f, " {target:#x} >> const{amount} -> {target:#x}")
+            }
+            Instruction_UntypedCalculate::ConstRotateLeftTo { target, amount } => {
+                write!(f, " {target:#x} rol const{amount} -> {target:#x}")
+            }
+            Instruction_UntypedCalculate::ConstRotateRightTo { target, amount } => {
+                write!(f, " {target:#x} ror const{amount} -> {target:#x}")
+            }
+
+            Instruction_UntypedCalculate::Arithmetic {
+                op,
+                mode,
+                lhs,
+                rhs,
+                target,
+            } => f.write_fmt(format_args!(
+                " {lhs:#x} {op}.{mode} {rhs:#x} -> {target:#x}"
+            )),
+            Instruction_UntypedCalculate::ConstArithmeticTo {
+                op,
+                mode,
+                target,
+                data,
+            } => write!(
+                f,
+                " {target:#x} {op}.{mode} const{data}({:#x}) -> {target:#x}",
+                data.raw_bits()
+            ),
+            Instruction_UntypedCalculate::ArithmeticByConst {
+                op,
+                mode,
+                target,
+                data,
+            } => write!(
+                f,
+                " const{data}({:#x}) {op}.{mode} {target:#x} -> {target:#x}",
+                data.raw_bits()
+            ),
         }
     }
 }
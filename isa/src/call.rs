@@ -49,6 +49,35 @@ where
         args: Vec<TRegisterAddr>,
         ret_at: TRegisterAddr,
     },
+    /// Calls the managed method loaded by `LoadContent::MethodPointer`,
+    /// the first arg is used as `this` if the method is not static
+    IndirectCall {
+        f_pointer: TRegisterAddr,
+        args: Vec<TRegisterAddr>,
+        ret_at: TRegisterAddr,
+    },
+    /// Same as `InstanceCall`, but the current frame is popped before calling
+    /// and the result is returned directly from the current method
+    TailInstanceCall {
+        val: TRegisterAddr,
+        method: TMethodRef,
+        args: Vec<TRegisterAddr>,
+    },
+    /// Same as `StaticCall`, but the current frame is popped before calling
+    /// and the result is returned directly from the current method
+    TailStaticCall {
+        ty: TTypeRef,
+        method: TMethodRef,
+        args: Vec<TRegisterAddr>,
+    },
+    /// Same as `InterfaceCall`, but the current frame is popped before calling
+    /// and the result is returned directly from the current method
+    TailInterfaceCall {
+        interface: TTypeRef,
+        val: TRegisterAddr,
+        method: TMethodRef,
+        args: Vec<TRegisterAddr>,
+    },
 }
 
 impl<TTypeRef, TMethodRef, TRegisterAddr> Instruction_Call<TTypeRef, TMethodRef, TRegisterAddr>
@@ -133,6 +162,40 @@ where
                 args: args.into_iter().map(&mut f_TRegisterAddr).collect(),
                 ret_at: f_TRegisterAddr(ret_at),
             },
+            Instruction_Call::IndirectCall {
+                f_pointer,
+                args,
+                ret_at,
+            } => Instruction_Call::IndirectCall {
+                f_pointer: f_TRegisterAddr(f_pointer),
+                args: args.into_iter().map(&mut f_TRegisterAddr).collect(),
+                ret_at: f_TRegisterAddr(ret_at),
+            },
+            Instruction_Call::TailInstanceCall { val, method, args } => {
+                Instruction_Call::TailInstanceCall {
+                    val: f_TRegisterAddr(val),
+                    method: f_TMethodRef(method),
+                    args: args.into_iter().map(&mut f_TRegisterAddr).collect(),
+                }
+            }
+            Instruction_Call::TailStaticCall { ty, method, args } => {
+                Instruction_Call::TailStaticCall {
+                    ty: f_TTypeRef(ty),
+                    method: f_TMethodRef(method),
+                    args: args.into_iter().map(&mut f_TRegisterAddr).collect(),
+                }
+            }
+            Instruction_Call::TailInterfaceCall {
+                interface,
+                val,
+                method,
+                args,
+            } => Instruction_Call::TailInterfaceCall {
+                interface: f_TTypeRef(interface),
+                val: f_TRegisterAddr(val),
+                method: f_TMethodRef(method),
+                args: args.into_iter().map(&mut f_TRegisterAddr).collect(),
+            },
         }
     }
 }
@@ -332,6 +395,82 @@ impl<TTypeRef, TMethodRef> Instruction_Call<TTypeRef, TMethodRef, RegisterAddr>
                     ret_at,
                 }),
             },
+            Instruction_Call::IndirectCall {
+                f_pointer,
+                args,
+                ret_at,
+            } => match f_pointer.try_into_short().and_then(|f_pointer| {
+                args.iter()
+                    .copied()
+                    .map(RegisterAddr::try_into_short)
+                    .try_collect::<Vec<_>>()
+                    .and_then(|args| ret_at.try_into_short().map(|ret_at| (args, ret_at)))
+                    .map(|(args, ret_at)| (f_pointer, args, ret_at))
+            }) {
+                Some((f_pointer, args, ret_at)) => Ok(Instruction_Call::IndirectCall {
+                    f_pointer,
+                    args,
+                    ret_at,
+                }),
+                None => Err(Instruction_Call::IndirectCall {
+                    f_pointer,
+                    args,
+                    ret_at,
+                }),
+            },
+            Instruction_Call::TailInstanceCall { val, method, args } => {
+                if let Some(val) = val.try_into_short()
+                    && let Some(args) = args
+                        .iter()
+                        .copied()
+                        .map(RegisterAddr::try_into_short)
+                        .try_collect::<Vec<_>>()
+                {
+                    Ok(Instruction_Call::TailInstanceCall { val, method, args })
+                } else {
+                    Err(Instruction_Call::TailInstanceCall { val, method, args })
+                }
+            }
+            Instruction_Call::TailStaticCall { ty, method, args } => {
+                if let Some(args) = args
+                    .iter()
+                    .copied()
+                    .map(RegisterAddr::try_into_short)
+                    .try_collect::<Vec<_>>()
+                {
+                    Ok(Instruction_Call::TailStaticCall { ty, method, args })
+                } else {
+                    Err(Instruction_Call::TailStaticCall { ty, method, args })
+                }
+            }
+            Instruction_Call::TailInterfaceCall {
+                interface,
+                val,
+                method,
+                args,
+            } => {
+                if let Some(val) = val.try_into_short()
+                    && let Some(args) = args
+                        .iter()
+                        .copied()
+                        .map(RegisterAddr::try_into_short)
+                        .try_collect::<Vec<_>>()
+                {
+                    Ok(Instruction_Call::TailInterfaceCall {
+                        interface,
+                        val,
+                        method,
+                        args,
+                    })
+                } else {
+                    Err(Instruction_Call::TailInterfaceCall {
+                        interface,
+                        val,
+                        method,
+                        args,
+                    })
+                }
+            }
         }
     }
 }
@@ -390,6 +529,31 @@ where
                 " StaticNonPurusCall #{config:#x} {f_pointer:#x}({}) -> {ret_at:#x}",
                 display_args(args)
             )),
+            Instruction_Call::IndirectCall {
+                f_pointer,
+                args,
+                ret_at,
+            } => f.write_fmt(format_args!(
+                " IndirectCall {f_pointer:#x}({}) -> {ret_at:#x}",
+                display_args(args)
+            )),
+            Instruction_Call::TailInstanceCall { val, method, args } => f.write_fmt(format_args!(
+                " TailInstanceCall {val:#x} {method}({})",
+                display_args(args)
+            )),
+            Instruction_Call::TailStaticCall { ty, method, args } => f.write_fmt(format_args!(
+                " TailStaticCall {ty} {method}({})",
+                display_args(args)
+            )),
+            Instruction_Call::TailInterfaceCall {
+                interface,
+                val,
+                method,
+                args,
+            } => f.write_fmt(format_args!(
+                " TailInterfaceCall {val:#x} as {interface} {method}({})",
+                display_args(args)
+            )),
         }
     }
 }
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use binary_proc_macros::{ReadFromSection, WriteToSection};
+use global_proc_macros::{DeriveMap, Transpose, WithType};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::{IRegisterAddr, RegisterAddr, ShortRegisterAddr};
+
+#[repr(u8)]
+#[derive(Debug, Clone, WithType, ReadFromSection, WriteToSection, Transpose, DeriveMap)]
+#[transpose(TTypeRef)]
+#[with_type(derive_const = (Clone, PartialEq, Eq))]
+#[with_type(derive = (Copy, IntoPrimitive, TryFromPrimitive, Debug, ReadFromSection, WriteToSection))]
+pub enum Instruction_Cast<TTypeRef, TRegisterAddr>
+where
+    TRegisterAddr: IRegisterAddr,
+{
+    /// Writes a `System::Boolean` to `output`,
+    /// which is true if `val` is not null and can be cast to `ty`
+    IsInstanceOf {
+        ty: TTypeRef,
+        val: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+    /// Copies `val` to `output` if it is null or can be cast to `ty`,
+    /// otherwise throws `System::InvalidCastException`
+    CastClass {
+        ty: TTypeRef,
+        val: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+    /// Copies the struct `value` to a new heap object and writes it to `output`,
+    /// the reference is copied as is if `ty` is not a struct
+    Box {
+        ty: TTypeRef,
+        value: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+    /// Copies the struct value boxed in `obj` to `output`,
+    /// throws `System::InvalidCastException` if it is not a boxed `ty`
+    Unbox {
+        ty: TTypeRef,
+        obj: TRegisterAddr,
+        output: TRegisterAddr,
+    },
+}
+
+impl<TTypeRef> Instruction_Cast<TTypeRef, RegisterAddr> {
+    pub fn try_into_short(self) -> Result<Instruction_Cast<TTypeRef, ShortRegisterAddr>, Self> {
+        match self {
+            Instruction_Cast::IsInstanceOf { ty, val, output } => {
+                match (val.try_into_short(), output.try_into_short()) {
+                    (Some(val), Some(output)) => {
+                        Ok(Instruction_Cast::IsInstanceOf { ty, val, output })
+                    }
+                    _ => Err(Instruction_Cast::IsInstanceOf { ty, val, output }),
+                }
+            }
+            Instruction_Cast::CastClass { ty, val, output } => {
+                match (val.try_into_short(), output.try_into_short()) {
+                    (Some(val), Some(output)) => {
+                        Ok(Instruction_Cast::CastClass { ty, val, output })
+                    }
+                    _ => Err(Instruction_Cast::CastClass { ty, val, output }),
+                }
+            }
+            Instruction_Cast::Box { ty, value, output } => {
+                match (value.try_into_short(), output.try_into_short()) {
+                    (Some(value), Some(output)) => Ok(Instruction_Cast::Box { ty, value, output }),
+                    _ => Err(Instruction_Cast::Box { ty, value, output }),
+                }
+            }
+            Instruction_Cast::Unbox { ty, obj, output } => {
+                match (obj.try_into_short(), output.try_into_short()) {
+                    (Some(obj), Some(output)) => Ok(Instruction_Cast::Unbox { ty, obj, output }),
+                    _ => Err(Instruction_Cast::Unbox { ty, obj, output }),
+                }
+            }
+        }
+    }
+}
+
+impl<TTypeRef, TRegisterAddr: IRegisterAddr> Display for Instruction_Cast<TTypeRef, TRegisterAddr>
+where
+    TTypeRef: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction_Cast::IsInstanceOf { ty, val, output } => f.write_fmt(format_args!(
+                " IsInstanceOf {val:#x} as {ty} -> {output:#x}"
+            )),
+            Instruction_Cast::CastClass { ty, val, output } => {
+                f.write_fmt(format_args!(" CastClass {val:#x} as {ty} -> {output:#x}"))
+            }
+            Instruction_Cast::Box { ty, value, output } => {
+                f.write_fmt(format_args!(" Box {value:#x} as {ty} -> {output:#x}"))
+            }
+            Instruction_Cast::Unbox { ty, obj, output } => {
+                f.write_fmt(format_args!(" Unbox {obj:#x} as {ty} -> {output:#x}"))
+            }
+        }
+    }
+}
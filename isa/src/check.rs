@@ -5,7 +5,7 @@ use binary_proc_macros::{ReadFromSection, WriteToSection};
 use global_proc_macros::WithType;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use crate::{IRegisterAddr, RegisterAddr, ShortRegisterAddr};
+use crate::{CalculateConst, IRegisterAddr, RegisterAddr, ShortRegisterAddr};
 
 #[derive(Debug, Clone, ReadFromSection, WriteToSection)]
 pub struct Instruction_CommonCheck<TRegisterAddr: IRegisterAddr> {
@@ -44,6 +44,21 @@ impl<TRegisterAddr: IRegisterAddr> Display for Instruction_CommonCheck<TRegister
 #[with_type(derive = (Copy, IntoPrimitive, TryFromPrimitive, Debug, ReadFromSection, WriteToSection))]
 pub enum ToCheckContent<TRegisterAddr: IRegisterAddr> {
     IsAllZero(TRegisterAddr),
+
+    /// Compares two registers which are both of `ty`
+    Compare {
+        kind: CompareKind,
+        ty: CompareType,
+        lhs: TRegisterAddr,
+        rhs: TRegisterAddr,
+    },
+    /// Compares a register with a constant,
+    /// the register is treated as the type of the constant
+    CompareWithConst {
+        kind: CompareKind,
+        lhs: TRegisterAddr,
+        rhs: CompareConst,
+    },
 }
 
 impl ToCheckContent<RegisterAddr> {
@@ -53,6 +68,16 @@ impl ToCheckContent<RegisterAddr> {
                 Some(to_check) => Ok(ToCheckContent::IsAllZero(to_check)),
                 None => Err(self),
             },
+            ToCheckContent::Compare { kind, ty, lhs, rhs } => {
+                match (lhs.try_into_short(), rhs.try_into_short()) {
+                    (Some(lhs), Some(rhs)) => Ok(ToCheckContent::Compare { kind, ty, lhs, rhs }),
+                    _ => Err(self),
+                }
+            }
+            ToCheckContent::CompareWithConst { kind, lhs, rhs } => match lhs.try_into_short() {
+                Some(lhs) => Ok(ToCheckContent::CompareWithConst { kind, lhs, rhs }),
+                None => Err(self),
+            },
         }
     }
 }
@@ -63,6 +88,161 @@ impl<TRegisterAddr: IRegisterAddr> Display for ToCheckContent<TRegisterAddr> {
             ToCheckContent::IsAllZero(to_check) => {
                 f.write_fmt(format_args!("is_all_zero({to_check:#x})"))
             }
+            ToCheckContent::Compare { kind, ty, lhs, rhs } => {
+                f.write_fmt(format_args!("{kind}_{ty}({lhs:#x}, {rhs:#x})"))
+            }
+            ToCheckContent::CompareWithConst { kind, lhs, rhs } => {
+                f.write_fmt(format_args!("{kind}_{}({lhs:#x}, {rhs})", rhs.ty()))
+            }
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    ReadFromSection,
+    WriteToSection,
+)]
+pub enum CompareKind {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Display for CompareKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CompareKind::Eq => "eq",
+            CompareKind::Ne => "ne",
+            CompareKind::Lt => "lt",
+            CompareKind::Le => "le",
+            CompareKind::Gt => "gt",
+            CompareKind::Ge => "ge",
+        })
+    }
+}
+
+/// The type of the values to compare, which decides whether the comparison is signed
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    ReadFromSection,
+    WriteToSection,
+)]
+pub enum CompareType {
+    U8,
+    U16,
+    U32,
+    U64,
+
+    I8,
+    I16,
+    I32,
+    I64,
+
+    F32,
+    F64,
+
+    /// Compares the references, only [`CompareKind::Eq`] and [`CompareKind::Ne`] are allowed
+    Object,
+}
+
+impl Display for CompareType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CompareType::U8 => "u8",
+            CompareType::U16 => "u16",
+            CompareType::U32 => "u32",
+            CompareType::U64 => "u64",
+
+            CompareType::I8 => "i8",
+            CompareType::I16 => "i16",
+            CompareType::I32 => "i32",
+            CompareType::I64 => "i64",
+
+            CompareType::F32 => "f32",
+            CompareType::F64 => "f64",
+
+            CompareType::Object => "object",
+        })
+    }
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, WithType, ReadFromSection, WriteToSection)]
+#[with_type(derive_const = (Clone, PartialEq, Eq))]
+#[with_type(derive = (Copy, IntoPrimitive, TryFromPrimitive, Debug, ReadFromSection, WriteToSection))]
+pub enum CompareConst {
+    U8(u8),
+    U16(#[varint] u16),
+    U32(#[varint] u32),
+    U64(#[varint] u64),
+
+    I8(i8),
+    I16(#[varint] i16),
+    I32(#[varint] i32),
+    I64(#[varint] i64),
+
+    F32(f32),
+    F64(f64),
+}
+
+impl CompareConst {
+    pub const fn ty(&self) -> CompareType {
+        match self {
+            CompareConst::U8(_) => CompareType::U8,
+            CompareConst::U16(_) => CompareType::U16,
+            CompareConst::U32(_) => CompareType::U32,
+            CompareConst::U64(_) => CompareType::U64,
+
+            CompareConst::I8(_) => CompareType::I8,
+            CompareConst::I16(_) => CompareType::I16,
+            CompareConst::I32(_) => CompareType::I32,
+            CompareConst::I64(_) => CompareType::I64,
+
+            CompareConst::F32(_) => CompareType::F32,
+            CompareConst::F64(_) => CompareType::F64,
+        }
+    }
+}
+
+impl Display for CompareConst {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_const<T: CalculateConst>(
+            f: &mut std::fmt::Formatter<'_>,
+            x: T,
+        ) -> std::fmt::Result {
+            f.write_fmt(format_args!("const{x}({:#x})", x.raw_bits()))
+        }
+        match *self {
+            CompareConst::U8(x) => write_const(f, x),
+            CompareConst::U16(x) => write_const(f, x),
+            CompareConst::U32(x) => write_const(f, x),
+            CompareConst::U64(x) => write_const(f, x),
+
+            CompareConst::I8(x) => write_const(f, x),
+            CompareConst::I16(x) => write_const(f, x),
+            CompareConst::I32(x) => write_const(f, x),
+            CompareConst::I64(x) => write_const(f, x),
+
+            CompareConst::F32(x) => write_const(f, x),
+            CompareConst::F64(x) => write_const(f, x),
         }
     }
 }
@@ -0,0 +1,112 @@
+use std::fmt::Display;
+
+use binary_proc_macros::{ReadFromSection, WriteToSection};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::{IRegisterAddr, OverflowMode, RegisterAddr, ShortRegisterAddr};
+
+/// Converts the value in `source` from `from` to `to` and writes it to `target`
+///
+/// * [`OverflowMode::Wrapping`] truncates or extends integers like `as` in Rust,
+///   which also makes conversions between integers of the same size a reinterpretation
+/// * [`OverflowMode::Checked`] throws `System::OverflowException` if the value
+///   cannot be represented by `to`
+/// * [`OverflowMode::Saturating`] clamps the value to the range of `to`
+///
+/// Floats converted to integers are rounded toward zero,
+/// NaN becomes zero unless the conversion is checked.
+#[derive(Debug, Clone, Copy, ReadFromSection, WriteToSection)]
+pub struct Instruction_Convert<TRegisterAddr: IRegisterAddr> {
+    pub from: PrimitiveKind,
+    pub to: PrimitiveKind,
+    pub mode: OverflowMode,
+    pub source: TRegisterAddr,
+    pub target: TRegisterAddr,
+}
+
+impl Instruction_Convert<RegisterAddr> {
+    pub const fn try_into_short(self) -> Result<Instruction_Convert<ShortRegisterAddr>, Self> {
+        let Instruction_Convert {
+            from,
+            to,
+            mode,
+            source,
+            target,
+        } = self;
+        match (source.try_into_short(), target.try_into_short()) {
+            (Some(source), Some(target)) => Ok(Instruction_Convert {
+                from,
+                to,
+                mode,
+                source,
+                target,
+            }),
+            _ => Err(self),
+        }
+    }
+}
+
+impl<TRegisterAddr: IRegisterAddr> Display for Instruction_Convert<TRegisterAddr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            from,
+            to,
+            mode,
+            source,
+            target,
+        } = self;
+        f.write_fmt(format_args!(
+            " ({from} -> {to}).{mode} {source:#x} -> {target:#x}"
+        ))
+    }
+}
+
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    ReadFromSection,
+    WriteToSection,
+)]
+pub enum PrimitiveKind {
+    U8,
+    U16,
+    U32,
+    U64,
+    USize,
+
+    I8,
+    I16,
+    I32,
+    I64,
+    ISize,
+
+    F32,
+    F64,
+}
+
+impl Display for PrimitiveKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PrimitiveKind::U8 => "u8",
+            PrimitiveKind::U16 => "u16",
+            PrimitiveKind::U32 => "u32",
+            PrimitiveKind::U64 => "u64",
+            PrimitiveKind::USize => "usize",
+
+            PrimitiveKind::I8 => "i8",
+            PrimitiveKind::I16 => "i16",
+            PrimitiveKind::I32 => "i32",
+            PrimitiveKind::I64 => "i64",
+            PrimitiveKind::ISize => "isize",
+
+            PrimitiveKind::F32 => "f32",
+            PrimitiveKind::F64 => "f64",
+        })
+    }
+}
@@ -56,6 +56,38 @@ impl JumpCondition<RegisterAddr> {
     }
 }
 
+/// Jumps to `targets[value]`, or to `default` if `value` is out of bounds
+///
+/// `value` is read as a `System::UInt32`
+#[derive(Clone, Debug, ReadFromSection, WriteToSection)]
+pub struct Instruction_Switch<TRegisterAddr: IRegisterAddr> {
+    pub value: TRegisterAddr,
+    pub targets: Vec<JumpTarget>,
+    pub default: JumpTarget,
+}
+
+impl Instruction_Switch<RegisterAddr> {
+    pub fn try_into_short(self) -> Result<Instruction_Switch<ShortRegisterAddr>, Self> {
+        let Instruction_Switch {
+            value,
+            targets,
+            default,
+        } = self;
+        match value.try_into_short() {
+            Some(value) => Ok(Instruction_Switch {
+                value,
+                targets,
+                default,
+            }),
+            None => Err(Self {
+                value,
+                targets,
+                default,
+            }),
+        }
+    }
+}
+
 impl<TRegisterAddr: IRegisterAddr> Display for Instruction_Jump<TRegisterAddr> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(" {}", self.target))?;
@@ -72,3 +104,18 @@ impl<TRegisterAddr: IRegisterAddr> Display for Instruction_Jump<TRegisterAddr> {
         }
     }
 }
+
+impl<TRegisterAddr: IRegisterAddr> Display for Instruction_Switch<TRegisterAddr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            " {:#x} [{}] else {}",
+            self.value,
+            self.targets
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.default
+        ))
+    }
+}
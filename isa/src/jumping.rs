@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-use binary_core::traits::{ReadFromSection, WriteToSection};
+use binary_core::traits::{
+    ReadFromSection, ReadVarIntFromSection, WriteToSection, WriteVarIntToSection,
+};
 use bitfields::{FromBits, IntoBits};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -46,6 +48,27 @@ pub struct JumpTarget {
     val: u64,
 }
 
+impl JumpTarget {
+    pub fn absolute(index: u64) -> Self {
+        JumpTargetBuilder::new()
+            .with_ty(JumpTargetType::Absolute)
+            .with_val(index)
+            .build()
+    }
+    pub fn forward(offset: u64) -> Self {
+        JumpTargetBuilder::new()
+            .with_ty(JumpTargetType::Forward)
+            .with_val(offset)
+            .build()
+    }
+    pub fn backward(offset: u64) -> Self {
+        JumpTargetBuilder::new()
+            .with_ty(JumpTargetType::Backward)
+            .with_val(offset)
+            .build()
+    }
+}
+
 impl Display for JumpTarget {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{}({:#x})", self.ty(), self.val()))
@@ -56,7 +79,7 @@ impl ReadFromSection for JumpTarget {
     fn read_from_section(
         cursor: &mut std::io::Cursor<&binary_core::section::Section>,
     ) -> binary_core::BinaryResult<Self> {
-        u64::read_from_section(cursor).map(Self)
+        u64::read_varint_from_section(cursor).map(Self)
     }
 }
 
@@ -65,6 +88,6 @@ impl WriteToSection for JumpTarget {
         &self,
         cursor: &mut std::io::Cursor<&mut Vec<u8>>,
     ) -> binary_core::BinaryResult<()> {
-        self.0.write_to_section(cursor)
+        self.0.write_varint_to_section(cursor)
     }
 }
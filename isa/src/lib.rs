@@ -29,21 +29,29 @@ pub use jumping::*;
 mod register_addr;
 pub use register_addr::*;
 
+mod array;
 mod calculate;
 mod call;
+mod cast;
 mod check;
+mod convert;
 mod jump;
 mod load;
+mod move_register;
 mod new;
 mod read_write_pointer;
 mod set;
 mod stack_allocate;
 
+pub use array::*;
 pub use calculate::*;
 pub use call::*;
+pub use cast::*;
 pub use check::*;
+pub use convert::*;
 pub use jump::*;
 pub use load::*;
+pub use move_register::*;
 pub use new::*;
 pub use read_write_pointer::*;
 pub use set::*;
@@ -55,8 +63,8 @@ pub use stack_allocate::*;
 #[with_type(derive = (Copy, IntoPrimitive, TryFromPrimitive, Debug, ReadFromSection, WriteToSection))]
 pub enum Instruction<TString, TTypeRef, TMethodRef, TFieldRef> {
     Nop,
-    Load(Instruction_Load<TString, TTypeRef, TFieldRef, RegisterAddr>),
-    SLoad(Instruction_Load<TString, TTypeRef, TFieldRef, ShortRegisterAddr>),
+    Load(Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, RegisterAddr>),
+    SLoad(Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, ShortRegisterAddr>),
 
     ReadPointerTo(ReadPointerTo),
     SReadPointerTo(SReadPointerTo),
@@ -91,6 +99,24 @@ pub enum Instruction<TString, TTypeRef, TMethodRef, TFieldRef> {
 
     StackAllocate(Instruction_StackAllocate<RegisterAddr>),
     SStackAllocate(Instruction_StackAllocate<ShortRegisterAddr>),
+
+    Convert(Instruction_Convert<RegisterAddr>),
+    SConvert(Instruction_Convert<ShortRegisterAddr>),
+
+    Cast(Instruction_Cast<TTypeRef, RegisterAddr>),
+    SCast(Instruction_Cast<TTypeRef, ShortRegisterAddr>),
+
+    Switch(Instruction_Switch<RegisterAddr>),
+    SSwitch(Instruction_Switch<ShortRegisterAddr>),
+
+    Move(Instruction_Move<RegisterAddr>),
+    SMove(Instruction_Move<ShortRegisterAddr>),
+
+    ZeroInit { target: RegisterAddr },
+    SZeroInit { target: ShortRegisterAddr },
+
+    Array(Instruction_Array<RegisterAddr>),
+    SArray(Instruction_Array<ShortRegisterAddr>),
 }
 
 impl<TString, TTypeRef, TMethodRef, TFieldRef>
@@ -153,6 +179,24 @@ impl<TString, TTypeRef, TMethodRef, TFieldRef>
 
             StackAllocate(ins) => Some(StackAllocate(ins)),
             SStackAllocate(ins) => Some(SStackAllocate(ins)),
+
+            Convert(ins) => Some(Convert(ins)),
+            SConvert(ins) => Some(SConvert(ins)),
+
+            Cast(ins) => ins.transpose().map(Cast),
+            SCast(ins) => ins.transpose().map(SCast),
+
+            Switch(ins) => Some(Switch(ins)),
+            SSwitch(ins) => Some(SSwitch(ins)),
+
+            Move(ins) => Some(Move(ins)),
+            SMove(ins) => Some(SMove(ins)),
+
+            ZeroInit { target } => Some(ZeroInit { target }),
+            SZeroInit { target } => Some(SZeroInit { target }),
+
+            Array(ins) => Some(Array(ins)),
+            SArray(ins) => Some(SArray(ins)),
         }
     }
 }
@@ -211,6 +255,24 @@ impl<TString, E1, TTypeRef, E2, TMethodRef, E3, TFieldRef, E4>
 
             StackAllocate(ins) => Ok(StackAllocate(ins)),
             SStackAllocate(ins) => Ok(SStackAllocate(ins)),
+
+            Convert(ins) => Ok(Convert(ins)),
+            SConvert(ins) => Ok(SConvert(ins)),
+
+            Cast(ins) => ins.transpose().map(Cast),
+            SCast(ins) => ins.transpose().map(SCast),
+
+            Switch(ins) => Ok(Switch(ins)),
+            SSwitch(ins) => Ok(SSwitch(ins)),
+
+            Move(ins) => Ok(Move(ins)),
+            SMove(ins) => Ok(SMove(ins)),
+
+            ZeroInit { target } => Ok(ZeroInit { target }),
+            SZeroInit { target } => Ok(SZeroInit { target }),
+
+            Array(ins) => Ok(Array(ins)),
+            SArray(ins) => Ok(SArray(ins)),
         }
     }
 }
@@ -248,8 +310,8 @@ impl<TString, TTypeRef, TMethodRef, TFieldRef>
         }
         match self {
             Nop => Nop,
-            Load(ins) => Load(ins.map(f_TString, f_TTypeRef, f_TFieldRef, noop)),
-            SLoad(ins) => SLoad(ins.map(f_TString, f_TTypeRef, f_TFieldRef, noop)),
+            Load(ins) => Load(ins.map(f_TString, f_TTypeRef, f_TMethodRef, f_TFieldRef, noop)),
+            SLoad(ins) => SLoad(ins.map(f_TString, f_TTypeRef, f_TMethodRef, f_TFieldRef, noop)),
 
             ReadPointerTo(ins) => ReadPointerTo(ins),
             SReadPointerTo(ins) => SReadPointerTo(ins),
@@ -284,6 +346,24 @@ impl<TString, TTypeRef, TMethodRef, TFieldRef>
 
             StackAllocate(ins) => StackAllocate(ins),
             SStackAllocate(ins) => SStackAllocate(ins),
+
+            Convert(ins) => Convert(ins),
+            SConvert(ins) => SConvert(ins),
+
+            Cast(ins) => Cast(ins.map(f_TTypeRef, noop)),
+            SCast(ins) => SCast(ins.map(f_TTypeRef, noop)),
+
+            Switch(ins) => Switch(ins),
+            SSwitch(ins) => SSwitch(ins),
+
+            Move(ins) => Move(ins),
+            SMove(ins) => SMove(ins),
+
+            ZeroInit { target } => ZeroInit { target },
+            SZeroInit { target } => SZeroInit { target },
+
+            Array(ins) => Array(ins),
+            SArray(ins) => SArray(ins),
         }
     }
 }
@@ -364,6 +444,28 @@ where
             Instruction::SStackAllocate(ins) => {
                 f.write_fmt(format_args!("{NAME}::SStackAllocate{ins}"))
             }
+
+            Instruction::Convert(ins) => f.write_fmt(format_args!("{NAME}::Convert{ins}")),
+            Instruction::SConvert(ins) => f.write_fmt(format_args!("{NAME}::SConvert{ins}")),
+
+            Instruction::Cast(ins) => f.write_fmt(format_args!("{NAME}::Cast{ins}")),
+            Instruction::SCast(ins) => f.write_fmt(format_args!("{NAME}::SCast{ins}")),
+
+            Instruction::Switch(ins) => f.write_fmt(format_args!("{NAME}::Switch{ins}")),
+            Instruction::SSwitch(ins) => f.write_fmt(format_args!("{NAME}::SSwitch{ins}")),
+
+            Instruction::Move(ins) => f.write_fmt(format_args!("{NAME}::Move{ins}")),
+            Instruction::SMove(ins) => f.write_fmt(format_args!("{NAME}::SMove{ins}")),
+
+            Instruction::ZeroInit { target } => {
+                f.write_fmt(format_args!("{NAME}::ZeroInit {target:#x}"))
+            }
+            Instruction::SZeroInit { target } => {
+                f.write_fmt(format_args!("{NAME}::SZeroInit {target:#x}"))
+            }
+
+            Instruction::Array(ins) => f.write_fmt(format_args!("{NAME}::Array{ins}")),
+            Instruction::SArray(ins) => f.write_fmt(format_args!("{NAME}::SArray{ins}")),
         }
     }
 }
@@ -449,6 +551,42 @@ impl<TString, TTypeRef, TMethodRef, TFieldRef>
                 Err(ins) => StackAllocate(ins),
             },
             SStackAllocate(ins) => SStackAllocate(ins),
+
+            Convert(ins) => match ins.try_into_short() {
+                Ok(ins) => SConvert(ins),
+                Err(ins) => Convert(ins),
+            },
+            SConvert(ins) => SConvert(ins),
+
+            Cast(ins) => match ins.try_into_short() {
+                Ok(ins) => SCast(ins),
+                Err(ins) => Cast(ins),
+            },
+            SCast(ins) => SCast(ins),
+
+            Switch(ins) => match ins.try_into_short() {
+                Ok(ins) => SSwitch(ins),
+                Err(ins) => Switch(ins),
+            },
+            SSwitch(ins) => SSwitch(ins),
+
+            Move(ins) => match ins.try_into_short() {
+                Ok(ins) => SMove(ins),
+                Err(ins) => Move(ins),
+            },
+            SMove(ins) => SMove(ins),
+
+            ZeroInit { target } => match target.try_into_short() {
+                Some(target) => SZeroInit { target },
+                None => ZeroInit { target },
+            },
+            SZeroInit { target } => SZeroInit { target },
+
+            Array(ins) => match ins.try_into_short() {
+                Ok(ins) => SArray(ins),
+                Err(ins) => Array(ins),
+            },
+            SArray(ins) => SArray(ins),
         }
     }
 }
@@ -9,17 +9,24 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use crate::{IRegisterAddr, RegisterAddr, ShortRegisterAddr};
 
 #[derive(Debug, Clone, ReadFromSection, WriteToSection)]
-pub struct Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr: IRegisterAddr> {
+pub struct Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr: IRegisterAddr>
+{
     pub addr: TRegisterAddr,
-    pub content: LoadContent<TString, TTypeRef, TFieldRef, TRegisterAddr>,
+    pub content: LoadContent<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>,
 }
 
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr: IRegisterAddr>
-    Instruction_Load<Option<TString>, Option<TTypeRef>, Option<TFieldRef>, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr: IRegisterAddr>
+    Instruction_Load<
+        Option<TString>,
+        Option<TTypeRef>,
+        Option<TMethodRef>,
+        Option<TFieldRef>,
+        TRegisterAddr,
+    >
 {
     pub fn transpose(
         self,
-    ) -> Option<Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>> {
+    ) -> Option<Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>> {
         let Self { addr, content } = self;
         match content.transpose() {
             Some(content) => Some(Instruction_Load { addr, content }),
@@ -28,19 +35,20 @@ impl<TString, TTypeRef, TFieldRef, TRegisterAddr: IRegisterAddr>
     }
 }
 
-impl<TString, E1, TTypeRef, E2, TFieldRef, E3, TRegisterAddr: IRegisterAddr>
+impl<TString, E1, TTypeRef, E2, TMethodRef, E3, TFieldRef, E4, TRegisterAddr: IRegisterAddr>
     Instruction_Load<
         Result<TString, E1>,
         Result<TTypeRef, E2>,
-        Result<TFieldRef, E3>,
+        Result<TMethodRef, E3>,
+        Result<TFieldRef, E4>,
         TRegisterAddr,
     >
 {
     pub fn transpose<UniE>(
         self,
-    ) -> Result<Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>, UniE>
+    ) -> Result<Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>, UniE>
     where
-        UniE: From<E1> + From<E2> + From<E3>,
+        UniE: From<E1> + From<E2> + From<E3> + From<E4>,
     {
         let Self { addr, content } = self;
         content
@@ -49,50 +57,60 @@ impl<TString, E1, TTypeRef, E2, TFieldRef, E3, TRegisterAddr: IRegisterAddr>
     }
 }
 
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr: IRegisterAddr>
-    Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr: IRegisterAddr>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 {
     #[allow(nonstandard_style)]
     pub fn map<
         __TString,
         __TTypeRef,
+        __TMethodRef,
         __TFieldRef,
         __TRegisterAddr,
         __F_TString,
         __F_TTypeRef,
+        __F_TMethodRef,
         __F_TFieldRef,
         __F_TRegisterAddr,
     >(
         self,
         f_TString: __F_TString,
         f_TTypeRef: __F_TTypeRef,
+        f_TMethodRef: __F_TMethodRef,
         f_TFieldRef: __F_TFieldRef,
         mut f_TRegisterAddr: __F_TRegisterAddr,
-    ) -> Instruction_Load<__TString, __TTypeRef, __TFieldRef, __TRegisterAddr>
+    ) -> Instruction_Load<__TString, __TTypeRef, __TMethodRef, __TFieldRef, __TRegisterAddr>
     where
         __TRegisterAddr: IRegisterAddr,
         __F_TString: ::core::ops::FnMut(TString) -> __TString,
         __F_TTypeRef: ::core::ops::FnMut(TTypeRef) -> __TTypeRef,
+        __F_TMethodRef: ::core::ops::FnMut(TMethodRef) -> __TMethodRef,
         __F_TFieldRef: ::core::ops::FnMut(TFieldRef) -> __TFieldRef,
         __F_TRegisterAddr: ::core::ops::FnMut(TRegisterAddr) -> __TRegisterAddr,
     {
         let Self { addr, content } = self;
         Instruction_Load {
             addr: f_TRegisterAddr(addr),
-            content: content.map(f_TString, f_TTypeRef, f_TFieldRef, f_TRegisterAddr),
+            content: content.map(
+                f_TString,
+                f_TTypeRef,
+                f_TMethodRef,
+                f_TFieldRef,
+                f_TRegisterAddr,
+            ),
         }
     }
 }
 
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr>
-    Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TRegisterAddr: IRegisterAddr,
 {
     pub fn map_string<__TString, __F_TString>(
         self,
         f: __F_TString,
-    ) -> Instruction_Load<__TString, TTypeRef, TFieldRef, TRegisterAddr>
+    ) -> Instruction_Load<__TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
     where
         __F_TString: ::core::ops::FnMut(TString) -> __TString,
         TRegisterAddr: IRegisterAddr,
@@ -102,18 +120,19 @@ where
             core::convert::identity,
             core::convert::identity,
             core::convert::identity,
+            core::convert::identity,
         )
     }
 }
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr>
-    Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TRegisterAddr: IRegisterAddr,
 {
     pub fn map_type_ref<__TTypeRef, __F_TTypeRef>(
         self,
         f: __F_TTypeRef,
-    ) -> Instruction_Load<TString, __TTypeRef, TFieldRef, TRegisterAddr>
+    ) -> Instruction_Load<TString, __TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
     where
         __F_TTypeRef: ::core::ops::FnMut(TTypeRef) -> __TTypeRef,
         TRegisterAddr: IRegisterAddr,
@@ -123,23 +142,47 @@ where
             f,
             core::convert::identity,
             core::convert::identity,
+            core::convert::identity,
+        )
+    }
+}
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
+where
+    TRegisterAddr: IRegisterAddr,
+{
+    pub fn map_method_ref<__TMethodRef, __F_TMethodRef>(
+        self,
+        f: __F_TMethodRef,
+    ) -> Instruction_Load<TString, TTypeRef, __TMethodRef, TFieldRef, TRegisterAddr>
+    where
+        __F_TMethodRef: ::core::ops::FnMut(TMethodRef) -> __TMethodRef,
+        TRegisterAddr: IRegisterAddr,
+    {
+        self.map(
+            core::convert::identity,
+            core::convert::identity,
+            f,
+            core::convert::identity,
+            core::convert::identity,
         )
     }
 }
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr>
-    Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TRegisterAddr: IRegisterAddr,
 {
     pub fn map_field_ref<__TFieldRef, __F_TFieldRef>(
         self,
         f: __F_TFieldRef,
-    ) -> Instruction_Load<TString, TTypeRef, __TFieldRef, TRegisterAddr>
+    ) -> Instruction_Load<TString, TTypeRef, TMethodRef, __TFieldRef, TRegisterAddr>
     where
         __F_TFieldRef: ::core::ops::FnMut(TFieldRef) -> __TFieldRef,
         TRegisterAddr: IRegisterAddr,
     {
         self.map(
+            core::convert::identity,
             core::convert::identity,
             core::convert::identity,
             f,
@@ -147,15 +190,15 @@ where
         )
     }
 }
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr>
-    Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TRegisterAddr: IRegisterAddr,
 {
     pub fn map_register_addr<__TRegisterAddr, __F_TRegisterAddr>(
         self,
         f: __F_TRegisterAddr,
-    ) -> Instruction_Load<TString, TTypeRef, TFieldRef, __TRegisterAddr>
+    ) -> Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, __TRegisterAddr>
     where
         __F_TRegisterAddr: ::core::ops::FnMut(TRegisterAddr) -> __TRegisterAddr,
         __TRegisterAddr: IRegisterAddr,
@@ -164,16 +207,18 @@ where
             core::convert::identity,
             core::convert::identity,
             core::convert::identity,
+            core::convert::identity,
             f,
         )
     }
 }
 
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr: IRegisterAddr> Display
-    for Instruction_Load<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr: IRegisterAddr> Display
+    for Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TString: Display,
     TTypeRef: Display,
+    TMethodRef: Display,
     TFieldRef: Display,
     TRegisterAddr: Display,
 {
@@ -182,10 +227,13 @@ where
     }
 }
 
-impl<TString, TTypeRef, TFieldRef> Instruction_Load<TString, TTypeRef, TFieldRef, RegisterAddr> {
+impl<TString, TTypeRef, TMethodRef, TFieldRef>
+    Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, RegisterAddr>
+{
     pub fn try_into_short(
         self,
-    ) -> Result<Instruction_Load<TString, TTypeRef, TFieldRef, ShortRegisterAddr>, Self> {
+    ) -> Result<Instruction_Load<TString, TTypeRef, TMethodRef, TFieldRef, ShortRegisterAddr>, Self>
+    {
         let Instruction_Load { addr, content } = self;
         let Some(addr) = addr.try_into_short() else {
             return Err(Instruction_Load { addr, content });
@@ -317,16 +365,43 @@ impl<TString, TTypeRef, TFieldRef> Instruction_Load<TString, TTypeRef, TFieldRef
                 addr,
                 content: LoadContent::CaughtException,
             }),
+
+            LoadContent::F32(x) => Ok(Instruction_Load {
+                addr,
+                content: LoadContent::F32(x),
+            }),
+            LoadContent::F64(x) => Ok(Instruction_Load {
+                addr,
+                content: LoadContent::F64(x),
+            }),
+
+            LoadContent::MethodPointer { ty, method } => Ok(Instruction_Load {
+                addr,
+                content: LoadContent::MethodPointer { ty, method },
+            }),
+
+            LoadContent::Null => Ok(Instruction_Load {
+                addr,
+                content: LoadContent::Null,
+            }),
+            LoadContent::Default(ty) => Ok(Instruction_Load {
+                addr,
+                content: LoadContent::Default(ty),
+            }),
+            LoadContent::Char(x) => Ok(Instruction_Load {
+                addr,
+                content: LoadContent::Char(x),
+            }),
         }
     }
 }
 
 #[repr(u8)]
 #[derive(Debug, Clone, WithType, ReadFromSection, WriteToSection, Transpose, DeriveMap)]
-#[transpose(TString, TTypeRef, TFieldRef)]
+#[transpose(TString, TTypeRef, TMethodRef, TFieldRef)]
 #[with_type(derive_const = (Clone, PartialEq, Eq))]
 #[with_type(derive = (Copy, IntoPrimitive, TryFromPrimitive, Debug, ReadFromSection, WriteToSection))]
-pub enum LoadContent<TString, TTypeRef, TFieldRef, TRegisterAddr>
+pub enum LoadContent<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TRegisterAddr: IRegisterAddr,
 {
@@ -335,14 +410,14 @@ where
     False,
 
     U8(u8),
-    U16(u16),
-    U32(u32),
-    U64(u64),
+    U16(#[varint] u16),
+    U32(#[varint] u32),
+    U64(#[varint] u64),
 
     I8(i8),
-    I16(i16),
-    I32(i32),
-    I64(i64),
+    I16(#[varint] i16),
+    I32(#[varint] i32),
+    I64(#[varint] i64),
 
     AddressOfThis,
     This,
@@ -353,10 +428,10 @@ where
 
     NonPurusCallConfiguration(NonPurusCallConfiguration),
 
-    Arg(u64),
-    ArgRef(u64),
+    Arg(#[varint] u64),
+    ArgRef(#[varint] u64),
     /// It will read the value if the arg is passed by ref
-    ArgValue(u64),
+    ArgValue(#[varint] u64),
 
     AddressOfStatic {
         ty: TTypeRef,
@@ -376,13 +451,31 @@ where
     },
 
     CaughtException,
+
+    F32(f32),
+    F64(f64),
+
+    /// A pointer to `method` of `ty`, which can be called by `IndirectCall`
+    /// or captured by `System::Delegate`
+    MethodPointer {
+        ty: TTypeRef,
+        method: TMethodRef,
+    },
+
+    /// A null reference of any reference type
+    Null,
+    /// The zero-initialized value of `ty`
+    Default(TTypeRef),
+    /// A `System::Char`
+    Char(#[varint] u16),
 }
 
-impl<TString, TTypeRef, TFieldRef, TRegisterAddr: IRegisterAddr> Display
-    for LoadContent<TString, TTypeRef, TFieldRef, TRegisterAddr>
+impl<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr: IRegisterAddr> Display
+    for LoadContent<TString, TTypeRef, TMethodRef, TFieldRef, TRegisterAddr>
 where
     TString: Display,
     TTypeRef: Display,
+    TMethodRef: Display,
     TFieldRef: Display,
     TRegisterAddr: Display,
 {
@@ -428,6 +521,20 @@ where
             }
 
             LoadContent::CaughtException => f.write_fmt(format_args!("caught exception")),
+
+            LoadContent::F32(x) => f.write_fmt(format_args!("{x}f32({:#x})", x.to_bits())),
+            LoadContent::F64(x) => f.write_fmt(format_args!("{x}f64({:#x})", x.to_bits())),
+
+            LoadContent::MethodPointer { ty, method } => {
+                f.write_fmt(format_args!("&method({method} at {ty})"))
+            }
+
+            LoadContent::Null => f.write_str("Null"),
+            LoadContent::Default(ty) => f.write_fmt(format_args!("default({ty})")),
+            LoadContent::Char(x) => match char::from_u32(*x as u32) {
+                Some(c) => f.write_fmt(format_args!("{c:?}char({x:#x})")),
+                None => f.write_fmt(format_args!("char({x:#x})")),
+            },
         }
     }
 }
@@ -0,0 +1,27 @@
+use std::fmt::Display;
+
+use binary_proc_macros::{ReadFromSection, WriteToSection};
+
+use crate::{IRegisterAddr, RegisterAddr, ShortRegisterAddr};
+
+/// Copies the whole register `from` into `to`
+#[derive(Debug, Clone, Copy, ReadFromSection, WriteToSection)]
+pub struct Instruction_Move<TRegisterAddr: IRegisterAddr> {
+    pub from: TRegisterAddr,
+    pub to: TRegisterAddr,
+}
+
+impl Instruction_Move<RegisterAddr> {
+    pub const fn try_into_short(self) -> Result<Instruction_Move<ShortRegisterAddr>, Self> {
+        match (self.from.try_into_short(), self.to.try_into_short()) {
+            (Some(from), Some(to)) => Ok(Instruction_Move { from, to }),
+            _ => Err(self),
+        }
+    }
+}
+
+impl<TRegisterAddr: IRegisterAddr> Display for Instruction_Move<TRegisterAddr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(" {:#x} -> {:#x}", self.from, self.to))
+    }
+}
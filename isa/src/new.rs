@@ -24,6 +24,7 @@ where
     },
     NewArray {
         element_type: TTypeRef,
+        #[varint]
         len: u64,
         output: TRegisterAddr,
     },
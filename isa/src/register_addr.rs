@@ -39,7 +39,7 @@ pub const trait IRegisterAddr:
 #[repr(transparent)]
 #[derive(Debug, Copy, ReadFromSection, WriteToSection)]
 #[derive_const(Clone)]
-pub struct RegisterAddr(u64);
+pub struct RegisterAddr(#[varint] u64);
 
 impl RegisterAddr {
     pub const fn try_into_short(self) -> Option<ShortRegisterAddr> {
@@ -51,7 +51,7 @@ impl RegisterAddr {
 #[repr(transparent)]
 #[derive(Debug, Copy, ReadFromSection, WriteToSection)]
 #[derive_const(Clone)]
-pub struct ShortRegisterAddr(u16);
+pub struct ShortRegisterAddr(#[varint] u16);
 
 impl const IRegisterAddr for RegisterAddr {
     type TInner = u64;
@@ -24,12 +24,16 @@ where
     },
     Static {
         out: TRegisterAddr,
+        #[varint]
         size: u64,
+        #[varint]
         align: u64,
     },
     StaticZeroed {
         out: TRegisterAddr,
+        #[varint]
         size: u64,
+        #[varint]
         align: u64,
     },
 }
@@ -80,6 +80,8 @@ pub enum NonPurusCallType {
     I64,
     String,
     Object,
+    F32,
+    F64,
     Structure(Vec<NonPurusCallType>) = Self::STRUCTURE_DISCRIMINANT,
 }
 
@@ -104,6 +106,8 @@ impl NonPurusCallType {
             x if x == Self::I64.discriminant() => Some(Self::I64),
             x if x == Self::String.discriminant() => Some(Self::String),
             x if x == Self::Object.discriminant() => Some(Self::Object),
+            x if x == Self::F32.discriminant() => Some(Self::F32),
+            x if x == Self::F64.discriminant() => Some(Self::F64),
 
             Self::STRUCTURE_DISCRIMINANT => field_producer().map(Self::Structure),
 
@@ -138,6 +142,8 @@ impl ReadFromSection for NonPurusCallType {
             x if x == Self::I64.discriminant() => Ok(Self::I64),
             x if x == Self::String.discriminant() => Ok(Self::String),
             x if x == Self::Object.discriminant() => Ok(Self::Object),
+            x if x == Self::F32.discriminant() => Ok(Self::F32),
+            x if x == Self::F64.discriminant() => Ok(Self::F64),
 
             Self::STRUCTURE_DISCRIMINANT => {
                 let len: u64 = kind & 0x00ffffff;
@@ -243,6 +249,8 @@ impl NonPurusCallType {
             NonPurusCallType::I64 => Layout::new::<i64>(),
             NonPurusCallType::String => Layout::new::<NonNull<u8>>(),
             NonPurusCallType::Object => Layout::new::<NonNull<u8>>(),
+            NonPurusCallType::F32 => Layout::new::<f32>(),
+            NonPurusCallType::F64 => Layout::new::<f64>(),
             NonPurusCallType::Structure(types) => {
                 let mut result = unsafe { Layout::from_size_align_unchecked(0, 1) };
                 for ty in types {
@@ -2,12 +2,15 @@ pub mod AllocException;
 pub mod Array_1;
 pub mod Boolean;
 pub mod Char;
+pub mod Delegate;
+pub mod DivideByZeroException;
 pub mod DlErrorException;
 pub mod DynamicLibrary;
 pub mod ErrnoException;
 pub mod Exception;
 pub mod IDispose;
 pub mod IndexOutOfRangeException;
+pub mod InvalidCastException;
 pub mod InvalidEnumException;
 pub mod LargeString;
 pub mod NonPurusCallConfiguration;
@@ -15,6 +18,7 @@ pub mod NonPurusCallType;
 pub mod NullReferenceException;
 pub mod Nullable_1;
 pub mod Object;
+pub mod OverflowException;
 pub mod Pointer;
 pub mod Reference_1;
 pub mod RuntimeBasic;
@@ -27,6 +31,10 @@ pub mod Void;
 pub mod Win32Exception;
 
 pub mod Reflection;
+pub mod Threading;
 
 mod Integers;
 pub use Integers::*;
+
+mod Floats;
+pub use Floats::*;
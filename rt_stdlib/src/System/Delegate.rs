@@ -0,0 +1,19 @@
+use crate::{CoreTypeId, CoreTypeRef};
+
+proc_macros::define_core_class! {
+    #[Public {}] assembly
+    System_Delegate Some((CoreTypeId::System_Object.into(), vec![])) =>
+    #fields of super::Object::FieldId:
+    #[Private {}] Target "_target" => CoreTypeId::System_Object.into();
+    #[Private {}] MethodPointer "_methodPointer" => CoreTypeId::System_Pointer.into();
+
+    #methods of super::Object::MethodId:
+    [
+        #[Public {}] Constructor_Object_Pointer ".ctor" (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Pointer)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Void);
+        #[Public {AllowExtraArgs UseReturnBuffer}]
+        Invoke 1 () -> CoreTypeRef::MethodGeneric(0);
+    ] []
+}
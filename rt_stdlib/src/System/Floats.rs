@@ -0,0 +1,2 @@
+pub mod Double;
+pub mod Single;
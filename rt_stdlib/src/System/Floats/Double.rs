@@ -0,0 +1,14 @@
+use crate::{CoreTypeId, CoreTypeRef};
+
+proc_macros::define_core_struct! {
+    #[Public {}] assembly
+    System_Double "System::Double" =>
+    [None]
+    #fields:
+    #methods:
+    [] [
+        #[Public {Static}] ToString (
+            #[{ByRef}] CoreTypeRef::Core(CoreTypeId::System_Double)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_String);
+    ]
+}
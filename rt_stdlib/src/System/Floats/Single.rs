@@ -0,0 +1,14 @@
+use crate::{CoreTypeId, CoreTypeRef};
+
+proc_macros::define_core_struct! {
+    #[Public {}] assembly
+    System_Single "System::Single" =>
+    [None]
+    #fields:
+    #methods:
+    [] [
+        #[Public {Static}] ToString (
+            #[{ByRef}] CoreTypeRef::Core(CoreTypeId::System_Single)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_String);
+    ]
+}
@@ -0,0 +1,12 @@
+use crate::{CoreTypeId, CoreTypeRef};
+
+proc_macros::define_core_class! {
+    #[Public {}] assembly
+    System_InvalidCastException Some((CoreTypeId::System_Exception.into(), vec![])) =>
+    #fields:
+
+    #methods of super::Exception::MethodId:
+    [
+        #[Public {}] Constructor ".ctor" () -> CoreTypeRef::Core(CoreTypeId::System_Void);
+    ] []
+}
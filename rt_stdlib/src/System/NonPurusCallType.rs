@@ -48,5 +48,8 @@ proc_macros::define_core_class! {
                 ],
             )
         ) -> CoreTypeRef::Core(CoreTypeId::System_NonPurusCallType);
+
+        #[Public {Static}] CreateF32 () -> CoreTypeRef::Core(CoreTypeId::System_NonPurusCallType);
+        #[Public {Static}] CreateF64 () -> CoreTypeRef::Core(CoreTypeId::System_NonPurusCallType);
     ]
 }
@@ -0,0 +1,2 @@
+pub mod Monitor;
+pub mod SynchronizationLockException;
@@ -0,0 +1,29 @@
+use crate::{CoreTypeId, CoreTypeRef};
+
+proc_macros::define_core_class! {
+    #[Public {}] assembly
+    System_Threading_Monitor Some((CoreTypeId::System_Object.into(), vec![])) =>
+    #fields:
+
+    #methods of super::super::Object::MethodId:
+    [] [
+        #[Public {Static}] Enter (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Void);
+        #[Public {Static}] TryEnter (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Boolean);
+        #[Public {Static}] Exit (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Void);
+        #[Public {Static}] Wait (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Void);
+        #[Public {Static}] Pulse (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Void);
+        #[Public {Static}] PulseAll (
+            #[{}] CoreTypeRef::Core(CoreTypeId::System_Object)
+        ) -> CoreTypeRef::Core(CoreTypeId::System_Void);
+    ]
+}
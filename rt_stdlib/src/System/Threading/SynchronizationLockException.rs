@@ -0,0 +1,12 @@
+use crate::{CoreTypeId, CoreTypeRef};
+
+proc_macros::define_core_class! {
+    #[Public {}] assembly
+    System_Threading_SynchronizationLockException Some((CoreTypeId::System_Exception.into(), vec![])) =>
+    #fields:
+
+    #methods of super::super::Exception::MethodId:
+    [
+        #[Public {}] Constructor ".ctor" () -> CoreTypeRef::Core(CoreTypeId::System_Void);
+    ] []
+}
@@ -104,6 +104,25 @@ pub enum CoreTypeId {
     System_Reflection_MethodInfo,
     System_Reflection_ParameterInfo,
     /* #endregion */
+
+    /* #region Floats */
+    System_Single,
+    System_Double,
+    /* #endregion */
+
+    /* #region Arithmetic Exception */
+    System_DivideByZeroException,
+    System_OverflowException,
+    /* #endregion */
+
+    System_InvalidCastException,
+
+    /* #region System::Threading */
+    System_Threading_Monitor,
+    System_Threading_SynchronizationLockException,
+    /* #endregion */
+
+    System_Delegate,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -273,6 +292,21 @@ impl CoreTypeId {
             System_Reflection_FieldInfo => "System::Reflection::FieldInfo",
             System_Reflection_MethodInfo => "System::Reflection::MethodInfo",
             System_Reflection_ParameterInfo => "System::Reflection::ParameterInfo",
+
+            Self::System_Single => "System::Single",
+            Self::System_Double => "System::Double",
+
+            Self::System_DivideByZeroException => "System::DivideByZeroException",
+            Self::System_OverflowException => "System::OverflowException",
+
+            Self::System_InvalidCastException => "System::InvalidCastException",
+
+            System_Threading_Monitor => "System::Threading::Monitor",
+            System_Threading_SynchronizationLockException => {
+                "System::Threading::SynchronizationLockException"
+            }
+
+            Self::System_Delegate => "System::Delegate",
         }
     }
 }
@@ -351,6 +385,19 @@ impl CoreTypeId {
             System_Reflection_FieldInfo in System::Reflection::FieldInfo::load,
             System_Reflection_MethodInfo in System::Reflection::MethodInfo::load,
             System_Reflection_ParameterInfo in System::Reflection::ParameterInfo::load,
+
+            System_Single in of!(Single),
+            System_Double in of!(Double),
+
+            System_DivideByZeroException in of!(DivideByZeroException),
+            System_OverflowException in of!(OverflowException),
+
+            System_InvalidCastException in of!(InvalidCastException),
+
+            System_Threading_Monitor in System::Threading::Monitor::load,
+            System_Threading_SynchronizationLockException in System::Threading::SynchronizationLockException::load,
+
+            System_Delegate in of!(Delegate),
         )
     }
 }
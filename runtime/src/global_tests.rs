@@ -1,12 +1,22 @@
 use pura_lingua_isa::{
-    IRegisterAddr, Instruction, Instruction_Load, Instruction_UntypedCalculate, LoadContent,
-    RegisterAddr,
+    ArithmeticOp, CompareConst, CompareKind, CompareType, IRegisterAddr, Instruction,
+    Instruction_Array, Instruction_Call, Instruction_Cast, Instruction_CommonCheck,
+    Instruction_Convert, Instruction_Jump, Instruction_Load, Instruction_Move, Instruction_New,
+    Instruction_Switch, Instruction_UntypedCalculate, JumpCondition, JumpTarget, LoadContent,
+    OverflowMode, PrimitiveKind, RegisterAddr, ToCheckContent,
 };
 
 use crate::{
-    test_utils::{g_core_type, try_invoke_instructions},
-    type_system::class::Class,
-    value::managed_reference::{ManagedReference, StringAccessor},
+    stdlib::CoreTypeId,
+    test_utils::{
+        g_core_type, is_of_core_class, load_assembled, try_invoke_instructions,
+        try_invoke_instructions_for_exception,
+    },
+    type_system::{
+        cached_type_reference::GenericCachedTypeReference, class::Class, method::MethodRef,
+        type_handle::MaybeUnloadedTypeHandle,
+    },
+    value::managed_reference::{ArrayAccessor, FieldAccessor, ManagedReference, StringAccessor},
     virtual_machine::{cpu_manager::CpuID, global_vm},
 };
 
@@ -323,3 +333,1653 @@ fn calculating() {
         std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
     }
 }
+
+#[test]
+fn calculating_float() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Double).into(),
+            g_core_type!(System_Double).into(),
+            g_core_type!(System_Double).into(),
+        ],
+        g_core_type!(System_Double).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::F64(1.5),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::F64(0.25),
+            }),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, f64>::Mul {
+                    lhs: RegisterAddr::new(0),
+                    rhs: RegisterAddr::new(1),
+                    target: RegisterAddr::new(2),
+                }
+                .into(),
+            ),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, f64>::ConstAddTo {
+                    target: RegisterAddr::new(2),
+                    data: 2.0,
+                }
+                .into(),
+            ),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, f64>::AddOne {
+                    target: RegisterAddr::new(2),
+                }
+                .into(),
+            ),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<f64>().read(), 1.5 * 0.25 + 2.0 + 1.0);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn calculating_overflow_modes() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_UInt8).into(),
+            g_core_type!(System_UInt8).into(),
+        ],
+        g_core_type!(System_UInt8).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::U8(250),
+            }),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, u8>::ConstArithmeticTo {
+                    op: ArithmeticOp::Add,
+                    mode: OverflowMode::Saturating,
+                    target: RegisterAddr::new(0),
+                    data: 10,
+                }
+                .into(),
+            ),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::U8(2),
+            }),
+            // Plain arithmetic wraps: 255 + 2 = 1
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, u8>::Add {
+                    lhs: RegisterAddr::new(0),
+                    rhs: RegisterAddr::new(1),
+                    target: RegisterAddr::new(0),
+                }
+                .into(),
+            ),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(0),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<u8>().read(), 1);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn dividing_by_zero() {
+    let divide = |calculate: Instruction_UntypedCalculate<RegisterAddr, i32>| {
+        try_invoke_instructions_for_exception(
+            vec![
+                g_core_type!(System_Int32).into(),
+                g_core_type!(System_Int32).into(),
+            ],
+            g_core_type!(System_Int32).into(),
+            vec![
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content: LoadContent::I32(7),
+                }),
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(1),
+                    content: LoadContent::I32(0),
+                }),
+                Instruction::Calculate(calculate.into()),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(0),
+                },
+            ],
+        )
+    };
+
+    for calculate in [
+        Instruction_UntypedCalculate::Div {
+            lhs: RegisterAddr::new(0),
+            rhs: RegisterAddr::new(1),
+            target: RegisterAddr::new(0),
+        },
+        Instruction_UntypedCalculate::Rem {
+            lhs: RegisterAddr::new(0),
+            rhs: RegisterAddr::new(1),
+            target: RegisterAddr::new(0),
+        },
+        Instruction_UntypedCalculate::RemByConst {
+            target: RegisterAddr::new(0),
+            data: 0,
+        },
+    ] {
+        assert!(is_of_core_class(
+            &divide(calculate),
+            CoreTypeId::System_DivideByZeroException
+        ));
+    }
+}
+
+#[test]
+fn catching_checked_overflow() {
+    let exception = try_invoke_instructions_for_exception(
+        vec![g_core_type!(System_UInt8).into()],
+        g_core_type!(System_UInt8).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::U8(250),
+            }),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, u8>::ConstArithmeticTo {
+                    op: ArithmeticOp::Add,
+                    mode: OverflowMode::Checked,
+                    target: RegisterAddr::new(0),
+                    data: 10,
+                }
+                .into(),
+            ),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(0),
+            },
+        ],
+    );
+    assert!(is_of_core_class(
+        &exception,
+        CoreTypeId::System_OverflowException
+    ));
+
+    let assembly = load_assembled(
+        r#".assembly "Test::CheckedOverflow"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_UInt8
+.typeref "!" $System_OverflowException
+
+.class Public "Test::CheckedOverflow::Test"
+    .extends typeref[0]
+
+    .method Public [Static] "AddTen" (typeref[2]) -> typeref[2]
+        .locals [typeref[2]]
+        Load { addr: %0, content: Arg 0 }
+    L1:
+        Calculate U8 ConstArithmeticTo { op: Add, mode: Checked, target: %0, data: 10 }
+    L2:
+        ReturnVal { register_addr: %0 }
+    L3:
+        Load { addr: %0, content: U8 0 }
+        ReturnVal { register_addr: %0 }
+    L4:
+        .try @L1 @L2 catch typeref[3] @L3 @L4
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+"#,
+    );
+    let class = assembly.get_class(0).unwrap();
+    let method = *unsafe { class.as_ref() }
+        .method_table_ref()
+        .find_first_method_by_name(widestring::utf16str!("AddTen"))
+        .unwrap();
+    let add_ten = |mut val: u8| {
+        let mut cpu = CpuID::new_write_global();
+        let res = unsafe { method.as_ref() }.typed_res_call::<u8>(
+            &mut cpu,
+            None,
+            &[(&raw mut val).cast()],
+        );
+        assert!(!cpu.has_exception());
+        res
+    };
+    assert_eq!(add_ten(5), 15);
+    assert_eq!(add_ten(250), 0);
+}
+
+#[test]
+fn converting() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Int8).into(),
+            g_core_type!(System_Int64).into(),
+            g_core_type!(System_UInt16).into(),
+        ],
+        g_core_type!(System_UInt16).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I8(-1),
+            }),
+            // Sign extension: -1i8 -> -1i64
+            Instruction::Convert(Instruction_Convert {
+                from: PrimitiveKind::I8,
+                to: PrimitiveKind::I64,
+                mode: OverflowMode::Checked,
+                source: RegisterAddr::new(0),
+                target: RegisterAddr::new(1),
+            }),
+            // Truncation: -1i64 -> 0xffffu16
+            Instruction::Convert(Instruction_Convert {
+                from: PrimitiveKind::I64,
+                to: PrimitiveKind::U16,
+                mode: OverflowMode::Wrapping,
+                source: RegisterAddr::new(1),
+                target: RegisterAddr::new(2),
+            })
+            .try_into_short(),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<u16>().read(), u16::MAX);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn calculating_bitwise() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Int32).into(),
+        ],
+        g_core_type!(System_Int32).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I32(-256),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::I32(4),
+            }),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, i32>::Sar {
+                    lhs: RegisterAddr::new(0),
+                    rhs: RegisterAddr::new(1),
+                    target: RegisterAddr::new(2),
+                }
+                .into(),
+            ),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, i32>::ConstXorTo {
+                    target: RegisterAddr::new(2),
+                    data: 0x0f,
+                }
+                .into(),
+            ),
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, i32>::ConstShrTo {
+                    target: RegisterAddr::new(2),
+                    amount: 28,
+                }
+                .into(),
+            ),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(
+            res_ptr.cast::<i32>().read(),
+            (((-256i32 >> 4) ^ 0x0f) as u32 >> 28) as i32
+        );
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn checking_compare() {
+    let compare = |content: ToCheckContent<RegisterAddr>| {
+        let (res_ptr, res_layout) = try_invoke_instructions(
+            vec![
+                g_core_type!(System_Int32).into(),
+                g_core_type!(System_Int32).into(),
+                g_core_type!(System_Boolean).into(),
+            ],
+            g_core_type!(System_Boolean).into(),
+            vec![
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content: LoadContent::I32(-1),
+                }),
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(1),
+                    content: LoadContent::I32(1),
+                }),
+                Instruction::Check(Instruction_CommonCheck {
+                    output: RegisterAddr::new(2),
+                    content,
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(2),
+                },
+            ],
+        );
+        unsafe {
+            let res = res_ptr.cast::<bool>().read();
+            std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+            res
+        }
+    };
+
+    assert!(compare(ToCheckContent::Compare {
+        kind: CompareKind::Lt,
+        ty: CompareType::I32,
+        lhs: RegisterAddr::new(0),
+        rhs: RegisterAddr::new(1),
+    }));
+    assert!(!compare(ToCheckContent::Compare {
+        kind: CompareKind::Lt,
+        ty: CompareType::U32,
+        lhs: RegisterAddr::new(0),
+        rhs: RegisterAddr::new(1),
+    }));
+    assert!(compare(ToCheckContent::CompareWithConst {
+        kind: CompareKind::Ge,
+        lhs: RegisterAddr::new(1),
+        rhs: CompareConst::I32(1),
+    }));
+    assert!(!compare(ToCheckContent::CompareWithConst {
+        kind: CompareKind::Ne,
+        lhs: RegisterAddr::new(0),
+        rhs: CompareConst::U32(u32::MAX),
+    }));
+}
+
+#[test]
+fn jumping_on_compare() {
+    // Whether the jump is taken, both registers are of `ty`
+    let jumps = |ty: MaybeUnloadedTypeHandle, lhs, rhs, condition: JumpCondition<RegisterAddr>| {
+        let load_and_return = |val: i32| {
+            [
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(2),
+                    content: LoadContent::I32(val),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(2),
+                },
+            ]
+        };
+        let mut instructions = vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: lhs,
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: rhs,
+            }),
+            Instruction::Jump(Instruction_Jump {
+                target: JumpTarget::absolute(5),
+                condition,
+            }),
+        ];
+        instructions.extend(load_and_return(0));
+        instructions.extend(load_and_return(1));
+        let (res_ptr, res_layout) = try_invoke_instructions(
+            vec![
+                ty.clone().into(),
+                ty.into(),
+                g_core_type!(System_Int32).into(),
+            ],
+            g_core_type!(System_Int32).into(),
+            instructions,
+        );
+        unsafe {
+            let res = res_ptr.cast::<i32>().read();
+            std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+            res == 1
+        }
+    };
+    let compare = |kind, ty| ToCheckContent::Compare {
+        kind,
+        ty,
+        lhs: RegisterAddr::new(0),
+        rhs: RegisterAddr::new(1),
+    };
+    let compare_with_const = |kind, rhs| ToCheckContent::CompareWithConst {
+        kind,
+        lhs: RegisterAddr::new(0),
+        rhs,
+    };
+
+    let int = || g_core_type!(System_Int32);
+    assert!(jumps(
+        int(),
+        LoadContent::I32(-1),
+        LoadContent::I32(1),
+        JumpCondition::IfCheckSucceeds(compare(CompareKind::Lt, CompareType::I32)),
+    ));
+    assert!(!jumps(
+        int(),
+        LoadContent::I32(-1),
+        LoadContent::I32(1),
+        JumpCondition::IfCheckFails(compare(CompareKind::Lt, CompareType::I32)),
+    ));
+    assert!(jumps(
+        int(),
+        LoadContent::I32(-1),
+        LoadContent::I32(1),
+        JumpCondition::IfCheckFails(compare_with_const(CompareKind::Gt, CompareConst::I32(0))),
+    ));
+    assert!(!jumps(
+        int(),
+        LoadContent::I32(-1),
+        LoadContent::I32(1),
+        JumpCondition::IfCheckSucceeds(compare_with_const(CompareKind::Gt, CompareConst::I32(0))),
+    ));
+
+    // NaN is neither equal to nor ordered with anything, including itself
+    let double = || g_core_type!(System_Double);
+    for kind in [
+        CompareKind::Eq,
+        CompareKind::Lt,
+        CompareKind::Le,
+        CompareKind::Gt,
+        CompareKind::Ge,
+    ] {
+        assert!(!jumps(
+            double(),
+            LoadContent::F64(f64::NAN),
+            LoadContent::F64(f64::NAN),
+            JumpCondition::IfCheckSucceeds(compare(kind, CompareType::F64)),
+        ));
+        assert!(jumps(
+            double(),
+            LoadContent::F64(f64::NAN),
+            LoadContent::F64(1.0),
+            JumpCondition::IfCheckFails(compare_with_const(kind, CompareConst::F64(1.0))),
+        ));
+    }
+    assert!(jumps(
+        double(),
+        LoadContent::F64(f64::NAN),
+        LoadContent::F64(f64::NAN),
+        JumpCondition::IfCheckSucceeds(compare(CompareKind::Ne, CompareType::F64)),
+    ));
+    assert!(jumps(
+        double(),
+        LoadContent::F64(f64::NAN),
+        LoadContent::F64(1.0),
+        JumpCondition::IfCheckSucceeds(compare_with_const(
+            CompareKind::Ne,
+            CompareConst::F64(f64::NAN)
+        )),
+    ));
+
+    // Objects are compared by reference
+    let object = || g_core_type!(System_Object);
+    assert!(jumps(
+        object(),
+        LoadContent::Null,
+        LoadContent::Null,
+        JumpCondition::IfCheckSucceeds(compare(CompareKind::Eq, CompareType::Object)),
+    ));
+    assert!(jumps(
+        object(),
+        LoadContent::String("a".to_owned()),
+        LoadContent::String("a".to_owned()),
+        JumpCondition::IfCheckSucceeds(compare(CompareKind::Ne, CompareType::Object)),
+    ));
+    assert!(jumps(
+        object(),
+        LoadContent::String("a".to_owned()),
+        LoadContent::Null,
+        JumpCondition::IfCheckFails(compare(CompareKind::Eq, CompareType::Object)),
+    ));
+}
+
+#[test]
+fn checking_instance_of() {
+    let is_instance_of = |ty: GenericCachedTypeReference| {
+        let (res_ptr, res_layout) = try_invoke_instructions(
+            vec![
+                g_core_type!(System_Object).into(),
+                g_core_type!(System_Boolean).into(),
+            ],
+            g_core_type!(System_Boolean).into(),
+            vec![
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content: LoadContent::String("Hello".to_owned()),
+                }),
+                Instruction::Cast(Instruction_Cast::IsInstanceOf {
+                    ty,
+                    val: RegisterAddr::new(0),
+                    output: RegisterAddr::new(1),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(1),
+                },
+            ],
+        );
+        unsafe {
+            let res = res_ptr.cast::<bool>().read();
+            std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+            res
+        }
+    };
+
+    assert!(is_instance_of(g_core_type!(System_String).into()));
+    assert!(is_instance_of(g_core_type!(System_Object).into()));
+    assert!(!is_instance_of(g_core_type!(System_Exception).into()));
+}
+
+#[test]
+fn checking_class_hierarchy_instance_of() {
+    let assembly = load_assembled(
+        r#".assembly "Test::Casts"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_Int32
+
+.typespec typedef[2] [typeref[2]]
+
+.interface Public "Test::Casts::IShape"
+.end
+
+.class Public "Test::Casts::Base"
+    .extends typeref[0]
+    .implements typedef[0] []
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+
+.class Public "Test::Casts::Generic"
+    .generics Exact 1
+    .extends typedef[1]
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+
+.class Public "Test::Casts::Leaf"
+    .extends typespec[0]
+
+    .method Public ".ctor" () -> typeref[1]
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+
+.interface Public "Test::Casts::IOther"
+.end
+"#,
+    );
+    let leaf = assembly.get_class(3).unwrap();
+    let base = assembly.get_class(1).unwrap();
+    let shape = assembly.get_interface(0).unwrap();
+    let other = assembly.get_interface(4).unwrap();
+    drop(assembly);
+    let generic = unsafe { leaf.as_ref() }.instantiated_parent().unwrap();
+
+    let new_leaf = || {
+        Instruction::New(Instruction_New::NewObject {
+            ty: leaf.into(),
+            ctor_name: MethodRef::Index(2),
+            args: vec![],
+            output: RegisterAddr::new(0),
+        })
+    };
+    let is_instance_of = |ty: GenericCachedTypeReference| {
+        let (res_ptr, res_layout) = try_invoke_instructions(
+            vec![
+                g_core_type!(System_Object).into(),
+                g_core_type!(System_Boolean).into(),
+            ],
+            g_core_type!(System_Boolean).into(),
+            vec![
+                new_leaf(),
+                Instruction::Cast(Instruction_Cast::IsInstanceOf {
+                    ty,
+                    val: RegisterAddr::new(0),
+                    output: RegisterAddr::new(1),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(1),
+                },
+            ],
+        );
+        unsafe {
+            let res = res_ptr.cast::<bool>().read();
+            std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+            res
+        }
+    };
+    let cast_class = |ty: GenericCachedTypeReference| {
+        try_invoke_instructions_for_exception(
+            vec![
+                g_core_type!(System_Object).into(),
+                g_core_type!(System_Object).into(),
+            ],
+            g_core_type!(System_Object).into(),
+            vec![
+                new_leaf(),
+                Instruction::Cast(Instruction_Cast::CastClass {
+                    ty,
+                    val: RegisterAddr::new(0),
+                    output: RegisterAddr::new(1),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(1),
+                },
+            ],
+        )
+    };
+
+    assert!(is_instance_of(leaf.into()));
+    assert!(is_instance_of(generic.into()));
+    assert!(is_instance_of(base.into()));
+    assert!(is_instance_of(g_core_type!(System_Object).into()));
+    assert!(is_instance_of(shape.into()));
+    assert!(!is_instance_of(other.into()));
+    assert!(!is_instance_of(g_core_type!(System_String).into()));
+
+    assert!(cast_class(base.into()).is_null());
+    assert!(cast_class(shape.into()).is_null());
+    assert!(is_of_core_class(
+        &cast_class(other.into()),
+        CoreTypeId::System_InvalidCastException
+    ));
+    assert!(is_of_core_class(
+        &cast_class(g_core_type!(System_Exception).into()),
+        CoreTypeId::System_InvalidCastException
+    ));
+}
+
+#[test]
+fn casting_class() {
+    let cast_string = |content: LoadContent| {
+        try_invoke_instructions_for_exception(
+            vec![
+                g_core_type!(System_Object).into(),
+                g_core_type!(System_Exception).into(),
+            ],
+            g_core_type!(System_Exception).into(),
+            vec![
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content,
+                }),
+                Instruction::Cast(Instruction_Cast::CastClass {
+                    ty: g_core_type!(System_Exception).into(),
+                    val: RegisterAddr::new(0),
+                    output: RegisterAddr::new(1),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(1),
+                },
+            ],
+        )
+    };
+
+    assert!(is_of_core_class(
+        &cast_string(LoadContent::String("Hello".to_owned())),
+        CoreTypeId::System_InvalidCastException
+    ));
+    // Null can be cast to any class
+    assert!(cast_string(LoadContent::Null).is_null());
+}
+
+#[test]
+fn catching_by_parent_class() {
+    let assembly = load_assembled(
+        r#".assembly "Test::Catching"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_Int32
+.typeref "!" $System_Exception
+.typeref "!" $System_IndexOutOfRangeException
+
+.class Public "Test::Catching::Test"
+    .extends typeref[0]
+
+    .method Public [Static] "Divide" (typeref[2], typeref[2]) -> typeref[2]
+        .locals [typeref[2], typeref[2]]
+        Load { addr: %0, content: Arg 0 }
+        Load { addr: %1, content: Arg 1 }
+    L1:
+        Calculate I32 Div { lhs: %0, rhs: %1, target: %0 }
+    L2:
+        ReturnVal { register_addr: %0 }
+    L3:
+        Load { addr: %0, content: I32 -1 }
+        ReturnVal { register_addr: %0 }
+    L4:
+        Load { addr: %0, content: I32 -2 }
+        ReturnVal { register_addr: %0 }
+    L5:
+        .try @L1 @L2 catch typeref[4] @L3 @L4
+        .try @L1 @L2 catch typeref[3] @L4 @L5
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+"#,
+    );
+    let class = assembly.get_class(0).unwrap();
+    let method = *unsafe { class.as_ref() }
+        .method_table_ref()
+        .find_first_method_by_name(widestring::utf16str!("Divide"))
+        .unwrap();
+    let divide = |mut lhs: i32, mut rhs: i32| {
+        let mut cpu = CpuID::new_write_global();
+        let res = unsafe { method.as_ref() }.typed_res_call::<i32>(
+            &mut cpu,
+            None,
+            &[(&raw mut lhs).cast(), (&raw mut rhs).cast()],
+        );
+        assert!(!cpu.has_exception());
+        res
+    };
+    assert_eq!(divide(6, 3), 2);
+    // DivideByZeroException is skipped by the IndexOutOfRangeException handler
+    // and caught as its parent System::Exception
+    assert_eq!(divide(6, 0), -2);
+}
+
+#[test]
+fn jumping_switch() {
+    let switch = |value: u32| {
+        let load_and_return = |val: i32| {
+            [
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(1),
+                    content: LoadContent::I32(val),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(1),
+                },
+            ]
+        };
+        let (res_ptr, res_layout) = try_invoke_instructions(
+            vec![
+                g_core_type!(System_UInt32).into(),
+                g_core_type!(System_Int32).into(),
+            ],
+            g_core_type!(System_Int32).into(),
+            [
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content: LoadContent::U32(value),
+                }),
+                Instruction::Switch(Instruction_Switch {
+                    value: RegisterAddr::new(0),
+                    targets: vec![JumpTarget::absolute(2), JumpTarget::absolute(4)],
+                    default: JumpTarget::absolute(6),
+                }),
+            ]
+            .into_iter()
+            .chain(load_and_return(10))
+            .chain(load_and_return(20))
+            .chain(load_and_return(30))
+            .collect(),
+        );
+        unsafe {
+            let res = res_ptr.cast::<i32>().read();
+            std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+            res
+        }
+    };
+
+    assert_eq!(switch(0), 10);
+    assert_eq!(switch(1), 20);
+    assert_eq!(switch(2), 30);
+    assert_eq!(switch(u32::MAX), 30);
+}
+
+#[test]
+fn moving_registers() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Int32).into(),
+        ],
+        g_core_type!(System_Int32).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I32(7),
+            }),
+            Instruction::Move(Instruction_Move {
+                from: RegisterAddr::new(0),
+                to: RegisterAddr::new(1),
+            }),
+            Instruction::ZeroInit {
+                target: RegisterAddr::new(0),
+            },
+            // 0 + 7
+            Instruction::Calculate(
+                Instruction_UntypedCalculate::<_, i32>::Add {
+                    lhs: RegisterAddr::new(0),
+                    rhs: RegisterAddr::new(1),
+                    target: RegisterAddr::new(0),
+                }
+                .into(),
+            ),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(0),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<i32>().read(), 7);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn calling_indirectly() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_UInt64).into(),
+            g_core_type!(System_Pointer).into(),
+            g_core_type!(System_String).into(),
+        ],
+        g_core_type!(System_String).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::U64(42),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::MethodPointer {
+                    ty: g_core_type!(System_UInt64).into(),
+                    method: MethodRef::Index(
+                        stdlib_header::StaticMethodId!(UInt64::ToString) as u32
+                    ),
+                },
+            }),
+            Instruction::Call(Instruction_Call::IndirectCall {
+                f_pointer: RegisterAddr::new(1),
+                args: vec![RegisterAddr::new(0)],
+                ret_at: RegisterAddr::new(2),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    unsafe {
+        let res = res_ptr.cast::<ManagedReference<Class>>().read();
+        assert_eq!(
+            res.access::<StringAccessor>().unwrap().get_str().unwrap(),
+            widestring::u16cstr!("42")
+        );
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn invoking_static_delegate() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_Pointer).into(),
+            g_core_type!(System_Delegate).into(),
+            g_core_type!(System_UInt64).into(),
+            g_core_type!(System_String).into(),
+        ],
+        g_core_type!(System_String).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::Null,
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::MethodPointer {
+                    ty: g_core_type!(System_UInt64).into(),
+                    method: MethodRef::Index(
+                        stdlib_header::StaticMethodId!(UInt64::ToString) as u32
+                    ),
+                },
+            }),
+            Instruction::New(Instruction_New::NewObject {
+                ty: g_core_type!(System_Delegate).into(),
+                ctor_name: MethodRef::Index(stdlib_header::MethodId!(
+                    Delegate::Constructor_Object_Pointer
+                ) as u32),
+                args: vec![RegisterAddr::new(0), RegisterAddr::new(1)],
+                output: RegisterAddr::new(2),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(3),
+                content: LoadContent::U64(42),
+            }),
+            Instruction::Call(Instruction_Call::InstanceCall {
+                val: RegisterAddr::new(2),
+                method: MethodRef::Specific {
+                    index: stdlib_header::MethodId!(Delegate::Invoke) as u32,
+                    types: vec![g_core_type!(System_String)],
+                },
+                args: vec![RegisterAddr::new(3)],
+                ret_at: RegisterAddr::new(4),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(4),
+            },
+        ],
+    );
+    unsafe {
+        let res = res_ptr.cast::<ManagedReference<Class>>().read();
+        assert_eq!(
+            res.access::<StringAccessor>().unwrap().get_str().unwrap(),
+            widestring::u16cstr!("42")
+        );
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn invoking_instance_delegate() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_String).into(),
+            g_core_type!(System_Pointer).into(),
+            g_core_type!(System_Delegate).into(),
+            g_core_type!(System_String).into(),
+        ],
+        g_core_type!(System_String).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::String("target".to_owned()),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::MethodPointer {
+                    ty: g_core_type!(System_String).into(),
+                    method: MethodRef::Index(stdlib_header::MethodId!(String::ToString) as u32),
+                },
+            }),
+            Instruction::New(Instruction_New::NewObject {
+                ty: g_core_type!(System_Delegate).into(),
+                ctor_name: MethodRef::Index(stdlib_header::MethodId!(
+                    Delegate::Constructor_Object_Pointer
+                ) as u32),
+                args: vec![RegisterAddr::new(0), RegisterAddr::new(1)],
+                output: RegisterAddr::new(2),
+            }),
+            Instruction::Call(Instruction_Call::InstanceCall {
+                val: RegisterAddr::new(2),
+                method: MethodRef::Specific {
+                    index: stdlib_header::MethodId!(Delegate::Invoke) as u32,
+                    types: vec![g_core_type!(System_String)],
+                },
+                args: vec![],
+                ret_at: RegisterAddr::new(3),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(3),
+            },
+        ],
+    );
+    unsafe {
+        let res = res_ptr.cast::<ManagedReference<Class>>().read();
+        assert_eq!(
+            res.access::<StringAccessor>().unwrap().get_str().unwrap(),
+            widestring::u16cstr!("target")
+        );
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn calling_at_tail() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![g_core_type!(System_UInt64).into()],
+        g_core_type!(System_String).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::U64(42),
+            }),
+            Instruction::Call(Instruction_Call::TailStaticCall {
+                ty: g_core_type!(System_UInt64).into(),
+                method: MethodRef::Index(stdlib_header::StaticMethodId!(UInt64::ToString) as u32),
+                args: vec![RegisterAddr::new(0)],
+            }),
+        ],
+    );
+    unsafe {
+        let res = res_ptr.cast::<ManagedReference<Class>>().read();
+        assert_eq!(
+            res.access::<StringAccessor>().unwrap().get_str().unwrap(),
+            widestring::u16cstr!("42")
+        );
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn calling_at_tail_recursively() {
+    let assembly = load_assembled(
+        r#".assembly "Test::TailRecursion"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_UInt64
+
+.class Public "Test::TailRecursion::Test"
+    .extends typeref[0]
+
+    .method Public [Static] "Count" (typeref[2]) -> typeref[2]
+        .locals [typeref[2]]
+        Load { addr: %0, content: Arg 0 }
+        Jump { target: @L1, condition: IfCheckFails IsAllZero %0 }
+        ReturnVal { register_addr: %0 }
+    L1:
+        Calculate U64 SubOne { target: %0 }
+        Call TailStaticCall { ty: typedef[0], method: method[2], args: [%0] }
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+"#,
+    );
+    let class = assembly.get_class(0).unwrap();
+    let method = unsafe { class.as_ref() }
+        .method_table_ref()
+        .find_first_method_by_name(widestring::utf16str!("Count"))
+        .unwrap();
+
+    let max_depth = |mut count: u64| {
+        let mut cpu = CpuID::new_write_global();
+        let res = unsafe { method.as_ref() }.typed_res_call::<u64>(
+            &mut cpu,
+            None,
+            &[(&raw mut count).cast()],
+        );
+        assert_eq!(res, 0);
+        assert!(!cpu.has_exception());
+        cpu.max_call_stack_depth()
+    };
+    assert_eq!(max_depth(100_000), max_depth(1));
+}
+
+#[test]
+fn calling_at_tail_in_try_range() {
+    let assembly = load_assembled(
+        r#".assembly "Test::TailCatching"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_Int32
+.typeref "!" $System_Exception
+
+.class Public "Test::TailCatching::Test"
+    .extends typeref[0]
+
+    .method Public [Static] "Divide" (typeref[2], typeref[2]) -> typeref[2]
+        .locals [typeref[2], typeref[2]]
+        Load { addr: %0, content: Arg 0 }
+        Load { addr: %1, content: Arg 1 }
+        Calculate I32 Div { lhs: %0, rhs: %1, target: %0 }
+        ReturnVal { register_addr: %0 }
+    .end
+
+    .method Public [Static] "TryDivide" (typeref[2], typeref[2]) -> typeref[2]
+        .locals [typeref[2], typeref[2]]
+        Load { addr: %0, content: Arg 0 }
+        Load { addr: %1, content: Arg 1 }
+    L1:
+        Call TailStaticCall { ty: typedef[0], method: method[2], args: [%0, %1] }
+    L2:
+        Load { addr: %0, content: I32 -1 }
+        ReturnVal { register_addr: %0 }
+    L3:
+        .try @L1 @L2 catch typeref[3] @L2 @L3
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+"#,
+    );
+    let class = assembly.get_class(0).unwrap();
+    let method = *unsafe { class.as_ref() }
+        .method_table_ref()
+        .find_first_method_by_name(widestring::utf16str!("TryDivide"))
+        .unwrap();
+    let try_divide = |mut lhs: i32, mut rhs: i32| {
+        let mut cpu = CpuID::new_write_global();
+        let res = unsafe { method.as_ref() }.typed_res_call::<i32>(
+            &mut cpu,
+            None,
+            &[(&raw mut lhs).cast(), (&raw mut rhs).cast()],
+        );
+        assert!(!cpu.has_exception());
+        res
+    };
+    assert_eq!(try_divide(6, 3), 2);
+    // The tail call is made as a normal call in the try range,
+    // so the exception is caught by the caller
+    assert_eq!(try_divide(6, 0), -1);
+}
+
+#[test]
+fn loading_null() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![g_core_type!(System_String).into()],
+        g_core_type!(System_String).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::String("not null".to_owned()),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::Null,
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(0),
+            },
+        ],
+    );
+    unsafe {
+        let res = res_ptr.cast::<ManagedReference<Class>>().read();
+        assert!(res.is_null());
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn loading_default() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![g_core_type!(System_Int64).into()],
+        g_core_type!(System_Int64).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I64(-1),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::Default(g_core_type!(System_Int64).into()),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(0),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<i64>().read(), 0);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn loading_char() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![g_core_type!(System_Char).into()],
+        g_core_type!(System_Char).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::Char(0x61),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(0),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<u16>().read(), 'a' as u16);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn boxing_and_unboxing() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_Int32).into(),
+        ],
+        g_core_type!(System_Int32).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I32(7),
+            }),
+            Instruction::Cast(Instruction_Cast::Box {
+                ty: g_core_type!(System_Int32).into(),
+                value: RegisterAddr::new(0),
+                output: RegisterAddr::new(1),
+            }),
+            Instruction::Cast(Instruction_Cast::Unbox {
+                ty: g_core_type!(System_Int32).into(),
+                obj: RegisterAddr::new(1),
+                output: RegisterAddr::new(2),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<i32>().read(), 7);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn unboxing_mismatched_type() {
+    let exception = try_invoke_instructions_for_exception(
+        vec![
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_Int64).into(),
+        ],
+        g_core_type!(System_Int64).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I32(7),
+            }),
+            Instruction::Cast(Instruction_Cast::Box {
+                ty: g_core_type!(System_Int32).into(),
+                value: RegisterAddr::new(0),
+                output: RegisterAddr::new(1),
+            }),
+            Instruction::Cast(Instruction_Cast::Unbox {
+                ty: g_core_type!(System_Int64).into(),
+                obj: RegisterAddr::new(1),
+                output: RegisterAddr::new(2),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    assert!(is_of_core_class(
+        &exception,
+        CoreTypeId::System_InvalidCastException
+    ));
+}
+
+#[test]
+fn checking_boxed_instance_of() {
+    let is_instance_of = |ty: GenericCachedTypeReference| {
+        let (res_ptr, res_layout) = try_invoke_instructions(
+            vec![
+                g_core_type!(System_Int32).into(),
+                g_core_type!(System_Object).into(),
+                g_core_type!(System_Boolean).into(),
+            ],
+            g_core_type!(System_Boolean).into(),
+            vec![
+                Instruction::Load(Instruction_Load {
+                    addr: RegisterAddr::new(0),
+                    content: LoadContent::I32(7),
+                }),
+                Instruction::Cast(Instruction_Cast::Box {
+                    ty: g_core_type!(System_Int32).into(),
+                    value: RegisterAddr::new(0),
+                    output: RegisterAddr::new(1),
+                }),
+                Instruction::Cast(Instruction_Cast::IsInstanceOf {
+                    ty,
+                    val: RegisterAddr::new(1),
+                    output: RegisterAddr::new(2),
+                }),
+                Instruction::ReturnVal {
+                    register_addr: RegisterAddr::new(2),
+                },
+            ],
+        );
+        unsafe {
+            let res = res_ptr.cast::<bool>().read();
+            std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+            res
+        }
+    };
+
+    assert!(is_instance_of(g_core_type!(System_Int32).into()));
+    assert!(is_instance_of(g_core_type!(System_Object).into()));
+    assert!(!is_instance_of(g_core_type!(System_Int64).into()));
+    assert!(!is_instance_of(g_core_type!(System_String).into()));
+}
+
+#[test]
+fn calling_boxed_to_string() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_String).into(),
+        ],
+        g_core_type!(System_String).into(),
+        vec![
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(0),
+                content: LoadContent::I32(-7),
+            }),
+            Instruction::Cast(Instruction_Cast::Box {
+                ty: g_core_type!(System_Int32).into(),
+                value: RegisterAddr::new(0),
+                output: RegisterAddr::new(1),
+            }),
+            Instruction::Call(Instruction_Call::InstanceCall {
+                val: RegisterAddr::new(1),
+                method: MethodRef::Index(stdlib_header::MethodId!(Object::ToString) as u32),
+                args: vec![],
+                ret_at: RegisterAddr::new(2),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    unsafe {
+        let res = res_ptr.cast::<ManagedReference<Class>>().read();
+        assert_eq!(
+            res.access::<StringAccessor>().unwrap().get_str().unwrap(),
+            widestring::u16cstr!("-7")
+        );
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn accessing_array_elements() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_USize).into(),
+            g_core_type!(System_Int32).into(),
+            g_core_type!(System_Int32).into(),
+        ],
+        g_core_type!(System_Int32).into(),
+        vec![
+            Instruction::New(Instruction_New::NewArray {
+                element_type: g_core_type!(System_Int32).into(),
+                len: 3,
+                output: RegisterAddr::new(0),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::U64(2),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(2),
+                content: LoadContent::I32(5),
+            }),
+            Instruction::Array(Instruction_Array::StoreElement {
+                array: RegisterAddr::new(0),
+                index: RegisterAddr::new(1),
+                value: RegisterAddr::new(2),
+            }),
+            Instruction::Array(Instruction_Array::LoadElement {
+                array: RegisterAddr::new(0),
+                index: RegisterAddr::new(1),
+                output: RegisterAddr::new(3),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(3),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<i32>().read(), 5);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn index_out_of_range() {
+    let exception = try_invoke_instructions_for_exception(
+        vec![
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_USize).into(),
+            g_core_type!(System_Int32).into(),
+        ],
+        g_core_type!(System_Int32).into(),
+        vec![
+            Instruction::New(Instruction_New::NewArray {
+                element_type: g_core_type!(System_Int32).into(),
+                len: 3,
+                output: RegisterAddr::new(0),
+            }),
+            Instruction::Load(Instruction_Load {
+                addr: RegisterAddr::new(1),
+                content: LoadContent::U64(3),
+            }),
+            Instruction::Array(Instruction_Array::LoadElement {
+                array: RegisterAddr::new(0),
+                index: RegisterAddr::new(1),
+                output: RegisterAddr::new(2),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(2),
+            },
+        ],
+    );
+    assert!(is_of_core_class(
+        &exception,
+        CoreTypeId::System_IndexOutOfRangeException
+    ));
+}
+
+#[test]
+fn getting_array_length() {
+    let (res_ptr, res_layout) = try_invoke_instructions(
+        vec![
+            g_core_type!(System_Object).into(),
+            g_core_type!(System_USize).into(),
+        ],
+        g_core_type!(System_USize).into(),
+        vec![
+            Instruction::New(Instruction_New::NewArray {
+                element_type: g_core_type!(System_String).into(),
+                len: 4,
+                output: RegisterAddr::new(0),
+            }),
+            Instruction::Array(Instruction_Array::ArrayLength {
+                array: RegisterAddr::new(0),
+                output: RegisterAddr::new(1),
+            }),
+            Instruction::ReturnVal {
+                register_addr: RegisterAddr::new(1),
+            },
+        ],
+    );
+    unsafe {
+        assert_eq!(res_ptr.cast::<usize>().read(), 4);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+}
+
+#[test]
+fn capturing_source_locations() {
+    let source = r#".assembly "Test::SourceLocations"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_String
+.typeref "!" $System_Exception
+
+.debug_info
+.document "test.pl"
+
+.class Public "Test::SourceLocations::Test"
+    .extends typeref[0]
+
+    .method Public [Static] "Throw" () -> typeref[1]
+        .locals [typeref[2], typeref[3]]
+        .line 0 3 5
+        Load { addr: %0, content: String "thrown" }
+        .line 0 4 5
+        New NewObject { ty: typeref[3], ctor_name: method[$CTOR], args: [%0], output: %1 }
+        Throw { exception_addr: %1 }
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+"#
+    .replace(
+        "$CTOR",
+        &(stdlib_header::MethodId!(Exception::Constructor_String) as u32).to_string(),
+    );
+    let assembly = load_assembled(&source);
+
+    let class = assembly.get_class(0).unwrap();
+    let method = unsafe { class.as_ref() }
+        .method_table_ref()
+        .find_first_method_by_name(widestring::utf16str!("Throw"))
+        .unwrap();
+
+    let mut cpu = CpuID::new_write_global();
+    unsafe { method.as_ref() }.typed_res_call::<()>(&mut cpu, None, &[]);
+    assert!(cpu.has_exception());
+
+    let exception = cpu.take_exception();
+    let stack_trace = exception
+        .const_access::<FieldAccessor<_>>()
+        .typed_field::<ManagedReference<Class>>(
+            stdlib_header::FieldId!(Exception::StackTrace) as _,
+            Default::default(),
+        )
+        .unwrap();
+    let stack_trace = unsafe {
+        stack_trace
+            .access::<ArrayAccessor>()
+            .unwrap()
+            .as_slice::<ManagedReference<Class>>()
+            .unwrap()
+    };
+    assert!(stack_trace.iter().any(|x| {
+        x.access::<StringAccessor>()
+            .unwrap()
+            .to_string_lossy()
+            .unwrap()
+            .contains("test.pl:4")
+    }));
+}
+
+#[test]
+fn calling_lazy_method() -> binary::prelude::BinaryResult<()> {
+    let source = r#".assembly "Test::Lazy"
+
+.typeref "!" $System_Object
+.typeref "!" $System_Void
+.typeref "!" $System_UInt64
+
+.class Public "Test::Lazy::Test"
+    .extends typeref[0]
+
+    .method Public [Static] "Answer" () -> typeref[2]
+        .locals [typeref[2]]
+        Load { addr: %0, content: U64 42 }
+        ReturnVal { register_addr: %0 }
+    .end
+
+    .method Public [Static] ".sctor" () -> typeref[1]
+    .end
+.end
+"#;
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    assembler::assemble(source).unwrap().write_to(&mut bytes)?;
+    let lazy = binary::lazy::SharedLazyAssembly::from_bytes(bytes.into_inner())?;
+    global_vm()
+        .assembly_manager()
+        .load_lazy_binaries(vec![lazy])?;
+
+    let assembly = global_vm()
+        .assembly_manager()
+        .get_assembly_by_name(widestring::utf16str!("Test::Lazy"))
+        .unwrap();
+    let class = assembly.get_class(0).unwrap();
+    let method = unsafe { class.as_ref() }
+        .method_table_ref()
+        .find_first_method_by_name(widestring::utf16str!("Answer"))
+        .unwrap();
+    let method = unsafe { method.as_ref() };
+
+    let mut cpu = CpuID::new_write_global();
+    assert_eq!(method.typed_res_call::<u64>(&mut cpu, None, &[]), 42);
+    assert!(!cpu.has_exception());
+    assert_eq!(method.instructions()?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn rejecting_unsatisfied_dependencies() {
+    let dependency = assembler::assemble(
+        r#".assembly "Test::Dependency"
+.version 1.0.0
+"#,
+    )
+    .unwrap();
+    let unsatisfied = assembler::assemble(
+        r#".assembly "Test::Unsatisfied"
+
+.assembly_ref "Test::Dependency" Compatible 2.0.0
+"#,
+    )
+    .unwrap();
+    let undeclared = assembler::assemble(
+        r#".assembly "Test::Undeclared"
+
+.typeref "Test::Dependency" 0
+"#,
+    )
+    .unwrap();
+
+    let manager = global_vm().assembly_manager();
+    for dependent in [&unsatisfied, &undeclared] {
+        assert!(
+            manager
+                .load_binaries(&[
+                    binary::assembly::Assembly::from_builder(&dependency),
+                    binary::assembly::Assembly::from_builder(dependent),
+                ])
+                .is_err()
+        );
+    }
+    for name in [
+        widestring::utf16str!("Test::Dependency"),
+        widestring::utf16str!("Test::Unsatisfied"),
+        widestring::utf16str!("Test::Undeclared"),
+    ] {
+        assert!(manager.get_assembly_by_name(name).is_none());
+    }
+}
@@ -7,6 +7,7 @@
 #![feature(const_default)]
 #![feature(const_try)]
 #![feature(current_thread_id)]
+#![feature(thread_id_value)]
 #![feature(likely_unlikely)]
 #![feature(associated_type_defaults)]
 #![feature(decl_macro)]
@@ -124,6 +124,17 @@ const impl CoreTypeIdConstExt for CoreTypeId {
             | System_Reflection_FieldInfo
             | System_Reflection_MethodInfo
             | System_Reflection_ParameterInfo => None,
+
+            System_Single => Some(Layout::new::<f32>()),
+            System_Double => Some(Layout::new::<f64>()),
+
+            System_DivideByZeroException | System_OverflowException => None,
+
+            System_InvalidCastException => None,
+
+            System_Threading_Monitor | System_Threading_SynchronizationLockException => None,
+
+            System_Delegate => None,
         }
     }
 
@@ -191,6 +202,21 @@ const impl CoreTypeIdConstExt for CoreTypeId {
             | System_Reflection_FieldInfo
             | System_Reflection_MethodInfo
             | System_Reflection_ParameterInfo => Some(Layout::new::<ManagedReference<Class>>()),
+
+            System_Single => Some(Layout::new::<f32>()),
+            System_Double => Some(Layout::new::<f64>()),
+
+            System_DivideByZeroException | System_OverflowException => {
+                Some(Layout::new::<ManagedReference<Class>>())
+            }
+
+            System_InvalidCastException => Some(Layout::new::<ManagedReference<Class>>()),
+
+            System_Threading_Monitor | System_Threading_SynchronizationLockException => {
+                Some(Layout::new::<ManagedReference<Class>>())
+            }
+
+            System_Delegate => Some(Layout::new::<ManagedReference<Class>>()),
         }
     }
 
@@ -203,6 +229,9 @@ const impl CoreTypeIdConstExt for CoreTypeId {
         macro of_System_Reflection($name:ident) {
             System::Reflection::$name::load
         }
+        macro of_System_Threading($name:ident) {
+            System::Threading::$name::load
+        }
         match self {
             System_Object => of_System!(Object),
             System_ValueType => of_System!(ValueType),
@@ -263,6 +292,21 @@ const impl CoreTypeIdConstExt for CoreTypeId {
             System_Reflection_FieldInfo => of_System_Reflection!(FieldInfo),
             System_Reflection_MethodInfo => of_System_Reflection!(MethodInfo),
             System_Reflection_ParameterInfo => of_System_Reflection!(ParameterInfo),
+
+            System_Single => System::_Floats::System_Single,
+            System_Double => System::_Floats::System_Double,
+
+            System_DivideByZeroException => of_System!(DivideByZeroException),
+            System_OverflowException => of_System!(OverflowException),
+
+            System_InvalidCastException => of_System!(InvalidCastException),
+
+            System_Threading_Monitor => of_System_Threading!(Monitor),
+            System_Threading_SynchronizationLockException => {
+                of_System_Threading!(SynchronizationLockException)
+            }
+
+            System_Delegate => of_System!(Delegate),
         }
     }
 }
@@ -339,6 +383,19 @@ impl CoreTypeIdExt for CoreTypeId {
             | System_Reflection_FieldInfo
             | System_Reflection_MethodInfo
             | System_Reflection_ParameterInfo => Some(Type::pointer()),
+
+            System_Single => Some(Type::f32()),
+            System_Double => Some(Type::f64()),
+
+            System_DivideByZeroException | System_OverflowException => Some(Type::pointer()),
+
+            System_InvalidCastException => Some(Type::pointer()),
+
+            System_Threading_Monitor | System_Threading_SynchronizationLockException => {
+                Some(Type::pointer())
+            }
+
+            System_Delegate => Some(Type::pointer()),
         }
     }
     fn non_purus_call_type(self) -> Option<NonPurusCallType> {
@@ -404,6 +461,21 @@ impl CoreTypeIdExt for CoreTypeId {
             | System_Reflection_FieldInfo
             | System_Reflection_MethodInfo
             | System_Reflection_ParameterInfo => Some(NonPurusCallType::Object),
+
+            System_Single => Some(NonPurusCallType::F32),
+            System_Double => Some(NonPurusCallType::F64),
+
+            System_DivideByZeroException | System_OverflowException => {
+                Some(NonPurusCallType::Object)
+            }
+
+            System_InvalidCastException => Some(NonPurusCallType::Object),
+
+            System_Threading_Monitor | System_Threading_SynchronizationLockException => {
+                Some(NonPurusCallType::Object)
+            }
+
+            System_Delegate => Some(NonPurusCallType::Object),
         }
     }
 }
@@ -20,12 +20,15 @@ pub mod AllocException;
 pub mod Array_1;
 pub mod Boolean;
 pub mod Char;
+pub mod Delegate;
+pub mod DivideByZeroException;
 pub mod DlErrorException;
 pub mod DynamicLibrary;
 pub mod ErrnoException;
 pub mod Exception;
 pub mod IDispose;
 pub mod IndexOutOfRangeException;
+pub mod InvalidCastException;
 pub mod InvalidEnumException;
 pub mod LargeString;
 pub mod NonPurusCallConfiguration;
@@ -33,6 +36,7 @@ pub mod NonPurusCallType;
 pub mod NullReferenceException;
 pub mod Nullable_1;
 pub mod Object;
+pub mod OverflowException;
 pub mod Pointer;
 pub mod Reference_1;
 pub mod Reflection;
@@ -40,10 +44,12 @@ pub mod RuntimeBasic;
 pub mod Span_1;
 pub mod String;
 pub mod ThreadLocal_1;
+pub mod Threading;
 pub mod Tuple;
 pub mod ValueType;
 pub mod Void;
 pub mod Win32Exception;
+pub mod _Floats;
 pub mod _Integers;
 
 pub(crate) macro common_new_method($mt:ident $TMethodId:ident $id:ident $f:path) {
@@ -0,0 +1,81 @@
+use std::{ffi::c_void, ptr::NonNull};
+
+use stdlib_header::System::Delegate::FieldId;
+
+use crate::{
+    stdlib::System::{_define_class, common_new_method, default_sctor},
+    type_system::{
+        class::Class,
+        method::{Method, MethodPointer},
+    },
+    value::managed_reference::{FieldAccessor, ManagedReference},
+    virtual_machine::cpu::CPU,
+};
+
+pub extern "system" fn Constructor_Object_Pointer(
+    _: &mut CPU,
+    _: &Method<Class>,
+    this: &mut ManagedReference<Class>,
+    target: ManagedReference<Class>,
+    method_pointer: MethodPointer,
+) {
+    let accessor = this.const_access_mut::<FieldAccessor<Class>>();
+    *accessor
+        .typed_field_mut::<ManagedReference<Class>>(FieldId::Target as _, Default::default())
+        .unwrap() = target;
+    *accessor
+        .typed_field_mut::<MethodPointer>(FieldId::MethodPointer as _, Default::default())
+        .unwrap() = method_pointer;
+}
+
+/// Calls the captured method with the extra args,
+/// using the captured target as `this` if the method is not static
+pub extern "system" fn Invoke(
+    cpu: &mut CPU,
+    method: &Method<Class>,
+    this: &mut ManagedReference<Class>,
+    extra_arg_ptr: NonNull<*mut c_void>,
+    extra_arg_len: usize,
+    return_buffer: NonNull<c_void>,
+) {
+    let accessor = this.const_access_mut::<FieldAccessor<Class>>();
+    let target = accessor
+        .read_typed_field::<ManagedReference<Class>>(FieldId::Target as _, Default::default())
+        .unwrap();
+    let method_pointer = accessor
+        .read_typed_field::<MethodPointer>(FieldId::MethodPointer as _, Default::default())
+        .unwrap();
+    let args = unsafe { std::slice::from_raw_parts(extra_arg_ptr.as_ptr(), extra_arg_len) };
+
+    let this = match method_pointer.is_static() {
+        Some(true) => None,
+        Some(false) if !target.is_null() => Some(NonNull::from_ref(&target).cast()),
+        _ => {
+            assert!(cpu.throw_helper_mut().null_reference());
+            return;
+        }
+    };
+    let (ret_ptr, ret_layout, actual_layout) =
+        method_pointer.untyped_call(cpu, this, args).unwrap();
+
+    // The generic argument of `Invoke` decides how large the return buffer is,
+    // and the return type of the target is all there is when it is not instantiated
+    let size = match method.try_get_return_type() {
+        Some(ty) => actual_layout.size().min(ty.val_layout().size()),
+        None => actual_layout.size(),
+    };
+    unsafe {
+        return_buffer.cast::<u8>().copy_from(ret_ptr, size);
+        std::alloc::Allocator::deallocate(&std::alloc::Global, ret_ptr, ret_layout);
+    }
+}
+
+_define_class!(
+    fn load(assembly, mt, method_info)
+    Delegate
+#methods(TMethodId):
+    Constructor_Object_Pointer => common_new_method!(mt TMethodId Constructor_Object_Pointer Constructor_Object_Pointer);
+    Invoke => common_new_method!(mt TMethodId Invoke Invoke);
+#static_methods(TStaticMethodId):
+    StaticConstructor => default_sctor!(mt TStaticMethodId);
+);
@@ -65,6 +65,9 @@ define_common!(CreatePointer Pointer);
 define_common!(CreateString String);
 define_common!(CreateObject Object);
 
+define_common!(CreateF32 F32);
+define_common!(CreateF64 F64);
+
 pub extern "system" fn CreateStructure(
     cpu: &mut CPU,
     _: &Method<Class>,
@@ -123,4 +126,7 @@ _define_class!(
     CreateString => make_common!(TStaticMethodId mt String);
     CreateObject => make_common!(TStaticMethodId mt Object);
     CreateStructure => common_new_method!(mt TStaticMethodId CreateStructure CreateStructure);
+
+    CreateF32 => make_common!(TStaticMethodId mt F32);
+    CreateF64 => make_common!(TStaticMethodId mt F64);
 );
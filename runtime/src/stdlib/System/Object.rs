@@ -1,6 +1,8 @@
+use std::ffi::c_void;
+
 use crate::{
     stdlib::System::{_define_class, common_new_method, default_sctor},
-    type_system::{class::Class, method::Method},
+    type_system::{class::Class, method::Method, method_table::MethodTable, r#struct::Struct},
     value::managed_reference::ManagedReference,
     virtual_machine::cpu::CPU,
 };
@@ -12,6 +14,9 @@ pub extern "system" fn ToString(
     _: &Method<Class>,
     this: &ManagedReference<Class>,
 ) -> ManagedReference<Class> {
+    if let Some(mt) = this.boxed_method_table_ref() {
+        return boxed_to_string(cpu, this, mt);
+    }
     println!("DEFAULT ToString");
     match this.method_table_ref() {
         Some(mt) => {
@@ -22,6 +27,28 @@ pub extern "system" fn ToString(
     }
 }
 
+/// Dispatches to the `ToString` of the boxed struct,
+/// which may be either static taking the value by reference or an instance method
+fn boxed_to_string(
+    cpu: &mut CPU,
+    this: &ManagedReference<Class>,
+    mt: &MethodTable<Struct>,
+) -> ManagedReference<Class> {
+    let Some(m) = mt
+        .find_first_method_by_name(widestring::utf16str!("ToString"))
+        .map(|x| *x)
+    else {
+        return ManagedReference::new_string_w(cpu, mt.ty_ref().name());
+    };
+    let m_ref = unsafe { m.as_ref() };
+    let data = this.unboxed_data().unwrap();
+    if m_ref.attr().is_static() {
+        m_ref.typed_res_call(cpu, None, &[data.cast::<c_void>().as_ptr()])
+    } else {
+        m_ref.typed_res_call(cpu, Some(data.cast()), &[])
+    }
+}
+
 _define_class!(
     fn load(assembly, mt, method_info)
     Object
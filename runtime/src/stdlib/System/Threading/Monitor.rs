@@ -0,0 +1,85 @@
+use std::{ptr::NonNull, sync::Arc};
+
+use crate::{
+    stdlib::System::{common_new_method, default_sctor},
+    type_system::{class::Class, method::Method},
+    value::{
+        managed_reference::ManagedReference,
+        object_header::{ObjectHeader, SyncBlock},
+    },
+    virtual_machine::cpu::CPU,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Throws `System::NullReferenceException` and returns [`None`] if `obj` is null
+fn get_sync_block(cpu: &mut CPU, obj: &ManagedReference<Class>) -> Option<Arc<SyncBlock>> {
+    match NonNull::new(obj.header_ptr()) {
+        Some(header) => Some(unsafe { ObjectHeader::sync_block(header) }),
+        None => {
+            assert!(cpu.throw_helper_mut().null_reference());
+            None
+        }
+    }
+}
+
+pub extern "system" fn Enter(cpu: &mut CPU, _: &Method<Class>, obj: ManagedReference<Class>) {
+    if let Some(block) = get_sync_block(cpu, &obj) {
+        block.enter();
+    }
+}
+
+pub extern "system" fn TryEnter(
+    cpu: &mut CPU,
+    _: &Method<Class>,
+    obj: ManagedReference<Class>,
+) -> bool {
+    get_sync_block(cpu, &obj).is_some_and(|block| block.try_enter())
+}
+
+pub extern "system" fn Exit(cpu: &mut CPU, _: &Method<Class>, obj: ManagedReference<Class>) {
+    if let Some(block) = get_sync_block(cpu, &obj)
+        && !block.exit()
+    {
+        assert!(cpu.throw_helper_mut().synchronization_lock());
+    }
+}
+
+pub extern "system" fn Wait(cpu: &mut CPU, _: &Method<Class>, obj: ManagedReference<Class>) {
+    if let Some(block) = get_sync_block(cpu, &obj)
+        && !block.wait()
+    {
+        assert!(cpu.throw_helper_mut().synchronization_lock());
+    }
+}
+
+pub extern "system" fn Pulse(cpu: &mut CPU, _: &Method<Class>, obj: ManagedReference<Class>) {
+    if let Some(block) = get_sync_block(cpu, &obj)
+        && !block.pulse(false)
+    {
+        assert!(cpu.throw_helper_mut().synchronization_lock());
+    }
+}
+
+pub extern "system" fn PulseAll(cpu: &mut CPU, _: &Method<Class>, obj: ManagedReference<Class>) {
+    if let Some(block) = get_sync_block(cpu, &obj)
+        && !block.pulse(true)
+    {
+        assert!(cpu.throw_helper_mut().synchronization_lock());
+    }
+}
+
+super::_define_class!(
+    fn load(assembly, mt, method_info)
+    Monitor
+#methods(TMethodId):
+#static_methods(TStaticMethodId):
+    StaticConstructor => default_sctor!(mt TStaticMethodId);
+    Enter => common_new_method!(mt TStaticMethodId Enter Enter);
+    TryEnter => common_new_method!(mt TStaticMethodId TryEnter TryEnter);
+    Exit => common_new_method!(mt TStaticMethodId Exit Exit);
+    Wait => common_new_method!(mt TStaticMethodId Wait Wait);
+    Pulse => common_new_method!(mt TStaticMethodId Pulse Pulse);
+    PulseAll => common_new_method!(mt TStaticMethodId PulseAll PulseAll);
+);
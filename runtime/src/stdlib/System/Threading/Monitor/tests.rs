@@ -0,0 +1,89 @@
+use stdlib_header::System::Threading::Monitor::StaticMethodId;
+
+use crate::{
+    memory::ThreadSafeNonNull,
+    test_utils::g_core_class,
+    type_system::{class::Class, method::Method},
+    value::managed_reference::ManagedReference,
+    virtual_machine::{EnsureGlobalVirtualMachineInitialized, cpu::CPU, cpu_manager::CpuID},
+};
+
+fn get_method(id: StaticMethodId) -> ThreadSafeNonNull<Method<Class>> {
+    let mt = unsafe {
+        g_core_class!(System_Threading_Monitor)
+            .as_ref()
+            .method_table_ref()
+    };
+    ThreadSafeNonNull::new(*mt.get_method(id as u32).unwrap())
+}
+
+fn call<R>(
+    cpu: &mut CPU,
+    method: ThreadSafeNonNull<Method<Class>>,
+    obj: &ManagedReference<Class>,
+) -> R {
+    unsafe { method.as_ref() }.typed_res_call(cpu, None, &[(&raw const *obj).cast_mut().cast()])
+}
+
+#[test]
+fn entering_recursively() {
+    EnsureGlobalVirtualMachineInitialized();
+
+    let Enter = get_method(StaticMethodId::Enter);
+    let TryEnter = get_method(StaticMethodId::TryEnter);
+    let Exit = get_method(StaticMethodId::Exit);
+
+    let mut cpu = CpuID::new_write_global();
+    let obj = ManagedReference::new_string(&mut cpu, "lock");
+
+    let try_enter_on_other_thread = || {
+        std::thread::spawn(move || {
+            let mut cpu = CpuID::new_write_global();
+            let entered = call::<bool>(&mut cpu, TryEnter, &obj);
+            if entered {
+                call::<()>(&mut cpu, Exit, &obj);
+            }
+            entered
+        })
+        .join()
+        .unwrap()
+    };
+
+    call::<()>(&mut cpu, Enter, &obj);
+    call::<()>(&mut cpu, Enter, &obj);
+    assert!(!try_enter_on_other_thread());
+
+    call::<()>(&mut cpu, Exit, &obj);
+    assert!(!try_enter_on_other_thread());
+
+    call::<()>(&mut cpu, Exit, &obj);
+    assert!(try_enter_on_other_thread());
+}
+
+#[test]
+fn waiting_and_pulsing() {
+    EnsureGlobalVirtualMachineInitialized();
+
+    let Enter = get_method(StaticMethodId::Enter);
+    let Exit = get_method(StaticMethodId::Exit);
+    let Wait = get_method(StaticMethodId::Wait);
+    let Pulse = get_method(StaticMethodId::Pulse);
+
+    let mut cpu = CpuID::new_write_global();
+    let obj = ManagedReference::new_string(&mut cpu, "lock");
+
+    call::<()>(&mut cpu, Enter, &obj);
+
+    // It can only enter the monitor after the main thread starts waiting
+    let pulser = std::thread::spawn(move || {
+        let mut cpu = CpuID::new_write_global();
+        call::<()>(&mut cpu, Enter, &obj);
+        call::<()>(&mut cpu, Pulse, &obj);
+        call::<()>(&mut cpu, Exit, &obj);
+    });
+
+    call::<()>(&mut cpu, Wait, &obj);
+    call::<()>(&mut cpu, Exit, &obj);
+
+    pulser.join().unwrap();
+}
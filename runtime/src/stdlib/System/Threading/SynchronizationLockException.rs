@@ -0,0 +1,27 @@
+use crate::{
+    stdlib::System::{common_new_method, default_sctor},
+    type_system::{class::Class, method::Method},
+    value::managed_reference::ManagedReference,
+    virtual_machine::cpu::CPU,
+};
+
+pub extern "system" fn Constructor(
+    cpu: &mut CPU,
+    method: &Method<Class>,
+    this: &mut ManagedReference<Class>,
+) {
+    let message = ManagedReference::new_string(
+        cpu,
+        "Object synchronization method was called from an unsynchronized block of code",
+    );
+    super::super::Exception::Constructor_String(cpu, method, this, message);
+}
+
+super::_define_class!(
+    fn load(assembly, mt, method_info)
+    SynchronizationLockException
+#methods(TMethodId):
+    Constructor => common_new_method!(mt TMethodId Constructor Constructor);
+#static_methods(TStaticMethodId):
+    StaticConstructor => default_sctor!(mt TStaticMethodId);
+);
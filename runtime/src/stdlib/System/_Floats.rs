@@ -0,0 +1,46 @@
+use std::ptr::NonNull;
+
+use stdlib_header::CoreTypeId;
+
+use crate::{
+    stdlib::{
+        CoreTypeIdConstExt as _,
+        System::_Integers::{ToString, define},
+    },
+    type_system::{
+        generics::GenericCountRequirement,
+        method::{ExceptionTable, Method, Parameter},
+        type_handle::MaybeUnloadedTypeHandle,
+    },
+};
+
+define! {
+assembly, mt, method_info, RustT:
+    System_Single of Single => f32;
+    System_Double of Double => f64;
+@StaticConstructor =>
+    Method::default_sctor(
+        Some(mt),
+        global::attr!(
+            method Public {Static}
+        ),
+    );
+@ToString =>
+    Method::native(
+        Some(mt),
+        widestring::utf16str!("ToString").to_owned(),
+        global::attr!(
+            method Public {Static}
+        ),
+        GenericCountRequirement::default(),
+        vec![Parameter::new(
+            MaybeUnloadedTypeHandle::Unloaded(CoreTypeId::System_UInt8.static_type_ref()),
+            global::attr!(parameter { ByRef }),
+        )],
+        MaybeUnloadedTypeHandle::Unloaded(CoreTypeId::System_String.static_type_ref()).into(),
+        global::attrs::CallConvention::PlatformDefault,
+        None,
+        ToString::<RustT> as _,
+        |method| ExceptionTable::new(NonNull::from_ref(method)),
+    );
+}
@@ -25,7 +25,7 @@ pub extern "system" fn ToString<T: Display>(
     ManagedReference::new_string(cpu, &this.to_string())
 }
 
-macro define(
+pub(super) macro define(
 $assembly:ident, $mt:ident, $method_info:ident, $RustT:ident $(,)?:
     $(
         $Name:ident of $HeaderName:ident => $inner:ident;
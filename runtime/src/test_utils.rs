@@ -5,6 +5,7 @@ use global::attrs::{CallConvention, MethodAttr, MethodImplementationFlags, Visib
 use mem_leak_detector::LeakDetector;
 
 use crate::{
+    stdlib::{CoreTypeId, CoreTypeIdExt},
     type_system::{
         assembly::{Assembly, TypeContainer},
         cached_type_reference::GenericCachedTypeReference,
@@ -13,6 +14,7 @@ use crate::{
         method::{ExceptionTable, Method, RuntimeInstruction},
         method_table::MethodTable,
     },
+    value::managed_reference::ManagedReference,
     virtual_machine::{cpu_manager::CpuID, global_vm},
 };
 
@@ -49,6 +51,39 @@ pub fn try_invoke_instructions(
     return_type: GenericCachedTypeReference,
     instructions: Vec<RuntimeInstruction>,
 ) -> (NonNull<u8>, Layout) {
+    let (res_ptr, res_layout, _) = invoke_instructions(locals, return_type, instructions);
+    (res_ptr, res_layout)
+}
+
+/// Same as [`try_invoke_instructions`], but returns the uncaught exception,
+/// which is null if nothing is thrown
+pub fn try_invoke_instructions_for_exception(
+    locals: Vec<GenericCachedTypeReference>,
+    return_type: GenericCachedTypeReference,
+    instructions: Vec<RuntimeInstruction>,
+) -> ManagedReference<Class> {
+    let (res_ptr, res_layout, exception) = invoke_instructions(locals, return_type, instructions);
+    unsafe {
+        std::alloc::Allocator::deallocate(&std::alloc::Global, res_ptr, res_layout);
+    }
+    exception
+}
+
+/// Whether `val` is exactly an instance of the core class
+pub fn is_of_core_class(val: &ManagedReference<Class>, id: CoreTypeId) -> bool {
+    val.method_table_ref().is_some_and(|mt| {
+        std::ptr::addr_eq(
+            mt,
+            unsafe { id.global_type_handle().unwrap_class().as_ref() }.method_table_ref(),
+        )
+    })
+}
+
+fn invoke_instructions(
+    locals: Vec<GenericCachedTypeReference>,
+    return_type: GenericCachedTypeReference,
+    instructions: Vec<RuntimeInstruction>,
+) -> (NonNull<u8>, Layout, ManagedReference<Class>) {
     let assembly = new_global_assembly("Test::TryInvoke", |assembly| {
         vec![
             Class::new(
@@ -97,5 +132,31 @@ pub fn try_invoke_instructions(
 
     let mut cpu = CpuID::new_write_global();
 
-    unsafe { method.as_ref().untyped_call(&mut cpu, None, &[]) }
+    let (res_ptr, res_layout) = unsafe { method.as_ref().untyped_call(&mut cpu, None, &[]) };
+    (res_ptr, res_layout, cpu.take_exception())
+}
+
+/// Assembles `source` with the textual assembler and loads the result like a `.plb` file
+///
+/// `$System_Object` and the like are replaced with the ids of the core types
+pub fn load_assembled(source: &str) -> MappedRwLockReadGuard<'static, Assembly> {
+    let mut source = source.to_owned();
+    let mut ids = CoreTypeId::ALL_VARIANTS.to_vec();
+    // Longer names first, so that no name is replaced as the prefix of another
+    ids.sort_by_key(|id| std::cmp::Reverse(id.raw_name().len()));
+    for id in ids {
+        source = source.replace(&format!("${}", id.raw_name()), &(id as u32).to_string());
+    }
+    let b_assembly = assembler::assemble(&source).unwrap();
+    let name = widestring::Utf16String::from_str(
+        b_assembly.get_string(b_assembly.extra_header.name).unwrap(),
+    );
+    global_vm()
+        .assembly_manager()
+        .load_binaries(&[binary::assembly::Assembly::from_builder(&b_assembly)])
+        .unwrap();
+    global_vm()
+        .assembly_manager()
+        .get_assembly_by_name(&name)
+        .unwrap()
 }
@@ -1,5 +1,9 @@
-use std::{ptr::NonNull, sync::nonpoison::RwLock};
+use std::{
+    ptr::NonNull,
+    sync::{Arc, nonpoison::RwLock},
+};
 
+use binary::{binary_core::Version, lazy::SharedLazyAssembly};
 use global::{UnwrapEnum, traits::IUnwrap};
 
 use crate::{
@@ -57,13 +61,18 @@ impl From<OwnedPtr<Interface>> for TypeContainer {
 pub struct Assembly {
     pub(crate) manager: NonNull<AssemblyManager>,
     pub(crate) name: Box<widestring::Utf16Str>,
+    pub(crate) version: Version,
     pub(crate) types: RwLock<Vec<TypeContainer>>,
+    /// The instructions of its methods are decoded from it when they are first invoked
+    pub(crate) lazy_binary: Option<Arc<SharedLazyAssembly>>,
 
     pub(crate) is_core: bool,
 }
 
 impl Assembly {
     /// The NonNull passed to f is always valid to be cast to &Self
+    ///
+    /// The assembly is built into the runtime, so it has the version of the runtime
     pub fn new_for_adding<F: FnOnce(NonNull<Self>) -> Vec<TypeContainer>>(
         name: widestring::Utf16String,
         is_core: bool,
@@ -72,7 +81,9 @@ impl Assembly {
         let mut this = Box::new(Self {
             manager: NonNull::dangling(),
             name: name.into_boxed_utfstr(),
+            version: AssemblyManager::runtime_version(),
             types: RwLock::new(Vec::new()),
+            lazy_binary: None,
             is_core,
         });
 
@@ -81,11 +92,18 @@ impl Assembly {
 
         this
     }
-    pub fn new(manager: &AssemblyManager, name: widestring::Utf16String, is_core: bool) -> Self {
+    pub fn new(
+        manager: &AssemblyManager,
+        name: widestring::Utf16String,
+        version: Version,
+        is_core: bool,
+    ) -> Self {
         Self {
             manager: NonNull::from_ref(manager),
             name: name.into_boxed_utfstr(),
+            version,
             types: RwLock::new(Vec::new()),
+            lazy_binary: None,
             is_core,
         }
     }
@@ -111,6 +129,9 @@ impl Assembly {
     pub const fn manager_ref(&self) -> &AssemblyManager {
         unsafe { self.manager.as_ref() }
     }
+    pub const fn version(&self) -> Version {
+        self.version
+    }
 }
 
 #[allow(clippy::type_complexity)]
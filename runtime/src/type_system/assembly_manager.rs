@@ -3,6 +3,7 @@ use std::{
     sync::nonpoison::{MappedRwLockReadGuard, RwLock, RwLockReadGuard},
 };
 
+use binary::binary_core::Version;
 use global::StringName;
 
 use crate::{stdlib::CoreTypeId, type_system::assembly::Assembly, virtual_machine::VirtualMachine};
@@ -47,6 +48,11 @@ impl AssemblyManager {
     pub const fn vm_ref(&self) -> &VirtualMachine {
         unsafe { self.vm.as_ref() }
     }
+
+    /// Also the version of the assemblies built into the runtime, such as the core assembly
+    pub fn runtime_version() -> Version {
+        env!("CARGO_PKG_VERSION").parse().unwrap()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -135,6 +141,15 @@ impl AssemblyManager {
         .ok()
     }
 
+    /// Names and versions of all the loaded assemblies
+    pub fn loaded_versions(&self) -> Vec<(String, Version)> {
+        self.assemblies
+            .read()
+            .iter()
+            .map(|x| (x.name.to_string(), x.version))
+            .collect()
+    }
+
     pub fn get_assembly<'a>(&'a self, id: usize) -> Option<MappedRwLockReadGuard<'a, Assembly>> {
         RwLockReadGuard::filter_map(self.assemblies.read(), |x| x.get(id).map(|x| &**x)).ok()
     }
@@ -1,5 +1,10 @@
-use std::{pin::Pin, ptr::NonNull, sync::atomic::AtomicU8};
+use std::{
+    pin::Pin,
+    ptr::NonNull,
+    sync::{Arc, atomic::AtomicU8},
+};
 
+use binary::{assembly::AssemblyTables, lazy::SharedLazyAssembly};
 use either::Either;
 use global::StringName;
 
@@ -12,7 +17,10 @@ use crate::type_system::{
     generics::{GenericBounds, GenericCountRequirement},
     get_traits::{GetAssemblyRef, GetTypeVars},
     interface::{Interface, InterfaceImplementation},
-    method::{ExceptionTable, ExceptionTableEntry, Method, MethodRef, Parameter},
+    method::{
+        ExceptionTable, ExceptionTableEntry, Method, MethodDebugInfo, MethodRef, Parameter,
+        RuntimeInstruction, SequencePoint,
+    },
     method_table::MethodTable,
     r#struct::Struct,
     type_handle::{GenericUnresolvable, MaybeUnloadedTypeHandle, NonGenericTypeHandle, TypeHandle},
@@ -138,9 +146,42 @@ impl AssemblyManager {
         &self,
         binaries: &[binary::assembly::Assembly],
     ) -> binary::prelude::BinaryResult<()> {
-        let mut loaded_ids = Vec::new();
+        self.load_binaries_with(binaries, None)
+    }
+
+    /// Same as [`Self::load_binaries`], but the instructions of each method are only decoded
+    /// when the method is first invoked
+    pub fn load_lazy_binaries(
+        &self,
+        binaries: Vec<SharedLazyAssembly>,
+    ) -> binary::prelude::BinaryResult<()> {
+        // Kept by the loaded assemblies
+        let lazy_binaries = binaries.into_iter().map(Arc::new).collect::<Vec<_>>();
+        let binaries = lazy_binaries.iter().map(|x| x.get()).collect::<Vec<_>>();
+        self.load_binaries_with(&binaries, Some(&lazy_binaries))
+    }
+
+    fn load_binaries_with<B: AssemblyTables>(
+        &self,
+        binaries: &[B],
+        lazy_binaries: Option<&[Arc<SharedLazyAssembly>]>,
+    ) -> binary::prelude::BinaryResult<()> {
+        let runtime_version = Self::runtime_version();
         for b_assembly in binaries {
-            loaded_ids.push(self.load_binary(b_assembly)?);
+            b_assembly
+                .extra_header()
+                .check_runtime_version(runtime_version)?;
+        }
+        binary::dependency::check_dependencies(
+            binaries,
+            self.loaded_versions(),
+            stdlib_header::CORE_ASSEMBLY_NAME,
+        )?;
+
+        let mut loaded_ids = Vec::new();
+        for (b_assembly_id, b_assembly) in binaries.iter().enumerate() {
+            let lazy_binary = lazy_binaries.map(|x| x[b_assembly_id].clone());
+            loaded_ids.push(self.load_binary_with(b_assembly, lazy_binary)?);
         }
 
         for (b_assembly_id, loaded_id) in loaded_ids.into_iter().enumerate() {
@@ -197,6 +238,7 @@ impl AssemblyManager {
                             }
                         };
 
+                        let class_def = b_assembly.type_def(t_id as _)?.unwrap_class_ref();
                         (*unsafe { NonNull::from_ref(&class.method_table).as_mut() }) =
                             MethodTable::new(NonNull::from_ref(&**class), |mt| {
                                 self.load_binary_methods(
@@ -205,13 +247,13 @@ impl AssemblyManager {
                                     b_assembly,
                                     t_id as _,
                                     mt,
-                                    &b_assembly.type_defs[t_id].unwrap_class_ref().method_table,
+                                    &class_def.method_table,
                                 )
                                 .unwrap()
                             })
                             .as_non_null_ptr();
                         unsafe { NonNull::from_ref(class).as_mut() }
-                            .rediscover_sctor(b_assembly.type_defs[t_id].unwrap_class_ref().sctor);
+                            .rediscover_sctor(class_def.sctor);
 
                         class.load_state.store(
                             TypeLoadState::Finished,
@@ -227,33 +269,54 @@ impl AssemblyManager {
         Ok(())
     }
 
+    /// Same as [`Self::load_binaries`], but nothing is loaded
+    /// if any of the binaries fails [`binary::verifier::verify`]
+    pub fn load_verified_binaries(
+        &self,
+        binaries: &[binary::assembly::Assembly],
+    ) -> binary::prelude::BinaryResult<()> {
+        for b_assembly in binaries {
+            binary::verifier::verify(
+                b_assembly,
+                stdlib_header::CORE_ASSEMBLY_NAME,
+                stdlib_header::CoreTypeId::System_Void as u32,
+            )?;
+        }
+        self.load_binaries(binaries)
+    }
+
     pub fn load_binary(
         &self,
         binary: &binary::assembly::Assembly,
     ) -> binary::binary_core::BinaryResult<usize> {
-        let name = binary.get_string(binary.extra_header.name)?;
-        let id = self.add_assembly(Box::new(Assembly::new(
+        self.load_binary_with(binary, None)
+    }
+
+    fn load_binary_with<B: AssemblyTables + ?Sized>(
+        &self,
+        binary: &B,
+        lazy_binary: Option<Arc<SharedLazyAssembly>>,
+    ) -> binary::binary_core::BinaryResult<usize> {
+        let name = binary.get_string(binary.extra_header().name)?;
+        let mut assembly = Box::new(Assembly::new(
             self,
             widestring::Utf16String::from_str(name),
+            binary.extra_header().version,
             false,
-        )));
+        ));
+        assembly.lazy_binary = lazy_binary;
+        let id = self.add_assembly(assembly);
         let assembly = self.get_assembly(id).unwrap();
-        for (type_id, type_def) in binary.type_defs.iter().enumerate() {
-            match type_def {
+        for type_id in 0..binary.type_def_count() {
+            match binary.type_def(type_id)? {
                 binary::ty::TypeDef::Class(class_def) => {
-                    self.load_binary_class(&assembly, id, binary, class_def, type_id as u32)?;
+                    self.load_binary_class(&assembly, id, binary, class_def, type_id)?;
                 }
                 binary::ty::TypeDef::Struct(struct_def) => {
-                    self.load_binary_struct(&assembly, id, binary, struct_def, type_id as u32)?;
+                    self.load_binary_struct(&assembly, id, binary, struct_def, type_id)?;
                 }
                 binary::ty::TypeDef::Interface(interface_def) => {
-                    self.load_binary_interface(
-                        &assembly,
-                        id,
-                        binary,
-                        interface_def,
-                        type_id as u32,
-                    )?;
+                    self.load_binary_interface(&assembly, id, binary, interface_def, type_id)?;
                 }
             }
         }
@@ -261,11 +324,11 @@ impl AssemblyManager {
         Ok(id)
     }
 
-    fn load_binary_class(
+    fn load_binary_class<B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         class_def: &binary::ty::ClassDef,
         class_id: u32,
     ) -> binary::prelude::BinaryResult<()> {
@@ -363,11 +426,11 @@ impl AssemblyManager {
         Ok(())
     }
 
-    fn load_binary_struct(
+    fn load_binary_struct<B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         struct_def: &binary::ty::StructDef,
         struct_id: u32,
     ) -> binary::binary_core::BinaryResult<()> {
@@ -412,11 +475,11 @@ impl AssemblyManager {
         Ok(())
     }
 
-    fn load_binary_interface(
+    fn load_binary_interface<B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         interface_def: &binary::ty::InterfaceDef,
         interface_id: u32,
     ) -> binary::binary_core::BinaryResult<()> {
@@ -466,11 +529,11 @@ impl AssemblyManager {
         Ok(())
     }
 
-    fn load_binary_field(
+    fn load_binary_field<B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         t_id: u32,
         field: &binary::ty::Field,
     ) -> binary::prelude::BinaryResult<Field> {
@@ -487,33 +550,50 @@ impl AssemblyManager {
         ))
     }
 
-    fn load_binary_methods<T: GetTypeVars + GetAssemblyRef>(
+    fn load_binary_methods<T: GetTypeVars + GetAssemblyRef, B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         t_id: u32,
         mt: NonNull<MethodTable<T>>,
         methods: &[binary::ty::Method],
     ) -> binary::prelude::BinaryResult<Vec<Pin<Box<Method<T>>>>> {
         methods
             .iter()
-            .map(|method| {
-                self.load_binary_method(assembly, assembly_id, b_assembly, t_id, mt, method)
+            .enumerate()
+            .map(|(method_id, method)| {
+                self.load_binary_method(
+                    assembly,
+                    assembly_id,
+                    b_assembly,
+                    t_id,
+                    mt,
+                    method_id as u32,
+                    method,
+                )
             })
             .try_collect()
     }
 
-    fn load_binary_method<T: GetTypeVars + GetAssemblyRef>(
+    fn load_binary_method<T: GetTypeVars + GetAssemblyRef, B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         t_id: u32,
         mt: NonNull<MethodTable<T>>,
+        method_id: u32,
         method: &binary::ty::Method,
     ) -> binary::prelude::BinaryResult<Pin<Box<Method<T>>>> {
         let name = widestring::Utf16String::from_str(b_assembly.get_string(method.name)?);
+        let debug_info = b_assembly
+            .debug_info()
+            .and_then(|x| Some((x, x.get_method(t_id, method_id)?)))
+            .map(|(debug_info, m_debug_info)| {
+                Self::load_binary_method_debug_info(b_assembly, debug_info, m_debug_info)
+            })
+            .transpose()?;
         let attr = method
             .attr
             .clone()
@@ -528,7 +608,7 @@ impl AssemblyManager {
                 .map(GenericCachedTypeReference::from)
             })
             .transpose()?;
-        Ok(Method::try_new(
+        let mut rt_method = Method::try_new(
             mt,
             name,
             attr,
@@ -563,37 +643,17 @@ impl AssemblyManager {
                 t_id,
                 &method.generic_bounds,
             )?,
-            method
-                .instructions
-                .iter()
-                .map(|ins| {
-                    ins.clone()
-                        .map(
-                            |s| b_assembly.get_string(s).map(ToOwned::to_owned),
-                            |tt| {
-                                MaybeUnloadedTypeHandle::from_token_for_type(
-                                    assembly,
-                                    assembly_id,
-                                    b_assembly,
-                                    &tt,
-                                    t_id,
-                                )
-                                .map(GenericCachedTypeReference::new)
-                            },
-                            |tt| {
-                                MethodRef::from_token_for_type(
-                                    assembly,
-                                    assembly_id,
-                                    b_assembly,
-                                    &tt,
-                                    t_id,
-                                )
-                            },
-                            Ok::<_, binary::prelude::Error>,
-                        )
-                        .transpose::<binary::prelude::Error>()
-                })
-                .try_collect()?,
+            match &assembly.lazy_binary {
+                // Set below
+                Some(_) => Vec::new(),
+                None => Self::load_binary_instructions(
+                    assembly,
+                    assembly_id,
+                    b_assembly,
+                    t_id,
+                    &method.instructions,
+                )?,
+            },
             |rt_method| {
                 method
                     .exception_table
@@ -646,14 +706,118 @@ impl AssemblyManager {
                         },
                     )
             },
-        )?)
+        )?;
+        unsafe { rt_method.as_mut().get_unchecked_mut() }.set_debug_info(debug_info);
+        if let Some(lazy_binary) = assembly.lazy_binary.clone() {
+            // The assembly is boxed by the manager, so it outlives its methods
+            let assembly = NonNull::from_ref(assembly);
+            unsafe { rt_method.as_mut().get_unchecked_mut() }.set_instruction_loader(Arc::new(
+                move || {
+                    let lazy_binary = lazy_binary.get();
+                    Self::load_binary_instructions(
+                        unsafe { assembly.as_ref() },
+                        assembly_id,
+                        lazy_binary,
+                        t_id,
+                        &lazy_binary.instructions(t_id, method_id)?,
+                    )
+                },
+            ));
+        }
+        Ok(rt_method)
+    }
+
+    fn load_binary_instructions<B: AssemblyTables + ?Sized>(
+        assembly: &Assembly,
+        assembly_id: usize,
+        b_assembly: &B,
+        t_id: u32,
+        instructions: &[binary::ty::BinaryInstruction],
+    ) -> binary::prelude::BinaryResult<Vec<RuntimeInstruction>> {
+        instructions
+            .iter()
+            .map(|ins| {
+                ins.clone()
+                    .map(
+                        |s| b_assembly.get_string(s).map(ToOwned::to_owned),
+                        |tt| {
+                            MaybeUnloadedTypeHandle::from_token_for_type(
+                                assembly,
+                                assembly_id,
+                                b_assembly,
+                                &tt,
+                                t_id,
+                            )
+                            .map(GenericCachedTypeReference::new)
+                        },
+                        |tt| {
+                            MethodRef::from_token_for_type(
+                                assembly,
+                                assembly_id,
+                                b_assembly,
+                                &tt,
+                                t_id,
+                            )
+                        },
+                        Ok::<_, binary::prelude::Error>,
+                    )
+                    .transpose::<binary::prelude::Error>()
+            })
+            .try_collect()
+    }
+
+    fn load_binary_method_debug_info<B: AssemblyTables + ?Sized>(
+        b_assembly: &B,
+        debug_info: &binary::debug_info::DebugInfo,
+        m_debug_info: &binary::debug_info::MethodDebugInfo,
+    ) -> binary::prelude::BinaryResult<MethodDebugInfo> {
+        if !m_debug_info
+            .sequence_points
+            .is_sorted_by_key(|x| x.instruction)
+        {
+            return Err(binary::prelude::Error::WrongFormat);
+        }
+        let mut documents: Vec<Option<Arc<str>>> = vec![None; debug_info.documents.len()];
+        let sequence_points = m_debug_info
+            .sequence_points
+            .iter()
+            .map(|point| {
+                let document = documents
+                    .get_mut(point.document as usize)
+                    .ok_or(binary::prelude::Error::IndexOutOfRange)?;
+                // Documents are shared by the sequence points
+                if document.is_none() {
+                    *document = Some(Arc::from(b_assembly.get_string(
+                        debug_info.documents[point.document as usize].path,
+                    )?));
+                }
+                let document = document.clone().unwrap();
+                Ok::<_, binary::prelude::Error>(SequencePoint::new(
+                    point.instruction,
+                    document,
+                    point.line,
+                    point.column,
+                ))
+            })
+            .try_collect()?;
+        let names = |names: &[binary::binary_core::traits::StringRef]| {
+            names
+                .iter()
+                .map(|x| b_assembly.get_string(*x).map(ToOwned::to_owned))
+                .try_collect::<Vec<_>>()
+        };
+        Ok(MethodDebugInfo::new(
+            sequence_points,
+            names(&m_debug_info.arg_names)?,
+            names(&m_debug_info.local_names)?,
+        ))
     }
 
-    fn load_binary_generic_bounds(
+    fn load_binary_generic_bounds<B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         t_id: u32,
         generic_bounds: &Option<Vec<binary::ty::GenericBounds>>,
     ) -> binary::prelude::BinaryResult<Option<Vec<GenericBounds>>> {
@@ -675,11 +839,11 @@ impl AssemblyManager {
             .transpose()
     }
 
-    fn load_binary_generic_bound(
+    fn load_binary_generic_bound<B: AssemblyTables + ?Sized>(
         &self,
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         t_id: u32,
         generic_bound: &binary::ty::GenericBounds,
     ) -> binary::prelude::BinaryResult<GenericBounds> {
@@ -724,10 +888,10 @@ impl AssemblyManager {
 }
 
 impl MaybeUnloadedTypeHandle {
-    fn from_token_for_type(
+    fn from_token_for_type<B: AssemblyTables + ?Sized>(
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         tt: &binary::prelude::TypeToken,
         t_id: u32,
     ) -> binary::prelude::BinaryResult<Self> {
@@ -746,7 +910,7 @@ impl MaybeUnloadedTypeHandle {
             }
             binary::prelude::TypeType::TypeRef => {
                 let actual = b_assembly
-                    .type_refs
+                    .type_refs()
                     .get(tt.index() as usize)
                     .ok_or(binary::binary_core::Error::IndexOutOfRange)?;
 
@@ -760,7 +924,7 @@ impl MaybeUnloadedTypeHandle {
             }
             binary::prelude::TypeType::TypeSpec => {
                 let actual = b_assembly
-                    .type_specs
+                    .type_specs()
                     .get(tt.index() as usize)
                     .ok_or(binary::binary_core::Error::IndexOutOfRange)?;
                 let ty = Self::from_token_for_type(
@@ -791,10 +955,10 @@ impl MaybeUnloadedTypeHandle {
 }
 
 impl MethodRef {
-    fn from_token_for_type(
+    fn from_token_for_type<B: AssemblyTables + ?Sized>(
         assembly: &Assembly,
         assembly_id: usize,
-        b_assembly: &binary::assembly::Assembly,
+        b_assembly: &B,
         tt: &binary::prelude::MethodToken,
         t_id: u32,
     ) -> binary::prelude::BinaryResult<Self> {
@@ -802,7 +966,7 @@ impl MethodRef {
             binary::prelude::MethodType::Method => Ok(Self::Index(tt.index())),
             binary::prelude::MethodType::MethodSpec => {
                 let actual = b_assembly
-                    .method_specs
+                    .method_specs()
                     .get(tt.index() as usize)
                     .ok_or(binary::binary_core::Error::IndexOutOfRange)?;
                 let generics = actual
@@ -138,6 +138,20 @@ impl Class {
         }
         self.m_parent.as_ref().map(map)
     }
+    /// Like [`Self::parent`], but a generic parent is instantiated with its generic arguments
+    pub fn instantiated_parent(&self) -> Option<NonNull<Self>> {
+        match unsafe { &*self.m_parent.as_ref()?.loaded } {
+            LoadedClassParent::Simple(class) => Some(*class),
+            parent @ LoadedClassParent::WithGeneric(..) => match parent.instantiate(
+                self.assembly_ref().manager_ref(),
+                self.type_vars.as_deref().unwrap_or_default(),
+            ) {
+                LoadedClassParent::Simple(class) | LoadedClassParent::WithGeneric(class, _) => {
+                    Some(class)
+                }
+            },
+        }
+    }
 }
 
 impl Class {
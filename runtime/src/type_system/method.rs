@@ -1,4 +1,11 @@
-use std::{ffi::c_void, mem::offset_of, ops::RangeBounds, pin::Pin, ptr::NonNull};
+use std::{
+    ffi::c_void,
+    mem::offset_of,
+    ops::RangeBounds,
+    pin::Pin,
+    ptr::NonNull,
+    sync::{Arc, OnceLock},
+};
 
 use global::{
     attrs::{CallConvention, MethodAttr, MethodImplementationFlags},
@@ -25,13 +32,20 @@ use super::{
 };
 
 mod calling;
+mod debug_info;
 mod exception_table;
+mod method_pointer;
 mod parameter;
 
+pub use debug_info::{MethodDebugInfo, SequencePoint};
 pub use exception_table::{ExceptionTable, ExceptionTableEntry};
+pub use method_pointer::MethodPointer;
 pub use parameter::Parameter;
 
 pub type RuntimeInstruction = Instruction<String, GenericCachedTypeReference, MethodRef, u32>;
+/// Decodes the instructions of a method when it is first invoked
+pub type InstructionLoader =
+    Arc<dyn Fn() -> binary::prelude::BinaryResult<Vec<RuntimeInstruction>>>;
 
 #[derive(Getters)]
 #[getset(get = "pub")]
@@ -52,10 +66,16 @@ pub struct Method<T> {
     generic_bounds: Option<NonNull<[GenericBounds]>>,
     type_vars: Option<Box<[NonGenericTypeHandle]>>,
 
-    instructions: Vec<RuntimeInstruction>,
+    #[getset(skip)]
+    instructions: OnceLock<Vec<RuntimeInstruction>>,
+    #[getset(skip)]
+    instruction_loader: Option<InstructionLoader>,
     entry_point: CodePtr,
 
     exception_table: ExceptionTable<T>,
+
+    #[getset(skip)]
+    debug_info: Option<Box<MethodDebugInfo>>,
 }
 
 mod display;
@@ -108,10 +128,13 @@ where
                 .map(|x| Box::into_non_null(x.into_boxed_slice())),
             type_vars: None,
 
-            instructions,
+            instructions: OnceLock::from(instructions),
+            instruction_loader: None,
             entry_point: CodePtr::from_ptr(default_entry_point::__default_entry_point::<T> as _),
 
             exception_table: ExceptionTable::new(NonNull::dangling()),
+
+            debug_info: None,
         });
         this.exception_table = exception_table_generator(&this);
         this
@@ -150,10 +173,13 @@ where
                 .map(|x| Box::into_non_null(x.into_boxed_slice())),
             type_vars: None,
 
-            instructions,
+            instructions: OnceLock::from(instructions),
+            instruction_loader: None,
             entry_point: CodePtr::from_ptr(default_entry_point::__default_entry_point::<T> as _),
 
             exception_table: ExceptionTable::new(NonNull::dangling()),
+
+            debug_info: None,
         });
         this.exception_table = exception_table_generator(&this)?;
         Ok(this)
@@ -206,10 +232,13 @@ impl<T> Method<T> {
                 .map(|x| Box::into_non_null(x.into_boxed_slice())),
             type_vars: None,
 
-            instructions: Vec::new(),
+            instructions: OnceLock::from(Vec::new()),
+            instruction_loader: None,
             entry_point: CodePtr::from_ptr(entry_point),
 
             exception_table: ExceptionTable::new(NonNull::dangling()),
+
+            debug_info: None,
         });
         this.exception_table = exception_table_generator(&this);
         this
@@ -276,6 +305,7 @@ impl<T> Method<T> {
             return_type: self.return_type.clone(),
             call_convention: self.call_convention,
             instructions: self.instructions.clone(),
+            instruction_loader: self.instruction_loader.clone(),
             entry_point: self.entry_point,
 
             generic_instances: Vec::new(),
@@ -283,6 +313,8 @@ impl<T> Method<T> {
             type_vars: Some(Box::clone_from_ref(type_vars)),
 
             exception_table: self.exception_table.clone(),
+
+            debug_info: self.debug_info.clone(),
         });
 
         let mut instantiated = Box::into_non_null(instantiated);
@@ -303,6 +335,36 @@ impl<T> Method<T> {
     }
 }
 
+impl<T> Method<T> {
+    /// Decoded by the instruction loader on the first call if there is one,
+    /// a failed decoding is retried on the next call
+    pub fn instructions(&self) -> binary::prelude::BinaryResult<&Vec<RuntimeInstruction>> {
+        if let Some(instructions) = self.instructions.get() {
+            return Ok(instructions);
+        }
+        let instructions = match &self.instruction_loader {
+            Some(loader) => loader()?,
+            None => Vec::new(),
+        };
+        Ok(self.instructions.get_or_init(|| instructions))
+    }
+    /// The instructions passed when creating the method are replaced by the ones
+    /// returned by `loader`, which is called when they are first needed
+    pub fn set_instruction_loader(&mut self, loader: InstructionLoader) {
+        self.instructions = OnceLock::new();
+        self.instruction_loader = Some(loader);
+    }
+}
+
+impl<T> Method<T> {
+    pub fn debug_info(&self) -> Option<&MethodDebugInfo> {
+        self.debug_info.as_deref()
+    }
+    pub fn set_debug_info(&mut self, debug_info: Option<MethodDebugInfo>) {
+        self.debug_info = debug_info.map(Box::new);
+    }
+}
+
 impl<T> Method<T> {
     pub const fn call_convention(&self) -> CallConvention {
         self.call_convention
@@ -314,6 +376,11 @@ impl<T> Method<T> {
 
 impl<T: GetTypeVars + GetAssemblyRef> Method<T> {
     pub fn get_return_type(&self) -> NonGenericTypeHandle {
+        self.try_get_return_type().unwrap()
+    }
+    /// Returns [`None`] if the return type cannot be resolved,
+    /// e.g. it is a method generic of a method that is not instantiated
+    pub fn try_get_return_type(&self) -> Option<NonGenericTypeHandle> {
         self.return_type
             .get_with_generic_resolver(
                 self.require_method_table_ref()
@@ -321,10 +388,8 @@ impl<T: GetTypeVars + GetAssemblyRef> Method<T> {
                     .__get_assembly_ref()
                     .manager_ref(),
                 MethodGenericResolver::new(self),
-            )
-            .unwrap()
+            )?
             .get_non_generic_with_generic_resolver(MethodGenericResolver::new(self))
-            .unwrap()
     }
     fn libffi_return_type(&self) -> libffi::middle::Type {
         if self
@@ -118,6 +118,27 @@ impl<T: GetTypeVars + GetAssemblyRef + GetNonGenericTypeHandleKind> Method<T> {
     }
 
     pub unsafe fn buffered_call(
+        &self,
+        cpu: &mut CPU,
+        this: Option<NonNull<()>>,
+        args: &[*mut c_void],
+        return_buffer: NonNull<c_void>,
+    ) {
+        unsafe {
+            self.buffered_call_once(cpu, this, args, return_buffer);
+        }
+        // Tail calls are made after the frame of the caller has been popped,
+        // so neither the call stack nor the native stack grows
+        while let Some(tail_call) = cpu.take_tail_call() {
+            unsafe {
+                tail_call.call(cpu, return_buffer);
+            }
+        }
+    }
+
+    /// Same as [`Method::buffered_call`],
+    /// but the tail call requested by the method is left to the caller
+    pub(crate) unsafe fn buffered_call_once(
         &self,
         cpu: &mut CPU,
         this: Option<NonNull<()>>,
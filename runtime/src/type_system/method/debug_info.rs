@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use global::getset::{CopyGetters, Getters};
+
+/// Source-level information of a method, loaded from the debug info section of its assembly
+#[derive(Clone, Debug, Getters)]
+#[getset(get = "pub")]
+pub struct MethodDebugInfo {
+    sequence_points: Vec<SequencePoint>,
+    arg_names: Vec<String>,
+    local_names: Vec<String>,
+}
+
+#[derive(Clone, Debug, Getters, CopyGetters)]
+pub struct SequencePoint {
+    #[getset(get_copy = "pub")]
+    instruction: u64,
+    #[getset(get = "pub")]
+    document: Arc<str>,
+    #[getset(get_copy = "pub")]
+    line: u32,
+    #[getset(get_copy = "pub")]
+    column: u32,
+}
+
+impl MethodDebugInfo {
+    /// `sequence_points` should be sorted by instruction
+    pub fn new(
+        sequence_points: Vec<SequencePoint>,
+        arg_names: Vec<String>,
+        local_names: Vec<String>,
+    ) -> Self {
+        debug_assert!(sequence_points.is_sorted_by_key(|x| x.instruction));
+        Self {
+            sequence_points,
+            arg_names,
+            local_names,
+        }
+    }
+
+    /// The sequence point covering the instruction at `pc`
+    pub fn sequence_point_at(&self, pc: usize) -> Option<&SequencePoint> {
+        let pos = self
+            .sequence_points
+            .partition_point(|x| x.instruction <= pc as u64);
+        pos.checked_sub(1).map(|x| &self.sequence_points[x])
+    }
+}
+
+impl SequencePoint {
+    pub fn new(instruction: u64, document: Arc<str>, line: u32, column: u32) -> Self {
+        Self {
+            instruction,
+            document,
+            line,
+            column,
+        }
+    }
+}
+
+impl std::fmt::Display for SequencePoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.document, self.line)
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
         class::Class,
         get_traits::{GetAssemblyRef, GetTypeVars},
         r#struct::Struct,
-        type_handle::{MaybeUnloadedTypeHandle, NonGenericTypeHandleKind},
+        type_handle::{MaybeUnloadedTypeHandle, NonGenericTypeHandle, NonGenericTypeHandleKind},
     },
     value::managed_reference::ManagedReference,
     virtual_machine::cpu::{CPU, CommonCallStackFrame},
@@ -33,6 +33,15 @@ enum Termination {
     UnimplementedInterface,
     RethrowWithoutExceptions,
     LoadCaughtExceptionWithoutExceptions,
+    /// The calculation is not defined on the type, such as bitwise operations on floats
+    UnsupportedCalculation(&'static str),
+    /// The value of the first type cannot be moved into a register of the second type
+    IncompatibleRegisters(NonGenericTypeHandle, NonGenericTypeHandle),
+    /// The instructions of the method could not be decoded from its assembly
+    LoadInstructionsFailed(binary::prelude::Error),
+    /// The callee of a tail call does not return the type of the caller,
+    /// `None` if the return type of the callee cannot be resolved
+    TailCallReturnTypeMismatch(NonGenericTypeHandle, Option<NonGenericTypeHandle>),
 
     Returned,
     Terminated,
@@ -64,6 +73,14 @@ mod calculate;
 
 mod stack_allocate;
 
+mod convert;
+
+mod cast;
+
+mod array;
+
+mod r#move;
+
 fn eval_throw<T: Sized + GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
     #[allow(unused)] method: &Method<T>,
     #[allow(unused)] cpu: &mut CPU,
@@ -125,7 +142,11 @@ trait Spec: Sized + GetAssemblyRef + GetTypeVars {
         pc: &mut usize,
         caught_exception: Option<ManagedReference<Class>>,
     ) -> Option<Result<(), Termination>> {
-        let Some(ins) = method.instructions.get(*pc) else {
+        let instructions = match method.instructions() {
+            Ok(instructions) => instructions,
+            Err(err) => return Some(Err(Termination::LoadInstructionsFailed(err))),
+        };
+        let Some(ins) = instructions.get(*pc) else {
             return Some(Err(Termination::AllInstructionExecuted));
         };
 
@@ -263,6 +284,70 @@ trait Spec: Sized + GetAssemblyRef + GetTypeVars {
 
             Instruction::StackAllocate(ins) => _eval!(ins by stack_allocate),
             Instruction::SStackAllocate(ins) => _eval!(ins by stack_allocate),
+
+            Instruction::Convert(ins) => _eval!(ins by convert),
+            Instruction::SConvert(ins) => _eval!(ins by convert),
+
+            Instruction::Cast(ins) => _eval!(ins by cast),
+            Instruction::SCast(ins) => _eval!(ins by cast),
+
+            Instruction::Switch(ins) => jump::eval_switch(
+                method,
+                cpu,
+                this,
+                args,
+                result_ptr,
+                pc,
+                caught_exception,
+                ins,
+            ),
+            Instruction::SSwitch(ins) => jump::eval_switch(
+                method,
+                cpu,
+                this,
+                args,
+                result_ptr,
+                pc,
+                caught_exception,
+                ins,
+            ),
+
+            Instruction::Move(ins) => r#move::eval(
+                method,
+                cpu,
+                this,
+                args,
+                result_ptr,
+                pc,
+                caught_exception,
+                ins,
+            ),
+            Instruction::SMove(ins) => r#move::eval(
+                method,
+                cpu,
+                this,
+                args,
+                result_ptr,
+                pc,
+                caught_exception,
+                ins,
+            ),
+
+            Instruction::ZeroInit { target } => {
+                if !call_frame(cpu).zero_register(*target) {
+                    load_register_failed!(*target);
+                }
+                Some(Ok(()))
+            }
+            Instruction::SZeroInit { target } => {
+                if !call_frame(cpu).zero_register(*target) {
+                    load_register_failed!(*target);
+                }
+                Some(Ok(()))
+            }
+
+            Instruction::Array(ins) => _eval!(ins by array),
+            Instruction::SArray(ins) => _eval!(ins by array),
         }
     }
 }
@@ -295,6 +380,9 @@ pub extern "system" fn __default_entry_point<T: GetTypeVars + GetAssemblyRef>(
     return_buffer: NonNull<c_void>,
 ) {
     let mut pc = 0;
+    // The pc of the last executed instruction,
+    // as `pc` has moved past it when an exception is found
+    let mut faulting_pc = 0;
     let mut caught_exception = vec![];
 
     enum RunStatus {
@@ -369,7 +457,7 @@ pub extern "system" fn __default_entry_point<T: GetTypeVars + GetAssemblyRef>(
         }
 
         if cpu.has_exception() {
-            if let Some(handler) = method.exception_table.get_for(pc).find_map(|x| {
+            if let Some(handler) = method.exception_table.get_for(faulting_pc).find_map(|x| {
                 x.get_exception_type(method)
                     .filter(|exception_type| {
                         cpu.is_exception_type_suitable(*exception_type) && {
@@ -432,6 +520,9 @@ pub extern "system" fn __default_entry_point<T: GetTypeVars + GetAssemblyRef>(
                 return;
             }
         }
+        // Kept for the stack traces
+        call_frame(cpu).set_pc(pc);
+        faulting_pc = pc;
         if let Err(t) = T::spec_match_code(
             method,
             cpu,
@@ -486,6 +577,18 @@ pub extern "system" fn __default_entry_point<T: GetTypeVars + GetAssemblyRef>(
                 Termination::LoadCaughtExceptionWithoutExceptions => {
                     t_println!("Load caught exception without exceptions");
                 }
+                Termination::UnsupportedCalculation(ty) => {
+                    t_println!("Unsupported calculation on {ty}");
+                }
+                Termination::IncompatibleRegisters(from, to) => {
+                    t_println!("Cannot move {from:?} into {to:?}");
+                }
+                Termination::LoadInstructionsFailed(err) => {
+                    t_println!("Cannot load the instructions because:\n{err}");
+                }
+                Termination::TailCallReturnTypeMismatch(expected, found) => {
+                    t_println!("Tail call returns {found:?} instead of {expected:?}");
+                }
 
                 Termination::Terminated => {}
                 Termination::Returned => {}
@@ -0,0 +1,107 @@
+use std::{ffi::c_void, ptr::NonNull};
+
+use global::instruction::{IRegisterAddr, Instruction_Array};
+
+use crate::{
+    type_system::{
+        class::Class,
+        get_traits::{GetAssemblyRef, GetTypeVars},
+        method::{
+            Method,
+            default_entry_point::{Termination, call_frame, load_register_failed},
+        },
+    },
+    value::managed_reference::{ArrayAccessor, ManagedReference},
+    virtual_machine::cpu::CPU,
+};
+
+pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
+    #[allow(unused)] method: &Method<T>,
+    #[allow(unused)] cpu: &mut CPU,
+    #[allow(unused)] this: Option<NonNull<()>>,
+    #[allow(unused)] args: &[*mut c_void],
+    #[allow(unused)] result_ptr: NonNull<c_void>,
+    #[allow(unused)] pc: &mut usize,
+    #[allow(unused)] caught_exception: Option<ManagedReference<Class>>,
+    ins: &Instruction_Array<TRegisterAddr>,
+) -> Option<Result<(), Termination>> {
+    let (Instruction_Array::LoadElement { array, .. }
+    | Instruction_Array::StoreElement { array, .. }
+    | Instruction_Array::LoadElementAddress { array, .. }
+    | Instruction_Array::ArrayLength { array, .. }) = ins;
+
+    let Some(mut array) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*array) else {
+        load_register_failed!(*array);
+    };
+    if array.is_null() {
+        if !cpu.throw_helper_mut().null_reference() {
+            return Some(Err(Termination::NewObjectFailed));
+        }
+        return Some(Ok(()));
+    }
+    let Some(accessor) = array.access_mut::<ArrayAccessor>() else {
+        if !cpu.throw_helper_mut().invalid_cast() {
+            return Some(Err(Termination::NewObjectFailed));
+        }
+        return Some(Ok(()));
+    };
+    let len = unsafe { accessor.len_unchecked() };
+
+    let index = match ins {
+        Instruction_Array::ArrayLength { output, .. } => {
+            if !call_frame(cpu).write_typed(*output, len) {
+                load_register_failed!(*output);
+            }
+            return Some(Ok(()));
+        }
+        Instruction_Array::LoadElement { index, .. }
+        | Instruction_Array::StoreElement { index, .. }
+        | Instruction_Array::LoadElementAddress { index, .. } => index,
+    };
+    let Some(index) = call_frame(cpu).read_typed::<usize, _>(*index) else {
+        load_register_failed!(*index);
+    };
+    if index >= len {
+        if !cpu.throw_helper_mut().index_out_of_range(index, len) {
+            return Some(Err(Termination::NewObjectFailed));
+        }
+        return Some(Ok(()));
+    }
+    let element_ty = unsafe { accessor.element_type_handle_unchecked() };
+    let element = unsafe { accessor.get_mut_unchecked(index) };
+    let element_size = element.len();
+    let element_ptr = NonNull::from_mut(element).cast::<u8>();
+
+    match ins {
+        Instruction_Array::LoadElement { output, .. } => {
+            let Some(output) = call_frame(cpu).get(*output) else {
+                load_register_failed!(*output);
+            };
+            if output.layout.size() < element_size {
+                return Some(Err(Termination::IncompatibleRegisters(element_ty, output.ty)));
+            }
+            unsafe {
+                output.copy_from(element_ptr, element_size);
+            }
+        }
+        Instruction_Array::StoreElement { value, .. } => {
+            let Some(value) = call_frame(cpu).get(*value) else {
+                load_register_failed!(*value);
+            };
+            if value.layout.size() < element_size {
+                return Some(Err(Termination::IncompatibleRegisters(value.ty, element_ty)));
+            }
+            unsafe {
+                value.copy_to(element_ptr, element_size);
+            }
+        }
+        Instruction_Array::LoadElementAddress { output, .. } => {
+            if !call_frame(cpu).write_typed(*output, element_ptr) {
+                load_register_failed!(*output);
+            }
+        }
+        Instruction_Array::ArrayLength { .. } => unreachable!(),
+    }
+
+    Some(Ok(()))
+}
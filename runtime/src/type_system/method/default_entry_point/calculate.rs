@@ -1,10 +1,8 @@
-use std::{
-    ffi::c_void,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign},
-    ptr::NonNull,
-};
+use std::{ffi::c_void, ptr::NonNull};
 
-use global::instruction::{IRegisterAddr, Instruction_Calculate, Instruction_UntypedCalculate};
+use global::instruction::{
+    ArithmeticOp, IRegisterAddr, Instruction_Calculate, Instruction_UntypedCalculate, OverflowMode,
+};
 
 use crate::{
     type_system::{
@@ -55,22 +53,17 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
         Instruction_Calculate::I64(ins) => {
             eval_untyped(method, cpu, this, args, result_ptr, pc, ins)
         }
+
+        Instruction_Calculate::F32(ins) => {
+            eval_untyped(method, cpu, this, args, result_ptr, pc, ins)
+        }
+        Instruction_Calculate::F64(ins) => {
+            eval_untyped(method, cpu, this, args, result_ptr, pc, ins)
+        }
     }
 }
 
-trait ConstsForEval:
-    Copy
-    + Add<Output = Self>
-    + Sub<Output = Self>
-    + Mul<Output = Self>
-    + Div<Output = Self>
-    + Rem<Output = Self>
-    + AddAssign
-    + SubAssign
-    + MulAssign
-    + DivAssign
-    + RemAssign
-{
+trait ConstsForEval: Copy {
     const ONE: Self;
 }
 
@@ -82,11 +75,202 @@ macro impl_consts_for_eval($($i:ty),* $(,)?) {$(
 
 impl_consts_for_eval!(u8, u16, u32, u64, i8, i16, i32, i64);
 
+impl ConstsForEval for f32 {
+    const ONE: Self = 1.0;
+}
+impl ConstsForEval for f64 {
+    const ONE: Self = 1.0;
+}
+
+enum ArithmeticError {
+    DivideByZero,
+    Overflow,
+}
+
+trait ArithmeticForEval: Copy {
+    fn arithmetic(
+        op: ArithmeticOp,
+        mode: OverflowMode,
+        lhs: Self,
+        rhs: Self,
+    ) -> Result<Self, ArithmeticError>;
+}
+
+macro impl_arithmetic_for_eval($($i:ty),* $(,)?) {$(
+	impl ArithmeticForEval for $i {
+		fn arithmetic(
+			op: ArithmeticOp,
+			mode: OverflowMode,
+			lhs: Self,
+			rhs: Self,
+		) -> Result<Self, ArithmeticError> {
+			if matches!(op, ArithmeticOp::Div | ArithmeticOp::Rem) && rhs == 0 {
+				return Err(ArithmeticError::DivideByZero);
+			}
+			let res = match (op, mode) {
+				(ArithmeticOp::Add, OverflowMode::Wrapping) => Some(lhs.wrapping_add(rhs)),
+				(ArithmeticOp::Add, OverflowMode::Checked) => lhs.checked_add(rhs),
+				(ArithmeticOp::Add, OverflowMode::Saturating) => Some(lhs.saturating_add(rhs)),
+
+				(ArithmeticOp::Sub, OverflowMode::Wrapping) => Some(lhs.wrapping_sub(rhs)),
+				(ArithmeticOp::Sub, OverflowMode::Checked) => lhs.checked_sub(rhs),
+				(ArithmeticOp::Sub, OverflowMode::Saturating) => Some(lhs.saturating_sub(rhs)),
+
+				(ArithmeticOp::Mul, OverflowMode::Wrapping) => Some(lhs.wrapping_mul(rhs)),
+				(ArithmeticOp::Mul, OverflowMode::Checked) => lhs.checked_mul(rhs),
+				(ArithmeticOp::Mul, OverflowMode::Saturating) => Some(lhs.saturating_mul(rhs)),
+
+				(ArithmeticOp::Div, OverflowMode::Wrapping) => Some(lhs.wrapping_div(rhs)),
+				(ArithmeticOp::Div, OverflowMode::Checked) => lhs.checked_div(rhs),
+				(ArithmeticOp::Div, OverflowMode::Saturating) => Some(lhs.saturating_div(rhs)),
+
+				// The only overflowing case is `MIN % -1`, whose mathematical result 0 is what
+				// `wrapping_rem` returns
+				(ArithmeticOp::Rem, OverflowMode::Wrapping | OverflowMode::Saturating) => {
+					Some(lhs.wrapping_rem(rhs))
+				}
+				(ArithmeticOp::Rem, OverflowMode::Checked) => lhs.checked_rem(rhs),
+			};
+			res.ok_or(ArithmeticError::Overflow)
+		}
+	}
+)*}
+
+impl_arithmetic_for_eval!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+macro impl_float_arithmetic_for_eval($($i:ty),* $(,)?) {$(
+	impl ArithmeticForEval for $i {
+		fn arithmetic(
+			op: ArithmeticOp,
+			_: OverflowMode,
+			lhs: Self,
+			rhs: Self,
+		) -> Result<Self, ArithmeticError> {
+			Ok(match op {
+				ArithmeticOp::Add => lhs + rhs,
+				ArithmeticOp::Sub => lhs - rhs,
+				ArithmeticOp::Mul => lhs * rhs,
+				ArithmeticOp::Div => lhs / rhs,
+				ArithmeticOp::Rem => lhs % rhs,
+			})
+		}
+	}
+)*}
+
+impl_float_arithmetic_for_eval!(f32, f64);
+
+/// Bitwise operations are only defined on integers,
+/// so floating-point types return [`None`] for all of them
+trait BitwiseForEval: Copy {
+    fn bit_and(self, rhs: Self) -> Option<Self>;
+    fn bit_or(self, rhs: Self) -> Option<Self>;
+    fn bit_xor(self, rhs: Self) -> Option<Self>;
+    fn bit_not(self) -> Option<Self>;
+
+    /// Converts a value read from a register to a shift amount
+    fn to_shift_amount(self) -> Option<u32>;
+
+    fn shift_left(self, amount: u32) -> Option<Self>;
+    /// Logical right shift
+    fn shift_right(self, amount: u32) -> Option<Self>;
+    /// Arithmetic right shift
+    fn shift_right_arithmetic(self, amount: u32) -> Option<Self>;
+    fn rotate_left(self, amount: u32) -> Option<Self>;
+    fn rotate_right(self, amount: u32) -> Option<Self>;
+}
+
+macro impl_bitwise_for_eval($($i:ty => ($unsigned:ty, $signed:ty)),* $(,)?) {$(
+	impl BitwiseForEval for $i {
+		fn bit_and(self, rhs: Self) -> Option<Self> {
+			Some(self & rhs)
+		}
+		fn bit_or(self, rhs: Self) -> Option<Self> {
+			Some(self | rhs)
+		}
+		fn bit_xor(self, rhs: Self) -> Option<Self> {
+			Some(self ^ rhs)
+		}
+		fn bit_not(self) -> Option<Self> {
+			Some(!self)
+		}
+
+		fn to_shift_amount(self) -> Option<u32> {
+			Some(self as u32)
+		}
+
+		fn shift_left(self, amount: u32) -> Option<Self> {
+			Some(self.wrapping_shl(amount))
+		}
+		fn shift_right(self, amount: u32) -> Option<Self> {
+			Some((self as $unsigned).wrapping_shr(amount) as Self)
+		}
+		fn shift_right_arithmetic(self, amount: u32) -> Option<Self> {
+			Some((self as $signed).wrapping_shr(amount) as Self)
+		}
+		fn rotate_left(self, amount: u32) -> Option<Self> {
+			Some(<$i>::rotate_left(self, amount))
+		}
+		fn rotate_right(self, amount: u32) -> Option<Self> {
+			Some(<$i>::rotate_right(self, amount))
+		}
+	}
+)*}
+
+impl_bitwise_for_eval!(
+    u8 => (u8, i8),
+    u16 => (u16, i16),
+    u32 => (u32, i32),
+    u64 => (u64, i64),
+    i8 => (u8, i8),
+    i16 => (u16, i16),
+    i32 => (u32, i32),
+    i64 => (u64, i64),
+);
+
+macro impl_no_bitwise_for_eval($($i:ty),* $(,)?) {$(
+	impl BitwiseForEval for $i {
+		fn bit_and(self, _: Self) -> Option<Self> {
+			None
+		}
+		fn bit_or(self, _: Self) -> Option<Self> {
+			None
+		}
+		fn bit_xor(self, _: Self) -> Option<Self> {
+			None
+		}
+		fn bit_not(self) -> Option<Self> {
+			None
+		}
+
+		fn to_shift_amount(self) -> Option<u32> {
+			None
+		}
+
+		fn shift_left(self, _: u32) -> Option<Self> {
+			None
+		}
+		fn shift_right(self, _: u32) -> Option<Self> {
+			None
+		}
+		fn shift_right_arithmetic(self, _: u32) -> Option<Self> {
+			None
+		}
+		fn rotate_left(self, _: u32) -> Option<Self> {
+			None
+		}
+		fn rotate_right(self, _: u32) -> Option<Self> {
+			None
+		}
+	}
+)*}
+
+impl_no_bitwise_for_eval!(f32, f64);
+
 #[allow(private_bounds)]
 pub(super) fn eval_untyped<
     T: Sized + GetAssemblyRef + GetTypeVars,
     TRegisterAddr: IRegisterAddr,
-    TRust: ConstsForEval,
+    TRust: ConstsForEval + ArithmeticForEval + BitwiseForEval,
 >(
     #[allow(unused)] method: &Method<T>,
     #[allow(unused)] cpu: &mut CPU,
@@ -111,79 +295,241 @@ pub(super) fn eval_untyped<
             load_register_failed!(*$i);
         };
     }
+    macro arithmetic($op:expr, $mode:expr, $lhs:expr, $rhs:expr) {
+        match TRust::arithmetic($op, $mode, $lhs, $rhs) {
+            Ok(x) => x,
+            Err(err) => {
+                let thrown = match err {
+                    ArithmeticError::DivideByZero => cpu.throw_helper_mut().divide_by_zero(),
+                    ArithmeticError::Overflow => cpu.throw_helper_mut().overflow(),
+                };
+                if !thrown {
+                    return Some(Err(Termination::NewObjectFailed));
+                }
+                return Some(Ok(()));
+            }
+        }
+    }
+    macro bitwise($e:expr) {
+        match $e {
+            Some(x) => x,
+            None => {
+                return Some(Err(Termination::UnsupportedCalculation(
+                    std::any::type_name::<TRust>(),
+                )));
+            }
+        }
+    }
     match ins {
         Instruction_UntypedCalculate::Add { lhs, rhs, target } => {
             read!(lhs = (TRust)lhs);
             read!(rhs = (TRust)rhs);
-            let res = lhs + rhs;
+            let res = arithmetic!(ArithmeticOp::Add, OverflowMode::Wrapping, lhs, rhs);
             write_reg!(target = res);
         }
         Instruction_UntypedCalculate::Sub { lhs, rhs, target } => {
             read!(lhs = (TRust)lhs);
             read!(rhs = (TRust)rhs);
-            let res = lhs - rhs;
+            let res = arithmetic!(ArithmeticOp::Sub, OverflowMode::Wrapping, lhs, rhs);
             write_reg!(target = res);
         }
         Instruction_UntypedCalculate::Mul { lhs, rhs, target } => {
             read!(lhs = (TRust)lhs);
             read!(rhs = (TRust)rhs);
-            let res = lhs * rhs;
+            let res = arithmetic!(ArithmeticOp::Mul, OverflowMode::Wrapping, lhs, rhs);
             write_reg!(target = res);
         }
         Instruction_UntypedCalculate::Div { lhs, rhs, target } => {
             read!(lhs = (TRust)lhs);
             read!(rhs = (TRust)rhs);
-            let res = lhs / rhs;
+            let res = arithmetic!(ArithmeticOp::Div, OverflowMode::Wrapping, lhs, rhs);
             write_reg!(target = res);
         }
         Instruction_UntypedCalculate::Rem { lhs, rhs, target } => {
             read!(lhs = (TRust)lhs);
             read!(rhs = (TRust)rhs);
-            let res = lhs % rhs;
+            let res = arithmetic!(ArithmeticOp::Rem, OverflowMode::Wrapping, lhs, rhs);
             write_reg!(target = res);
         }
 
         Instruction_UntypedCalculate::ConstAddTo { target, data } => {
-            get!(target = (TRust)target);
-            target.add_assign(*data);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Add, OverflowMode::Wrapping, value, *data);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::ConstSubTo { target, data } => {
-            get!(target = (TRust)target);
-            target.sub_assign(*data);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Sub, OverflowMode::Wrapping, value, *data);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::ConstMulTo { target, data } => {
-            get!(target = (TRust)target);
-            target.mul_assign(*data);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Mul, OverflowMode::Wrapping, value, *data);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::ConstDivTo { target, data } => {
-            get!(target = (TRust)target);
-            target.div_assign(*data);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Div, OverflowMode::Wrapping, value, *data);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::ConstRemTo { target, data } => {
-            get!(target = (TRust)target);
-            target.rem_assign(*data);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Rem, OverflowMode::Wrapping, value, *data);
+            write_reg!(target = res);
         }
 
         Instruction_UntypedCalculate::SubByConst { target, data } => {
-            get!(target = (TRust)target);
-            *target = (*data).sub(*target);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Sub, OverflowMode::Wrapping, *data, value);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::DivByConst { target, data } => {
-            get!(target = (TRust)target);
-            *target = (*data).div(*target);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Div, OverflowMode::Wrapping, *data, value);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::RemByConst { target, data } => {
-            get!(target = (TRust)target);
-            *target = (*data).rem(*target);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Rem, OverflowMode::Wrapping, *data, value);
+            write_reg!(target = res);
         }
 
         Instruction_UntypedCalculate::AddOne { target } => {
-            get!(target = (TRust)target);
-            target.add_assign(TRust::ONE);
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Add, OverflowMode::Wrapping, value, TRust::ONE);
+            write_reg!(target = res);
         }
         Instruction_UntypedCalculate::SubOne { target } => {
+            read!(value = (TRust)target);
+            let res = arithmetic!(ArithmeticOp::Sub, OverflowMode::Wrapping, value, TRust::ONE);
+            write_reg!(target = res);
+        }
+
+        Instruction_UntypedCalculate::And { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let res = bitwise!(lhs.bit_and(rhs));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::Or { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let res = bitwise!(lhs.bit_or(rhs));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::Xor { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let res = bitwise!(lhs.bit_xor(rhs));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::Not { source, target } => {
+            read!(source = (TRust)source);
+            let res = bitwise!(source.bit_not());
+            write_reg!(target = res);
+        }
+
+        Instruction_UntypedCalculate::Shl { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let amount = bitwise!(rhs.to_shift_amount());
+            let res = bitwise!(lhs.shift_left(amount));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::Shr { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let amount = bitwise!(rhs.to_shift_amount());
+            let res = bitwise!(lhs.shift_right(amount));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::Sar { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let amount = bitwise!(rhs.to_shift_amount());
+            let res = bitwise!(lhs.shift_right_arithmetic(amount));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::RotateLeft { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let amount = bitwise!(rhs.to_shift_amount());
+            let res = bitwise!(lhs.rotate_left(amount));
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::RotateRight { lhs, rhs, target } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let amount = bitwise!(rhs.to_shift_amount());
+            let res = bitwise!(lhs.rotate_right(amount));
+            write_reg!(target = res);
+        }
+
+        Instruction_UntypedCalculate::ConstAndTo { target, data } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).bit_and(*data));
+        }
+        Instruction_UntypedCalculate::ConstOrTo { target, data } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).bit_or(*data));
+        }
+        Instruction_UntypedCalculate::ConstXorTo { target, data } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).bit_xor(*data));
+        }
+
+        Instruction_UntypedCalculate::ConstShlTo { target, amount } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).shift_left(*amount));
+        }
+        Instruction_UntypedCalculate::ConstShrTo { target, amount } => {
             get!(target = (TRust)target);
-            target.sub_assign(TRust::ONE);
+            *target = bitwise!((*target).shift_right(*amount));
+        }
+        Instruction_UntypedCalculate::ConstSarTo { target, amount } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).shift_right_arithmetic(*amount));
+        }
+        Instruction_UntypedCalculate::ConstRotateLeftTo { target, amount } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).rotate_left(*amount));
+        }
+        Instruction_UntypedCalculate::ConstRotateRightTo { target, amount } => {
+            get!(target = (TRust)target);
+            *target = bitwise!((*target).rotate_right(*amount));
+        }
+
+        Instruction_UntypedCalculate::Arithmetic {
+            op,
+            mode,
+            lhs,
+            rhs,
+            target,
+        } => {
+            read!(lhs = (TRust)lhs);
+            read!(rhs = (TRust)rhs);
+            let res = arithmetic!(*op, *mode, lhs, rhs);
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::ConstArithmeticTo {
+            op,
+            mode,
+            target,
+            data,
+        } => {
+            read!(value = (TRust)target);
+            let res = arithmetic!(*op, *mode, value, *data);
+            write_reg!(target = res);
+        }
+        Instruction_UntypedCalculate::ArithmeticByConst {
+            op,
+            mode,
+            target,
+            data,
+        } => {
+            read!(value = (TRust)target);
+            let res = arithmetic!(*op, *mode, *data, value);
+            write_reg!(target = res);
         }
     }
 
@@ -1,4 +1,8 @@
-use std::{alloc::Layout, ffi::c_void, ptr::NonNull};
+use std::{
+    alloc::{AllocError, Layout},
+    ffi::c_void,
+    ptr::NonNull,
+};
 
 use global::instruction::{IRegisterAddr, Instruction_Call};
 
@@ -8,13 +12,13 @@ use crate::{
         class::Class,
         get_traits::{GetAssemblyRef, GetTypeVars},
         method::{
-            Method, MethodRef,
+            Method, MethodPointer, MethodRef,
             default_entry_point::{Termination, call_frame, load_register_failed},
         },
-        type_handle::{MethodGenericResolver, NonGenericTypeHandle},
+        type_handle::MethodGenericResolver,
     },
     value::managed_reference::ManagedReference,
-    virtual_machine::cpu::CPU,
+    virtual_machine::cpu::{CPU, TailCall},
 };
 
 pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
@@ -49,14 +53,9 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
             let Some(val) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*val) else {
                 load_register_failed!(*val);
             };
-            if val.is_null() {
-                return Some(Err(Termination::NullReference(
-                    core::panic::Location::caller(),
-                )));
-            }
-            let mt = val.method_table_ref().unwrap();
-            let Some(m) = mt.get_method_by_ref(method) else {
-                return Some(Err(Termination::LoadMethodFailed(method.clone())));
+            let m = match resolve_instance_method(&val, method) {
+                Ok(m) => m,
+                Err(e) => return Some(Err(e)),
             };
 
             let m_ref = unsafe { m.as_ref() };
@@ -82,16 +81,9 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
             args,
             ret_at,
         } => {
-            let Some(ty) = ty
-                .get_with_generic_resolver(
-                    cpu.vm_ref().assembly_manager(),
-                    MethodGenericResolver::new(method),
-                )
-                .and_then(|x| x.get_non_generic_with_method(method))
-            else {
-                return Some(Err(Termination::LoadTypeHandleFailed(
-                    ty.to_maybe_unloaded_handle(),
-                )));
+            let f_pointer = match resolve_static_method(method, cpu, ty, m_target) {
+                Ok(x) => x,
+                Err(e) => return Some(Err(e)),
             };
 
             let args = args
@@ -106,27 +98,8 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
                 })
                 .collect::<Vec<_>>();
 
-            macro apply($n:ident $b:block) {
-                match ty {
-                    NonGenericTypeHandle::Class($n) => $b,
-                    NonGenericTypeHandle::Struct($n) => $b,
-                    NonGenericTypeHandle::Interface(_) => unreachable!(),
-                }
-            }
-
-            let actual_layout;
-
-            let (ret_ptr, ret_layout) = apply! {
-                t {
-                    let t_ref = unsafe { t.as_ref() };
-                    let mt = t_ref.method_table_ref();
-                    let m = mt.get_method_by_ref(m_target).unwrap();
-                    let m_ref = unsafe { m.as_ref() };
-                    actual_layout = m_ref.get_return_type().val_layout();
-
-                    m_ref.untyped_call(cpu, None, &args)
-                }
-            };
+            let (ret_ptr, ret_layout, actual_layout) =
+                f_pointer.untyped_call(cpu, None, &args).unwrap();
 
             if actual_layout != Layout::new::<()>() {
                 let Some(out_var) = call_frame(cpu).get(*ret_at) else {
@@ -147,60 +120,13 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
             args,
             ret_at,
         } => {
-            let Some(interface) = interface
-                .get_with_generic_resolver(
-                    method
-                        .require_method_table_ref()
-                        .ty_ref()
-                        .__get_assembly_ref()
-                        .manager_ref(),
-                    MethodGenericResolver::new(method),
-                )
-                .and_then(|x| x.get_non_generic_with_method(method))
-            else {
-                return Some(Err(Termination::LoadTypeHandleFailed(
-                    interface.to_maybe_unloaded_handle(),
-                )));
-            };
-            let interface = interface.unwrap_interface();
             let Some(val) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*val) else {
                 load_register_failed!(*val);
             };
-            let Some(val_mt) = val.method_table_ref() else {
-                return Some(Err(Termination::NullReference(
-                    std::panic::Location::caller(),
-                )));
+            let m = match resolve_interface_method(method, interface, &val, method_target) {
+                Ok(m) => m,
+                Err(e) => return Some(Err(e)),
             };
-            let implementation =
-                match val_mt
-                    .ty_ref()
-                    .implemented_interfaces()
-                    .iter()
-                    .try_find(|x| {
-                        let Some(target) = x
-                            .target
-                            .load_with_generic_resolver(
-                                method
-                                    .require_method_table_ref()
-                                    .ty_ref()
-                                    .__get_assembly_ref()
-                                    .manager_ref(),
-                                MethodGenericResolver::new(method),
-                            )
-                            .map(|x| x.get_non_generic_with_method(method))
-                            .flatten()
-                        else {
-                            return Err(Termination::LoadTypeHandleFailed(x.target.clone()));
-                        };
-                        let target = target.unwrap_interface();
-                        Ok(std::ptr::addr_eq(interface.as_ptr(), target.as_ptr()))
-                    }) {
-                    Ok(Some(x)) => x,
-                    Ok(None) => return Some(Err(Termination::UnimplementedInterface)),
-                    Err(e) => return Some(Err(e)),
-                };
-            let method_target =
-                method_target.cloned_map_index(|x| *implementation.map.get(x as usize).unwrap());
 
             let args = args
                 .iter()
@@ -214,11 +140,6 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
                 })
                 .collect::<Vec<_>>();
 
-            let mt = val.method_table_ref().unwrap();
-            let Some(m) = mt.get_method_by_ref(&method_target) else {
-                return Some(Err(Termination::LoadMethodFailed(method_target)));
-            };
-
             let m_ref = unsafe { m.as_ref() };
             let actual_layout = m_ref.get_return_type().val_layout();
             let (ret_ptr, ret_layout) =
@@ -236,6 +157,72 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
                 std::alloc::Allocator::deallocate(&std::alloc::Global, ret_ptr, ret_layout);
             }
         }
+        Instruction_Call::TailInstanceCall { val, method, args } => {
+            let Some(val) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*val) else {
+                load_register_failed!(*val);
+            };
+            let m = match resolve_instance_method(&val, method) {
+                Ok(m) => m,
+                Err(e) => return Some(Err(e)),
+            };
+            let tail_call = unsafe {
+                TailCall::new(
+                    MethodPointer::new(m.as_ref()),
+                    Some(val),
+                    args.iter().map(|x| {
+                        let var = call_frame(cpu).get(*x).unwrap();
+                        (var.ptr, var.layout)
+                    }),
+                )
+            };
+            return request_tail_call(method, cpu, result_ptr, *pc, tail_call);
+        }
+        Instruction_Call::TailStaticCall {
+            ty,
+            method: m_target,
+            args,
+        } => {
+            let f_pointer = match resolve_static_method(method, cpu, ty, m_target) {
+                Ok(x) => x,
+                Err(e) => return Some(Err(e)),
+            };
+            let tail_call = unsafe {
+                TailCall::new(
+                    f_pointer,
+                    None,
+                    args.iter().map(|x| {
+                        let var = call_frame(cpu).get(*x).unwrap();
+                        (var.ptr, var.layout)
+                    }),
+                )
+            };
+            return request_tail_call(method, cpu, result_ptr, *pc, tail_call);
+        }
+        Instruction_Call::TailInterfaceCall {
+            interface,
+            val,
+            method: method_target,
+            args,
+        } => {
+            let Some(val) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*val) else {
+                load_register_failed!(*val);
+            };
+            let m = match resolve_interface_method(method, interface, &val, method_target) {
+                Ok(m) => m,
+                Err(e) => return Some(Err(e)),
+            };
+            let tail_call = unsafe {
+                TailCall::new(
+                    MethodPointer::new(m.as_ref()),
+                    Some(val),
+                    args.iter().map(|x| {
+                        let var = call_frame(cpu).get(*x).unwrap();
+                        (var.ptr, var.layout)
+                    }),
+                )
+            };
+            return request_tail_call(method, cpu, result_ptr, *pc, tail_call);
+        }
         Instruction_Call::StaticNonPurusCall {
             f_pointer,
             config,
@@ -290,7 +277,194 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
                 std::alloc::Allocator::deallocate(&std::alloc::Global, ret_ptr, ret_layout);
             }
         }
+        Instruction_Call::IndirectCall {
+            f_pointer,
+            args,
+            ret_at,
+        } => {
+            let Some(f_pointer) = call_frame(cpu).read_typed::<MethodPointer, _>(*f_pointer) else {
+                load_register_failed!(*f_pointer);
+            };
+            let Some(is_static) = f_pointer.is_static() else {
+                return Some(Err(Termination::NullReference(
+                    core::panic::Location::caller(),
+                )));
+            };
+
+            let mut args = args
+                .iter()
+                .map(|x| {
+                    call_frame(cpu)
+                        .get(*x)
+                        .unwrap()
+                        .ptr()
+                        .cast::<c_void>()
+                        .as_ptr()
+                })
+                .collect::<Vec<_>>();
+            let this = if is_static {
+                None
+            } else if args.is_empty() {
+                return Some(Err(Termination::NullReference(
+                    core::panic::Location::caller(),
+                )));
+            } else {
+                NonNull::new(args.remove(0).cast::<()>())
+            };
+
+            let (ret_ptr, ret_layout, actual_layout) =
+                f_pointer.untyped_call(cpu, this, &args).unwrap();
+
+            if actual_layout != Layout::new::<()>() {
+                let Some(out_var) = call_frame(cpu).get(*ret_at) else {
+                    load_register_failed!(*ret_at);
+                };
+                unsafe {
+                    out_var.copy_from(ret_ptr, actual_layout.size());
+                }
+            }
+            unsafe {
+                std::alloc::Allocator::deallocate(&std::alloc::Global, ret_ptr, ret_layout);
+            }
+        }
     }
 
     Some(Ok(()))
 }
+
+/// Throws if the args of the tail call could not be copied
+///
+/// The callee writes into the return buffer of the caller, so it must return the same type.
+/// Inside a try range the call is made as a normal one, so that the handlers of the caller still run.
+fn request_tail_call<T: GetAssemblyRef + GetTypeVars>(
+    method: &Method<T>,
+    cpu: &mut CPU,
+    result_ptr: NonNull<c_void>,
+    pc: usize,
+    tail_call: Result<TailCall, AllocError>,
+) -> Option<Result<(), Termination>> {
+    let tail_call = match tail_call {
+        Ok(tail_call) => tail_call,
+        Err(AllocError) => {
+            if !cpu.throw_helper_mut().alloc() {
+                return Some(Err(Termination::NewObjectFailed));
+            }
+            return Some(Ok(()));
+        }
+    };
+    let expected = method.get_return_type();
+    let found = tail_call.return_type();
+    if found != Some(expected) {
+        return Some(Err(Termination::TailCallReturnTypeMismatch(
+            expected, found,
+        )));
+    }
+    if method.exception_table.get_for(pc).next().is_none() {
+        cpu.set_tail_call(tail_call);
+        return Some(Err(Termination::Returned));
+    }
+
+    unsafe {
+        tail_call.call(cpu, result_ptr);
+    }
+    while let Some(tail_call) = cpu.take_tail_call() {
+        unsafe {
+            tail_call.call(cpu, result_ptr);
+        }
+    }
+    if cpu.has_exception() {
+        return Some(Ok(()));
+    }
+    Some(Err(Termination::Returned))
+}
+
+#[track_caller]
+fn resolve_instance_method(
+    val: &ManagedReference<Class>,
+    method: &MethodRef,
+) -> Result<NonNull<Method<Class>>, Termination> {
+    let Some(mt) = val.class_method_table_ref() else {
+        return Err(Termination::NullReference(core::panic::Location::caller()));
+    };
+    mt.get_method_by_ref(method)
+        .ok_or_else(|| Termination::LoadMethodFailed(method.clone()))
+}
+
+fn resolve_static_method<T: GetAssemblyRef + GetTypeVars>(
+    method: &Method<T>,
+    cpu: &CPU,
+    ty: &GenericCachedTypeReference,
+    m_target: &MethodRef,
+) -> Result<MethodPointer, Termination> {
+    let Some(ty) = ty
+        .get_with_generic_resolver(
+            cpu.vm_ref().assembly_manager(),
+            MethodGenericResolver::new(method),
+        )
+        .and_then(|x| x.get_non_generic_with_method(method))
+    else {
+        return Err(Termination::LoadTypeHandleFailed(
+            ty.to_maybe_unloaded_handle(),
+        ));
+    };
+    MethodPointer::from_method_ref(ty, m_target)
+        .ok_or_else(|| Termination::LoadMethodFailed(m_target.clone()))
+}
+
+#[track_caller]
+fn resolve_interface_method<T: GetAssemblyRef + GetTypeVars>(
+    method: &Method<T>,
+    interface: &GenericCachedTypeReference,
+    val: &ManagedReference<Class>,
+    method_target: &MethodRef,
+) -> Result<NonNull<Method<Class>>, Termination> {
+    let Some(interface) = interface
+        .get_with_generic_resolver(
+            method
+                .require_method_table_ref()
+                .ty_ref()
+                .__get_assembly_ref()
+                .manager_ref(),
+            MethodGenericResolver::new(method),
+        )
+        .and_then(|x| x.get_non_generic_with_method(method))
+    else {
+        return Err(Termination::LoadTypeHandleFailed(
+            interface.to_maybe_unloaded_handle(),
+        ));
+    };
+    let interface = interface.unwrap_interface();
+    let Some(val_mt) = val.class_method_table_ref() else {
+        return Err(Termination::NullReference(std::panic::Location::caller()));
+    };
+    let implementation = val_mt
+        .ty_ref()
+        .implemented_interfaces()
+        .iter()
+        .try_find(|x| {
+            let Some(target) = x
+                .target
+                .load_with_generic_resolver(
+                    method
+                        .require_method_table_ref()
+                        .ty_ref()
+                        .__get_assembly_ref()
+                        .manager_ref(),
+                    MethodGenericResolver::new(method),
+                )
+                .map(|x| x.get_non_generic_with_method(method))
+                .flatten()
+            else {
+                return Err(Termination::LoadTypeHandleFailed(x.target.clone()));
+            };
+            let target = target.unwrap_interface();
+            Ok(std::ptr::addr_eq(interface.as_ptr(), target.as_ptr()))
+        })?
+        .ok_or(Termination::UnimplementedInterface)?;
+    let method_target =
+        method_target.cloned_map_index(|x| *implementation.map.get(x as usize).unwrap());
+
+    val_mt
+        .get_method_by_ref(&method_target)
+        .ok_or(Termination::LoadMethodFailed(method_target))
+}
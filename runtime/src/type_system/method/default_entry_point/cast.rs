@@ -0,0 +1,160 @@
+use std::{ffi::c_void, ptr::NonNull};
+
+use global::instruction::{IRegisterAddr, Instruction_Cast};
+
+use crate::{
+    stdlib::CoreTypeId,
+    type_system::{
+        cached_type_reference::GenericCachedTypeReference,
+        class::Class,
+        get_traits::{GetAssemblyRef, GetTypeVars},
+        method::{
+            Method,
+            default_entry_point::{Termination, call_frame, load_register_failed},
+        },
+        type_handle::{MethodGenericResolver, NonGenericTypeHandle},
+    },
+    value::managed_reference::ManagedReference,
+    virtual_machine::cpu::CPU,
+};
+
+pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
+    #[allow(unused)] method: &Method<T>,
+    #[allow(unused)] cpu: &mut CPU,
+    #[allow(unused)] this: Option<NonNull<()>>,
+    #[allow(unused)] args: &[*mut c_void],
+    #[allow(unused)] result_ptr: NonNull<c_void>,
+    #[allow(unused)] pc: &mut usize,
+    #[allow(unused)] caught_exception: Option<ManagedReference<Class>>,
+    ins: &Instruction_Cast<GenericCachedTypeReference, TRegisterAddr>,
+) -> Option<Result<(), Termination>> {
+    let (Instruction_Cast::IsInstanceOf { ty, .. }
+    | Instruction_Cast::CastClass { ty, .. }
+    | Instruction_Cast::Box { ty, .. }
+    | Instruction_Cast::Unbox { ty, .. }) = ins;
+
+    let Some(ty_handle) = ty
+        .get_with_generic_resolver(
+            cpu.vm_ref().assembly_manager(),
+            MethodGenericResolver::new(method),
+        )
+        .and_then(|x| x.get_non_generic_with_method(method))
+    else {
+        return Some(Err(Termination::LoadTypeHandleFailed(
+            ty.to_maybe_unloaded_handle(),
+        )));
+    };
+
+    match ins {
+        Instruction_Cast::IsInstanceOf { val, output, .. } => {
+            let Some(val) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*val) else {
+                load_register_failed!(*val);
+            };
+            if !call_frame(cpu).write_typed(*output, is_instance_of(&val, ty_handle)) {
+                load_register_failed!(*output);
+            }
+        }
+        Instruction_Cast::CastClass { val, output, .. } => {
+            return cast_class(cpu, ty_handle, *val, *output);
+        }
+        Instruction_Cast::Box { value, output, .. } => {
+            let Some(value) = call_frame(cpu).get(*value) else {
+                load_register_failed!(*value);
+            };
+            let boxed = match ty_handle {
+                NonGenericTypeHandle::Struct(s) => unsafe {
+                    ManagedReference::new_boxed(cpu, s.as_ref().method_table, value.ptr)
+                },
+                NonGenericTypeHandle::Class(_) | NonGenericTypeHandle::Interface(_) => {
+                    value.read_typed::<ManagedReference<Class>>()
+                }
+            };
+            if !call_frame(cpu).write_typed(*output, boxed) {
+                load_register_failed!(*output);
+            }
+        }
+        Instruction_Cast::Unbox { obj, output, .. } => {
+            // Unboxing to a reference type is the same as casting
+            let NonGenericTypeHandle::Struct(s) = ty_handle else {
+                return cast_class(cpu, ty_handle, *obj, *output);
+            };
+            let Some(obj) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*obj) else {
+                load_register_failed!(*obj);
+            };
+            if obj.is_null() {
+                if !cpu.throw_helper_mut().null_reference() {
+                    return Some(Err(Termination::NewObjectFailed));
+                }
+                return Some(Ok(()));
+            }
+            let s_ref = unsafe { s.as_ref() };
+            let Some(data) = obj.unboxed_data().filter(|_| {
+                obj.boxed_method_table_ref()
+                    .is_some_and(|mt| std::ptr::addr_eq(mt, s_ref.method_table_ref()))
+            }) else {
+                if !cpu.throw_helper_mut().invalid_cast() {
+                    return Some(Err(Termination::NewObjectFailed));
+                }
+                return Some(Ok(()));
+            };
+            let Some(out_var) = call_frame(cpu).get(*output) else {
+                load_register_failed!(*output);
+            };
+            unsafe {
+                out_var.copy_from(data, s_ref.val_layout().size());
+            }
+        }
+    }
+
+    Some(Ok(()))
+}
+
+fn cast_class<TRegisterAddr: IRegisterAddr>(
+    cpu: &mut CPU,
+    ty: NonGenericTypeHandle,
+    val: TRegisterAddr,
+    output: TRegisterAddr,
+) -> Option<Result<(), Termination>> {
+    let Some(val_ref) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(val) else {
+        load_register_failed!(val);
+    };
+    if !val_ref.is_null() && !is_instance_of(&val_ref, ty) {
+        if !cpu.throw_helper_mut().invalid_cast() {
+            return Some(Err(Termination::NewObjectFailed));
+        }
+        return Some(Ok(()));
+    }
+    if !call_frame(cpu).write_typed(output, val_ref) {
+        load_register_failed!(output);
+    }
+    Some(Ok(()))
+}
+
+/// Null is not an instance of any type,
+/// and boxed values are only instances of their struct and `System::Object`
+fn is_instance_of(val: &ManagedReference<Class>, ty: NonGenericTypeHandle) -> bool {
+    if let Some(boxed_mt) = val.boxed_method_table_ref() {
+        return match ty {
+            NonGenericTypeHandle::Struct(s) => {
+                std::ptr::addr_eq(boxed_mt, unsafe { s.as_ref() }.method_table_ref())
+            }
+            NonGenericTypeHandle::Class(class) => unsafe { class.as_ref() }
+                .method_table_ref()
+                .get_core_type_id()
+                .is_some_and(|x| x == CoreTypeId::System_Object),
+            NonGenericTypeHandle::Interface(_) => false,
+        };
+    }
+    let Some(mt) = val.method_table_ref() else {
+        return false;
+    };
+    match ty {
+        NonGenericTypeHandle::Class(class) => {
+            mt.can_cast_to(unsafe { class.as_ref() }.method_table_ref())
+        }
+        NonGenericTypeHandle::Interface(interface) => {
+            mt.is_implementation_of(unsafe { interface.as_ref() }.method_table_ref())
+        }
+        NonGenericTypeHandle::Struct(_) => false,
+    }
+}
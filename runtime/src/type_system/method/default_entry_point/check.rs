@@ -1,6 +1,8 @@
 use std::{ffi::c_void, ptr::NonNull};
 
-use global::instruction::{IRegisterAddr, Instruction_CommonCheck, ToCheckContent};
+use global::instruction::{
+    CompareConst, CompareKind, CompareType, IRegisterAddr, Instruction_CommonCheck, ToCheckContent,
+};
 
 use crate::{
     type_system::{
@@ -55,5 +57,85 @@ pub(super) fn eval_to_check<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IReg
             };
             Some(Ok(to_check_var.is_all_zero()))
         }
+        ToCheckContent::Compare { kind, ty, lhs, rhs } => {
+            macro compare_registers($T:ty) {{
+                let Some(lhs) = call_frame(cpu).read_typed::<$T, _>(*lhs) else {
+                    load_register_failed!(*lhs);
+                };
+                let Some(rhs) = call_frame(cpu).read_typed::<$T, _>(*rhs) else {
+                    load_register_failed!(*rhs);
+                };
+                compare(*kind, &lhs, &rhs)
+            }}
+            let res = match ty {
+                CompareType::U8 => compare_registers!(u8),
+                CompareType::U16 => compare_registers!(u16),
+                CompareType::U32 => compare_registers!(u32),
+                CompareType::U64 => compare_registers!(u64),
+
+                CompareType::I8 => compare_registers!(i8),
+                CompareType::I16 => compare_registers!(i16),
+                CompareType::I32 => compare_registers!(i32),
+                CompareType::I64 => compare_registers!(i64),
+
+                CompareType::F32 => compare_registers!(f32),
+                CompareType::F64 => compare_registers!(f64),
+
+                CompareType::Object => {
+                    let Some(lhs) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*lhs)
+                    else {
+                        load_register_failed!(*lhs);
+                    };
+                    let Some(rhs) = call_frame(cpu).read_typed::<ManagedReference<Class>, _>(*rhs)
+                    else {
+                        load_register_failed!(*rhs);
+                    };
+                    match kind {
+                        CompareKind::Eq => lhs == rhs,
+                        CompareKind::Ne => lhs != rhs,
+                        CompareKind::Lt | CompareKind::Le | CompareKind::Gt | CompareKind::Ge => {
+                            return Some(Err(Termination::UnsupportedCalculation(
+                                std::any::type_name::<ManagedReference<Class>>(),
+                            )));
+                        }
+                    }
+                }
+            };
+            Some(Ok(res))
+        }
+        ToCheckContent::CompareWithConst { kind, lhs, rhs } => {
+            macro compare_with_const($T:ty, $rhs:expr) {{
+                let Some(lhs) = call_frame(cpu).read_typed::<$T, _>(*lhs) else {
+                    load_register_failed!(*lhs);
+                };
+                compare(*kind, &lhs, $rhs)
+            }}
+            let res = match rhs {
+                CompareConst::U8(rhs) => compare_with_const!(u8, rhs),
+                CompareConst::U16(rhs) => compare_with_const!(u16, rhs),
+                CompareConst::U32(rhs) => compare_with_const!(u32, rhs),
+                CompareConst::U64(rhs) => compare_with_const!(u64, rhs),
+
+                CompareConst::I8(rhs) => compare_with_const!(i8, rhs),
+                CompareConst::I16(rhs) => compare_with_const!(i16, rhs),
+                CompareConst::I32(rhs) => compare_with_const!(i32, rhs),
+                CompareConst::I64(rhs) => compare_with_const!(i64, rhs),
+
+                CompareConst::F32(rhs) => compare_with_const!(f32, rhs),
+                CompareConst::F64(rhs) => compare_with_const!(f64, rhs),
+            };
+            Some(Ok(res))
+        }
+    }
+}
+
+fn compare<T: PartialOrd>(kind: CompareKind, lhs: &T, rhs: &T) -> bool {
+    match kind {
+        CompareKind::Eq => lhs == rhs,
+        CompareKind::Ne => lhs != rhs,
+        CompareKind::Lt => lhs < rhs,
+        CompareKind::Le => lhs <= rhs,
+        CompareKind::Gt => lhs > rhs,
+        CompareKind::Ge => lhs >= rhs,
     }
 }
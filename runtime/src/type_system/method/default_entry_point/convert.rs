@@ -0,0 +1,152 @@
+use std::{ffi::c_void, ptr::NonNull};
+
+use global::instruction::{IRegisterAddr, Instruction_Convert, OverflowMode, PrimitiveKind};
+
+use crate::{
+    type_system::{
+        class::Class,
+        get_traits::{GetAssemblyRef, GetTypeVars},
+        method::{
+            Method,
+            default_entry_point::{Termination, call_frame, load_register_failed},
+        },
+    },
+    value::managed_reference::ManagedReference,
+    virtual_machine::cpu::CPU,
+};
+
+pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
+    #[allow(unused)] method: &Method<T>,
+    #[allow(unused)] cpu: &mut CPU,
+    #[allow(unused)] this: Option<NonNull<()>>,
+    #[allow(unused)] args: &[*mut c_void],
+    #[allow(unused)] result_ptr: NonNull<c_void>,
+    #[allow(unused)] pc: &mut usize,
+    #[allow(unused)] caught_exception: Option<ManagedReference<Class>>,
+    ins: &Instruction_Convert<TRegisterAddr>,
+) -> Option<Result<(), Termination>> {
+    let Instruction_Convert {
+        from,
+        to,
+        mode,
+        source,
+        target,
+    } = ins;
+    macro read($TRust:ty) {
+        match call_frame(cpu).read_typed::<$TRust, _>(*source) {
+            Some(x) => x.into_value(),
+            None => load_register_failed!(*source),
+        }
+    }
+    let value = match from {
+        PrimitiveKind::U8 => read!(u8),
+        PrimitiveKind::U16 => read!(u16),
+        PrimitiveKind::U32 => read!(u32),
+        PrimitiveKind::U64 => read!(u64),
+        PrimitiveKind::USize => read!(usize),
+
+        PrimitiveKind::I8 => read!(i8),
+        PrimitiveKind::I16 => read!(i16),
+        PrimitiveKind::I32 => read!(i32),
+        PrimitiveKind::I64 => read!(i64),
+        PrimitiveKind::ISize => read!(isize),
+
+        PrimitiveKind::F32 => read!(f32),
+        PrimitiveKind::F64 => read!(f64),
+    };
+    macro write($TRust:ty) {{
+        let Some(converted) = <$TRust>::from_value(value, *mode) else {
+            if !cpu.throw_helper_mut().overflow() {
+                return Some(Err(Termination::NewObjectFailed));
+            }
+            return Some(Ok(()));
+        };
+        call_frame(cpu).write_typed(*target, converted)
+    }}
+    let written = match to {
+        PrimitiveKind::U8 => write!(u8),
+        PrimitiveKind::U16 => write!(u16),
+        PrimitiveKind::U32 => write!(u32),
+        PrimitiveKind::U64 => write!(u64),
+        PrimitiveKind::USize => write!(usize),
+
+        PrimitiveKind::I8 => write!(i8),
+        PrimitiveKind::I16 => write!(i16),
+        PrimitiveKind::I32 => write!(i32),
+        PrimitiveKind::I64 => write!(i64),
+        PrimitiveKind::ISize => write!(isize),
+
+        PrimitiveKind::F32 => write!(f32),
+        PrimitiveKind::F64 => write!(f64),
+    };
+    if !written {
+        load_register_failed!(*target);
+    }
+    Some(Ok(()))
+}
+
+/// A value wide enough to hold every primitive without losing information
+#[derive(Clone, Copy)]
+enum ConvertValue {
+    Integer(i128),
+    Float(f64),
+}
+
+trait ConvertForEval: Copy + Sized {
+    fn into_value(self) -> ConvertValue;
+    /// Returns [`None`] if the value overflows in [`OverflowMode::Checked`]
+    fn from_value(value: ConvertValue, mode: OverflowMode) -> Option<Self>;
+}
+
+macro impl_convert_for_eval($($i:ty),* $(,)?) {$(
+	impl ConvertForEval for $i {
+		fn into_value(self) -> ConvertValue {
+			ConvertValue::Integer(self as i128)
+		}
+		fn from_value(value: ConvertValue, mode: OverflowMode) -> Option<Self> {
+			match (value, mode) {
+				(ConvertValue::Integer(x), OverflowMode::Wrapping) => Some(x as Self),
+				(ConvertValue::Integer(x), OverflowMode::Checked) => Self::try_from(x).ok(),
+				(ConvertValue::Integer(x), OverflowMode::Saturating) => {
+					Some(x.clamp(Self::MIN as i128, Self::MAX as i128) as Self)
+				}
+				// `as` from floats already rounds toward zero and saturates
+				(ConvertValue::Float(x), OverflowMode::Wrapping | OverflowMode::Saturating) => {
+					Some(x as Self)
+				}
+				(ConvertValue::Float(x), OverflowMode::Checked) => {
+					if x.is_nan() {
+						None
+					} else {
+						Self::try_from(x as i128).ok()
+					}
+				}
+			}
+		}
+	}
+)*}
+
+impl_convert_for_eval!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+macro impl_float_convert_for_eval($($i:ty),* $(,)?) {$(
+	impl ConvertForEval for $i {
+		fn into_value(self) -> ConvertValue {
+			ConvertValue::Float(self as f64)
+		}
+		fn from_value(value: ConvertValue, mode: OverflowMode) -> Option<Self> {
+			match value {
+				ConvertValue::Integer(x) => Some(x as Self),
+				ConvertValue::Float(x) => {
+					let res = x as Self;
+					if mode == OverflowMode::Checked && res.is_infinite() && x.is_finite() {
+						None
+					} else {
+						Some(res)
+					}
+				}
+			}
+		}
+	}
+)*}
+
+impl_float_convert_for_eval!(f32, f64);
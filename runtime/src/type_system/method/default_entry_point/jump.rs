@@ -1,7 +1,7 @@
 use std::{ffi::c_void, ptr::NonNull};
 
 use global::instruction::{
-    IRegisterAddr, Instruction_Jump, JumpCondition, JumpTarget, JumpTargetType,
+    IRegisterAddr, Instruction_Jump, Instruction_Switch, JumpCondition, JumpTarget, JumpTargetType,
 };
 
 use crate::{
@@ -69,3 +69,26 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
 
     Some(Ok(()))
 }
+
+pub(super) fn eval_switch<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
+    #[allow(unused)] method: &Method<T>,
+    #[allow(unused)] cpu: &mut CPU,
+    #[allow(unused)] this: Option<NonNull<()>>,
+    #[allow(unused)] args: &[*mut c_void],
+    #[allow(unused)] result_ptr: NonNull<c_void>,
+    #[allow(unused)] pc: &mut usize,
+    #[allow(unused)] caught_exception: Option<ManagedReference<Class>>,
+    ins: &Instruction_Switch<TRegisterAddr>,
+) -> Option<Result<(), Termination>> {
+    let Some(value) = call_frame(cpu).read_typed::<u32, _>(ins.value) else {
+        load_register_failed!(ins.value);
+    };
+    let target = ins
+        .targets
+        .get(value as usize)
+        .copied()
+        .unwrap_or(ins.default);
+    do_jump(pc, target);
+
+    Some(Ok(()))
+}
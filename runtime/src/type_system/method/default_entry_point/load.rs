@@ -8,7 +8,7 @@ use crate::{
         class::Class,
         get_traits::{GetAssemblyRef, GetTypeVars},
         method::{
-            Method,
+            Method, MethodPointer, MethodRef,
             default_entry_point::{Termination, call_frame, load_register_failed},
         },
         type_handle::{MethodGenericResolver, NonGenericTypeHandle},
@@ -25,7 +25,7 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
     #[allow(unused)] result_ptr: NonNull<c_void>,
     #[allow(unused)] pc: &mut usize,
     #[allow(unused)] caught_exception: Option<ManagedReference<Class>>,
-    ins: &Instruction_Load<String, GenericCachedTypeReference, u32, TRegisterAddr>,
+    ins: &Instruction_Load<String, GenericCachedTypeReference, MethodRef, u32, TRegisterAddr>,
 ) -> Option<Result<(), Termination>> {
     let register_addr = &ins.addr;
     match &ins.content {
@@ -338,6 +338,83 @@ pub(super) fn eval<T: GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr
             }
             None => return Some(Err(Termination::LoadCaughtExceptionWithoutExceptions)),
         },
+
+        LoadContent::F32(val) => {
+            if !call_frame(cpu).write_typed(*register_addr, *val) {
+                load_register_failed!(*register_addr);
+            }
+        }
+        LoadContent::F64(val) => {
+            if !call_frame(cpu).write_typed(*register_addr, *val) {
+                load_register_failed!(*register_addr);
+            }
+        }
+
+        LoadContent::MethodPointer {
+            ty,
+            method: method_target,
+        } => {
+            let Some(ty) = ty
+                .get_with_generic_resolver(
+                    cpu.vm_ref().assembly_manager(),
+                    MethodGenericResolver::new(method),
+                )
+                .and_then(|x| x.get_non_generic_with_method(method))
+            else {
+                return Some(Err(Termination::LoadTypeHandleFailed(
+                    ty.to_maybe_unloaded_handle(),
+                )));
+            };
+            let Some(f_pointer) = MethodPointer::from_method_ref(ty, method_target) else {
+                return Some(Err(Termination::LoadMethodFailed(method_target.clone())));
+            };
+            if !call_frame(cpu).write_typed(*register_addr, f_pointer) {
+                load_register_failed!(*register_addr);
+            }
+        }
+
+        LoadContent::Null => {
+            let Some(local_var) = call_frame(cpu).get(*register_addr) else {
+                load_register_failed!(*register_addr);
+            };
+            // Only references can be null
+            let (NonGenericTypeHandle::Class(_) | NonGenericTypeHandle::Interface(_)) =
+                local_var.ty
+            else {
+                load_register_failed!(*register_addr);
+            };
+            if !call_frame(cpu).write_typed(*register_addr, ManagedReference::<Class>::null()) {
+                load_register_failed!(*register_addr);
+            }
+        }
+        LoadContent::Default(ty) => {
+            let Some(ty) = ty
+                .get_with_generic_resolver(
+                    cpu.vm_ref().assembly_manager(),
+                    MethodGenericResolver::new(method),
+                )
+                .and_then(|x| x.get_non_generic_with_method(method))
+            else {
+                return Some(Err(Termination::LoadTypeHandleFailed(
+                    ty.to_maybe_unloaded_handle(),
+                )));
+            };
+            let Some(local_var) = call_frame(cpu).get(*register_addr) else {
+                load_register_failed!(*register_addr);
+            };
+            let size = ty.val_layout().size();
+            if local_var.layout.size() < size {
+                load_register_failed!(*register_addr);
+            }
+            unsafe {
+                local_var.ptr.write_bytes(0, size);
+            }
+        }
+        LoadContent::Char(val) => {
+            if !call_frame(cpu).write_typed(*register_addr, *val) {
+                load_register_failed!(*register_addr);
+            }
+        }
     }
 
     Some(Ok(()))
@@ -0,0 +1,73 @@
+use std::{ffi::c_void, ptr::NonNull};
+
+use global::instruction::{IRegisterAddr, Instruction_Move};
+
+use crate::{
+    type_system::{
+        class::Class,
+        get_traits::{GetAssemblyRef, GetTypeVars},
+        method::{
+            Method,
+            default_entry_point::{Termination, call_frame, load_register_failed},
+        },
+        type_handle::NonGenericTypeHandle,
+    },
+    value::managed_reference::ManagedReference,
+    virtual_machine::cpu::CPU,
+};
+
+pub(super) fn eval<T: Sized + GetAssemblyRef + GetTypeVars, TRegisterAddr: IRegisterAddr>(
+    #[allow(unused)] method: &Method<T>,
+    #[allow(unused)] cpu: &mut CPU,
+    #[allow(unused)] this: Option<NonNull<()>>,
+    #[allow(unused)] args: &[*mut c_void],
+    #[allow(unused)] result_ptr: NonNull<c_void>,
+    #[allow(unused)] pc: &mut usize,
+    #[allow(unused)] caught_exception: Option<ManagedReference<Class>>,
+    ins: &Instruction_Move<TRegisterAddr>,
+) -> Option<Result<(), Termination>> {
+    let Some(from) = call_frame(cpu).get(ins.from) else {
+        load_register_failed!(ins.from);
+    };
+    let Some(to) = call_frame(cpu).get(ins.to) else {
+        load_register_failed!(ins.to);
+    };
+    if from.layout.size() > to.layout.size()
+        || (cfg!(debug_assertions) && !is_move_compatible(from.ty, to.ty))
+    {
+        return Some(Err(Termination::IncompatibleRegisters(from.ty, to.ty)));
+    }
+    unsafe {
+        to.copy_from(from.ptr, from.layout.size());
+    }
+    Some(Ok(()))
+}
+
+/// References can be moved into registers of their parents or implemented interfaces,
+/// other values can only be moved into registers of the same type
+fn is_move_compatible(from: NonGenericTypeHandle, to: NonGenericTypeHandle) -> bool {
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (NonGenericTypeHandle::Class(from), NonGenericTypeHandle::Class(to)) => unsafe {
+            from.as_ref()
+                .method_table_ref()
+                .can_cast_to(to.as_ref().method_table_ref())
+        },
+        (NonGenericTypeHandle::Class(from), NonGenericTypeHandle::Interface(to)) => unsafe {
+            from.as_ref()
+                .method_table_ref()
+                .is_implementation_of(to.as_ref().method_table_ref())
+        },
+        (NonGenericTypeHandle::Interface(from), NonGenericTypeHandle::Interface(to)) => unsafe {
+            from.as_ref()
+                .method_table_ref()
+                .can_cast_to(to.as_ref().method_table_ref())
+        },
+        (NonGenericTypeHandle::Interface(_), to @ NonGenericTypeHandle::Class(_)) => {
+            to.is_certain_core_type(stdlib_header::CoreTypeId::System_Object)
+        }
+        _ => false,
+    }
+}
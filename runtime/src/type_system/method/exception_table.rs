@@ -101,56 +101,9 @@ impl<T> ExceptionTable<T> {
         self.resort();
     }
 
+    /// The entries covering `pc`, the innermost first
     pub fn get_for(&self, pc: usize) -> impl Iterator<Item = &ExceptionTableEntry> {
-        ExceptionTableLookupFor::new(self, pc)
-    }
-}
-
-struct ExceptionTableLookupFor<'a, T> {
-    this: &'a ExceptionTable<T>,
-    middle: usize,
-    pc: usize,
-}
-
-impl<'a, T> ExceptionTableLookupFor<'a, T> {
-    pub fn new(this: &'a ExceptionTable<T>, pc: usize) -> Self {
-        Self {
-            this,
-            middle: this.entries.len().div_ceil(2),
-            pc,
-        }
-    }
-}
-
-impl<'a, T> Iterator for ExceptionTableLookupFor<'a, T> {
-    type Item = &'a ExceptionTableEntry;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.this.entries.is_empty() {
-            return None;
-        }
-        loop {
-            let x = unsafe { self.this.entries.get_unchecked(self.middle) };
-            match x.check(self.pc) {
-                std::cmp::Ordering::Less => {
-                    let next_middle = (self.this.entries.len() - self.middle).div_ceil(2);
-                    let overflowed;
-                    (self.middle, overflowed) = self.middle.overflowing_add(next_middle);
-                    if overflowed || self.middle >= self.this.entries.len() {
-                        return None;
-                    }
-                }
-                std::cmp::Ordering::Equal => return Some(x),
-                std::cmp::Ordering::Greater => {
-                    let next_middle = (self.this.entries.len() - self.middle).div_ceil(2);
-                    let overflowed;
-                    (self.middle, overflowed) = self.middle.overflowing_sub(next_middle);
-                    if overflowed {
-                        return None;
-                    }
-                }
-            }
-        }
+        self.entries.iter().filter(move |x| x.can_handle(pc))
     }
 }
 
@@ -230,17 +183,6 @@ impl ExceptionTableEntry {
         }
     }
 
-    const fn check(&self, pc: usize) -> std::cmp::Ordering {
-        if self.can_handle(pc) {
-            std::cmp::Ordering::Equal
-        } else if (pc as u64) > self.range.end {
-            std::cmp::Ordering::Greater
-        } else if (pc as u64) < self.range.start {
-            std::cmp::Ordering::Less
-        } else {
-            unsafe { std::hint::unreachable_unchecked() }
-        }
-    }
     pub fn get_exception_type<T: GetAssemblyRef + GetTypeVars>(
         &self,
         method: &Method<T>,
@@ -349,5 +291,20 @@ mod tests {
 
         assert!(table.get_for(3).next().is_none());
         assert!(table.get_for(99).next().is_none());
+
+        // 14 is covered by 5..15 and 14..24, 15 is the end of the first one
+        assert_eq!(table.get_for(14).count(), 2);
+        assert_eq!(table.get_for(14).next().unwrap().range.start, 14);
+        assert_eq!(table.get_for(15).count(), 1);
+    }
+
+    #[test]
+    fn test_search_single_exception_entry() {
+        let mut table: ExceptionTable<()> = ExceptionTable::new(NonNull::dangling());
+        table.push(generate_entry(1..2));
+
+        assert!(table.get_for(1).next().is_some());
+        assert!(table.get_for(0).next().is_none());
+        assert!(table.get_for(2).next().is_none());
     }
 }
@@ -0,0 +1,125 @@
+use std::{alloc::Layout, ffi::c_void, ptr::NonNull};
+
+use crate::{
+    type_system::{
+        class::Class,
+        get_traits::GetNonGenericTypeHandleKind,
+        r#struct::Struct,
+        type_handle::{NonGenericTypeHandle, NonGenericTypeHandleKind},
+    },
+    virtual_machine::cpu::CPU,
+};
+
+use super::{Method, MethodRef};
+
+const KIND_MASK: usize = 0b11;
+
+/// A managed method stored in a `System::Pointer`
+///
+/// The kind of the type owning the method is kept in the low bits,
+/// so the method can be called without knowing its type statically.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MethodPointer(usize);
+
+const _: () = {
+    assert!(align_of::<Method<Class>>() > KIND_MASK);
+    assert!(align_of::<Method<Struct>>() > KIND_MASK);
+    assert!(size_of::<MethodPointer>() == size_of::<*const c_void>());
+};
+
+impl MethodPointer {
+    pub const fn null() -> Self {
+        Self(0)
+    }
+
+    pub fn new<T: GetNonGenericTypeHandleKind>(method: &Method<T>) -> Self {
+        let kind = method
+            .require_method_table_ref()
+            .ty_ref()
+            .__get_non_generic_type_handle_kind();
+        Self(NonNull::from_ref(method).as_ptr().expose_provenance() | (kind as usize))
+    }
+
+    /// Interfaces have no callable methods, so it returns [`None`] for them
+    pub fn from_method_ref(ty: NonGenericTypeHandle, method: &MethodRef) -> Option<Self> {
+        match ty {
+            NonGenericTypeHandle::Class(ty) => unsafe { ty.as_ref() }
+                .method_table_ref()
+                .get_method_by_ref(method)
+                .map(|x| Self::new(unsafe { x.as_ref() })),
+            NonGenericTypeHandle::Struct(ty) => unsafe { ty.as_ref() }
+                .method_table_ref()
+                .get_method_by_ref(method)
+                .map(|x| Self::new(unsafe { x.as_ref() })),
+            NonGenericTypeHandle::Interface(_) => None,
+        }
+    }
+
+    pub const fn is_null(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn get(&self) -> Option<(NonNull<Method<()>>, NonGenericTypeHandleKind)> {
+        let kind = match self.0 & KIND_MASK {
+            x if x == NonGenericTypeHandleKind::Class as usize => NonGenericTypeHandleKind::Class,
+            x if x == NonGenericTypeHandleKind::Struct as usize => NonGenericTypeHandleKind::Struct,
+            _ => return None,
+        };
+        let method = NonNull::new(std::ptr::with_exposed_provenance_mut::<Method<()>>(
+            self.0 & !KIND_MASK,
+        ))?;
+        Some((method, kind))
+    }
+
+    /// Returns [`None`] if the pointer is null
+    pub fn is_static(&self) -> Option<bool> {
+        let (method, kind) = self.get()?;
+        let is_static = match kind {
+            NonGenericTypeHandleKind::Class => unsafe { method.cast::<Method<Class>>().as_ref() }
+                .attr()
+                .is_static(),
+            NonGenericTypeHandleKind::Struct => unsafe { method.cast::<Method<Struct>>().as_ref() }
+                .attr()
+                .is_static(),
+            NonGenericTypeHandleKind::Interface => unreachable!(),
+        };
+        Some(is_static)
+    }
+
+    /// Returns [`None`] if the pointer is null or the return type cannot be resolved
+    pub fn return_type(&self) -> Option<NonGenericTypeHandle> {
+        let (method, kind) = self.get()?;
+        match kind {
+            NonGenericTypeHandleKind::Class => {
+                unsafe { method.cast::<Method<Class>>().as_ref() }.try_get_return_type()
+            }
+            NonGenericTypeHandleKind::Struct => {
+                unsafe { method.cast::<Method<Struct>>().as_ref() }.try_get_return_type()
+            }
+            NonGenericTypeHandleKind::Interface => unreachable!(),
+        }
+    }
+
+    /// Returns [`None`] if the pointer is null,
+    /// otherwise the same as [`Method::untyped_call`] with the layout of the return type
+    pub fn untyped_call(
+        &self,
+        cpu: &mut CPU,
+        this: Option<NonNull<()>>,
+        args: &[*mut c_void],
+    ) -> Option<(NonNull<u8>, Layout, Layout)> {
+        let (method, kind) = self.get()?;
+        macro call($T:ty) {{
+            let method = unsafe { method.cast::<Method<$T>>().as_ref() };
+            let actual_layout = method.get_return_type().val_layout();
+            let (ret_ptr, ret_layout) = method.untyped_call(cpu, this, args);
+            Some((ret_ptr, ret_layout, actual_layout))
+        }}
+        match kind {
+            NonGenericTypeHandleKind::Class => call!(Class),
+            NonGenericTypeHandleKind::Struct => call!(Struct),
+            NonGenericTypeHandleKind::Interface => unreachable!(),
+        }
+    }
+}
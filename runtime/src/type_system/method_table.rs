@@ -12,7 +12,11 @@ use crate::{
     memory::{GetFieldOffsetOptions, GetLayoutOptions, OwnedPtr},
     stdlib::{CoreTypeId, CoreTypeIdConstExt as _},
     type_system::{
-        class::Class, method::Method, r#struct::Struct, type_handle::TypeGenericResolver,
+        class::Class,
+        interface::Interface,
+        method::Method,
+        r#struct::Struct,
+        type_handle::{MaybeUnloadedTypeHandle, TypeGenericResolver},
     },
 };
 
@@ -246,6 +250,35 @@ const impl CanCastToSpec for MethodTable<Struct> {
     }
 }
 
+impl CanCastToSpec for MethodTable<Interface> {
+    fn __can_cast_to(&self, desired: &Self) -> bool {
+        let ty = self.ty_ref();
+        ty.required_interfaces()
+            .iter()
+            .any(|x| interface_can_cast_to(ty, x, desired))
+    }
+}
+
+/// Resolve `interface` against the type vars of `ty` and check whether it can be cast to `desired`
+fn interface_can_cast_to<T: GetTypeVars + GetAssemblyRef>(
+    ty: &T,
+    interface: &MaybeUnloadedTypeHandle,
+    desired: &MethodTable<Interface>,
+) -> bool {
+    interface
+        .load_with_generic_resolver(
+            ty.__get_assembly_ref().manager_ref(),
+            TypeGenericResolver::new(ty),
+        )
+        .and_then(|x| x.get_non_generic_with_type(ty))
+        .is_some_and(|x| match x {
+            NonGenericTypeHandle::Interface(interface) => unsafe { interface.as_ref() }
+                .method_table_ref()
+                .can_cast_to(desired),
+            _ => false,
+        })
+}
+
 #[expect(private_bounds)]
 impl<T> MethodTable<T>
 where
@@ -262,14 +295,26 @@ where
 impl MethodTable<Class> {
     /// A is not inherited from itself
     pub fn is_inherited_from(&self, final_parent: &Self) -> bool {
-        if let Some(parent) = self.ty_ref().parent() {
-            unsafe { parent.as_ref() }
-                .method_table_ref()
-                .is_inherited_from(final_parent)
+        if let Some(parent) = self.ty_ref().instantiated_parent() {
+            let parent = unsafe { parent.as_ref() }.method_table_ref();
+            std::ptr::addr_eq(parent, final_parent) || parent.is_inherited_from(final_parent)
         } else {
             false
         }
     }
+    /// Whether the class or one of its parents implements `interface`,
+    /// including the interfaces required by the implemented ones
+    pub fn is_implementation_of(&self, interface: &MethodTable<Interface>) -> bool {
+        let ty = self.ty_ref();
+        ty.implemented_interfaces()
+            .iter()
+            .any(|x| interface_can_cast_to(ty, &x.target, interface))
+            || ty.instantiated_parent().is_some_and(|parent| {
+                unsafe { parent.as_ref() }
+                    .method_table_ref()
+                    .is_implementation_of(interface)
+            })
+    }
 }
 
 impl<T> MethodTable<T>
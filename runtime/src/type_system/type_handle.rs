@@ -543,8 +543,7 @@ impl<T: GetTypeVars + GetAssemblyRef> IGenericResolver for MethodGenericResolver
     fn resolve_method_generic(&self, g_index: u32) -> Option<TypeHandle> {
         self.0
             .__get_type_vars()
-            .as_ref()
-            .unwrap()
+            .as_ref()?
             .get(g_index as usize)
             .copied()
             .map(TypeHandle::from)
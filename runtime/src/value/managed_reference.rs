@@ -345,6 +345,10 @@ where
         let data = unsafe { self.data?.as_ref() };
         if data.header.is_static() {
             Some(unsafe { data.mt.as_ref() }.static_layout(Default::default()))
+        } else if data.header.is_boxed() {
+            // Boxed values always use the method table of the struct
+            let mt = unsafe { data.mt.cast::<MethodTable<Struct>>().as_ref() };
+            Some(mt.mem_layout(Default::default()))
         } else if let Some(accessor) = self.access::<ArrayAccessor>() {
             let element_layouts =
                 crate::memory::arrayed_layout(accessor.element_layout()?, accessor.len()?)?;
@@ -373,6 +377,7 @@ where
 }
 
 mod for_array;
+mod for_boxed;
 mod for_class;
 mod for_field;
 mod for_large_string;
@@ -393,6 +398,10 @@ trait GcMarkSpec {
 
 impl CallDestructorSpec for Class {
     fn call_destructor_spec(r: &ManagedReference<Self>, cpu: &mut CPU) {
+        // Structs have no destructors, even if boxed
+        if r.is_boxed() {
+            return;
+        }
         unsafe {
             let destructor = *r
                 .method_table_ref_unchecked()
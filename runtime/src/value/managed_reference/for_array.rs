@@ -125,15 +125,16 @@ impl ManagedReference<Class> {
     }
 
     pub fn is_array_like(&self) -> bool {
-        self.method_table_ref().is_some_and(|mt| {
-            mt.ty_ref().generic().is_some_and(|t| unsafe {
-                let t_ref = t.as_ref();
-                t_ref
-                    .method_table_ref()
-                    .get_core_type_id()
-                    .is_some_and(|id| id == CoreTypeId::System_Array_1)
+        !self.is_boxed()
+            && self.method_table_ref().is_some_and(|mt| {
+                mt.ty_ref().generic().is_some_and(|t| unsafe {
+                    let t_ref = t.as_ref();
+                    t_ref
+                        .method_table_ref()
+                        .get_core_type_id()
+                        .is_some_and(|id| id == CoreTypeId::System_Array_1)
+                })
             })
-        })
     }
 }
 
@@ -375,7 +376,7 @@ impl ArrayAccessor {
     }
 
     pub fn get(&self, index: usize) -> Option<&[u8]> {
-        if (self.len()? >= index) || (!self.can_get_element_type_handle()) {
+        if (index >= self.len()?) || (!self.can_get_element_type_handle()) {
             None
         } else {
             Some(unsafe { self.get_unchecked(index) })
@@ -393,7 +394,7 @@ impl ArrayAccessor {
         }
     }
     pub fn get_mut(&mut self, index: usize) -> Option<&mut [u8]> {
-        if (self.len()? >= index) || (!self.can_get_element_type_handle()) {
+        if (index >= self.len()?) || (!self.can_get_element_type_handle()) {
             None
         } else {
             Some(unsafe { self.get_mut_unchecked(index) })
@@ -0,0 +1,74 @@
+use std::ptr::NonNull;
+
+use crate::{
+    stdlib::CoreTypeId,
+    type_system::{
+        class::Class, get_traits::GetAssemblyRef, method_table::MethodTable, r#struct::Struct,
+    },
+    virtual_machine::cpu::CPU,
+};
+
+use super::ManagedReference;
+
+impl<T> ManagedReference<T> {
+    /// Whether it is a struct value boxed by [`ManagedReference::new_boxed`],
+    /// whose method table is the struct's one
+    #[inline]
+    pub fn is_boxed(&self) -> bool {
+        self.header().is_some_and(|x| x.is_boxed())
+    }
+}
+
+impl ManagedReference<Class> {
+    /// Copies the struct value at `val` to a new heap object
+    ///
+    /// # Safety
+    /// `val` must point to a valid value of the struct
+    pub unsafe fn new_boxed(
+        cpu: &mut CPU,
+        mt: NonNull<MethodTable<Struct>>,
+        val: NonNull<u8>,
+    ) -> Self {
+        let mut this = ManagedReference::<Struct>::common_alloc(cpu, mt, false);
+        let size = unsafe { mt.as_ref() }.mem_layout(Default::default()).size();
+        this.header_mut().unwrap().set_is_boxed(true);
+        unsafe {
+            this.data().unwrap().cast::<u8>().copy_from(val, size);
+        }
+        this.cast()
+    }
+
+    /// Returns [`None`] if it is null or not boxed
+    pub fn boxed_method_table_ref(&self) -> Option<&MethodTable<Struct>> {
+        if !self.is_boxed() {
+            return None;
+        }
+        self.method_table()
+            .map(|x| unsafe { x.cast::<MethodTable<Struct>>().as_ref() })
+    }
+
+    /// The method table to find the methods called on it in, [`None`] if it is null
+    ///
+    /// Boxed values carry the method table of their struct,
+    /// so only the methods of `System::Object` can be called on them
+    pub fn class_method_table_ref(&self) -> Option<&MethodTable<Class>> {
+        let Some(mt) = self.boxed_method_table_ref() else {
+            return self.method_table_ref();
+        };
+        let object = mt
+            .ty_ref()
+            .__get_assembly_ref()
+            .manager_ref()
+            .get_core_type(CoreTypeId::System_Object)
+            .unwrap_class();
+        Some(unsafe { object.as_ref() }.method_table_ref())
+    }
+
+    /// Pointer to the boxed struct value, [`None`] if it is null or not boxed
+    pub fn unboxed_data(&self) -> Option<NonNull<u8>> {
+        if !self.is_boxed() {
+            return None;
+        }
+        self.data().map(NonNull::cast)
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
         get_traits::{
             GetAssemblyRef, GetFields, GetGeneric, GetMethodTableRef, GetParent, GetTypeVars,
         },
-        method_table::FieldMemInfo,
+        method_table::{FieldMemInfo, MethodTable},
         r#struct::Struct,
         type_handle::NonGenericTypeHandle,
     },
@@ -68,6 +68,21 @@ where
             return Vec::new().into_iter().map(map_info);
         };
 
+        if self.0.is_boxed() {
+            // Boxed values always use the method table of the struct
+            let mt = unsafe {
+                self.0
+                    .method_table()
+                    .unwrap()
+                    .cast::<MethodTable<Struct>>()
+                    .as_ref()
+            };
+            return mt
+                .all_fields_mem_info(Default::default(), options)
+                .into_iter()
+                .map(map_info);
+        }
+
         if is_static {
             mt.all_static_fields_mem_info(Default::default(), options)
                 .into_iter()
@@ -22,9 +22,10 @@ impl<T> IAccessor<T> for LargeStringAccessor {
 
 impl IAccessor<Class> for LargeStringAccessor {
     fn is_valid(r: &ManagedReference<Class>) -> bool {
-        r.method_table_ref()
-            .and_then(|x| x.get_core_type_id())
-            .is_some_and(|x| x == CoreTypeId::System_LargeString)
+        !r.is_boxed()
+            && r.method_table_ref()
+                .and_then(|x| x.get_core_type_id())
+                .is_some_and(|x| x == CoreTypeId::System_LargeString)
     }
 }
 
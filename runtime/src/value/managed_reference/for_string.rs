@@ -92,9 +92,10 @@ impl<T> IAccessor<T> for StringAccessor {
 
 impl IAccessor<Class> for StringAccessor {
     fn is_valid(r: &ManagedReference<Class>) -> bool {
-        r.method_table_ref()
-            .and_then(|x| x.get_core_type_id())
-            .is_some_and(|x| x == CoreTypeId::System_String)
+        !r.is_boxed()
+            && r.method_table_ref()
+                .and_then(|x| x.get_core_type_id())
+                .is_some_and(|x| x == CoreTypeId::System_String)
     }
 }
 
@@ -1,13 +1,26 @@
-use std::{hint::unlikely, sync::nonpoison::RwLock, thread::ThreadId};
+use std::{
+    hint::unlikely,
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        nonpoison::{Condvar, Mutex, RwLock},
+    },
+    thread::ThreadId,
+};
 
 use bitfields::{FromBits, IntoBits, bitfield};
 use parking_lot::{RawMutex, lock_api::RawMutex as _};
 
+#[cfg(test)]
+mod tests;
+
 #[bitfield(u64, new = false)]
 pub struct ObjectHeader {
     is_marked: bool,
     is_static: bool,
-    #[bits(6)]
+    is_boxed: bool,
+    #[bits(5)]
     _pad1: u8,
     #[bits(32)]
     sync: Sync,
@@ -20,9 +33,41 @@ impl ObjectHeader {
         ObjectHeaderBuilder::new()
             .with_is_marked(false)
             .with_is_static(is_static)
+            .with_is_boxed(false)
             .with_sync(Sync::new())
             .build()
     }
+
+    /// Get the sync block of the object, inflating the thin sync if needed
+    ///
+    /// The header is shared between threads,
+    /// so it is only accessed atomically and the fat sync is installed by a compare-exchange
+    ///
+    /// # Safety
+    /// `this` must point to the header of a live object
+    pub unsafe fn sync_block(this: NonNull<Self>) -> Arc<SyncBlock> {
+        let word = unsafe { AtomicU64::from_ptr(this.as_ptr().cast()) };
+        let mut current = word.load(Ordering::Acquire);
+        loop {
+            let sync = Self(current).sync();
+            if unsafe { !sync.thin.is_thin() } {
+                return unsafe { sync.fat.get_block() }
+                    .expect("The sync block of a live object has been destroyed");
+            }
+            let mut blocks = G_SYNC_BLOCKS.write();
+            let fat = blocks.inflate(unsafe { sync.thin.id() });
+            let mut inflated = Self(current);
+            inflated.set_sync(Sync { fat });
+            match word.compare_exchange(current, inflated.0, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return blocks.get(fat).unwrap(),
+                // Another thread inflated it or the other bits changed
+                Err(actual) => {
+                    blocks.destroy(fat);
+                    current = actual;
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -48,34 +93,12 @@ impl Sync {
         unsafe { std::mem::transmute(thin) }
     }
 
-    #[allow(static_mut_refs)]
     pub fn to_fat(&mut self) {
         if unlikely(unsafe { !self.thin.is_thin() }) {
             return;
         }
 
-        let id = unsafe { self.thin.id() };
-        let _g = G_SYNC_BLOCKS_LOCK.write();
-        let index;
-        if let Some((i, sync_block)) =
-            unsafe { G_SYNC_BLOCKS.iter_mut().enumerate().find(|x| !x.1.is_valid) }
-        {
-            index = i;
-            sync_block.is_valid = true;
-            sync_block.id = id;
-            sync_block.lock = RawMutex::INIT;
-        } else {
-            index = unsafe { G_SYNC_BLOCKS.len() };
-            unsafe {
-                G_SYNC_BLOCKS.push(SyncBlock {
-                    is_valid: true,
-                    id,
-                    lock: RawMutex::INIT,
-                })
-            };
-        }
-
-        self.fat = FatSyncBuilder::new().with_index(index).build();
+        self.fat = G_SYNC_BLOCKS.write().inflate(unsafe { self.thin.id() });
     }
 
     pub fn lock(&mut self) {
@@ -147,36 +170,107 @@ impl ThinSync {
 #[derive(Clone, Copy)]
 #[bitfield(u32)]
 pub struct FatSync {
-    #[bits(31)]
+    #[bits(20)]
     index: usize,
+    /// The generation of the slot when the block was put in it
+    #[bits(11)]
+    generation: u16,
     #[bits(default = false)]
     is_thin: bool,
 }
 
 impl FatSync {
-    #[allow(static_mut_refs)]
-    pub fn get_block(&self) -> Option<&SyncBlock> {
-        unsafe { G_SYNC_BLOCKS.get(self.index()).filter(|x| x.is_valid) }
-    }
+    const MAX_INDEX: usize = (1 << 20) - 1;
+    const MAX_GENERATION: u16 = (1 << 11) - 1;
 
-    #[allow(static_mut_refs)]
-    pub fn get_block_mut(&self) -> Option<&mut SyncBlock> {
-        unsafe { G_SYNC_BLOCKS.get_mut(self.index()).filter(|x| x.is_valid) }
+    /// [`None`] if the block has been destroyed
+    pub fn get_block(&self) -> Option<Arc<SyncBlock>> {
+        G_SYNC_BLOCKS.read().get(*self)
     }
 
     pub fn destroy(&self) {
-        let Some(block) = self.get_block_mut() else {
-            return; // It has been destroyed
+        G_SYNC_BLOCKS.write().destroy(*self);
+    }
+}
+
+struct SyncBlockSlot {
+    /// Bumped every time the block is destroyed,
+    /// so that stale fat syncs do not get the block of the next object using the slot
+    generation: u16,
+    block: Option<Arc<SyncBlock>>,
+}
+
+/// Slots are reused once their blocks are destroyed,
+/// the blocks themselves are kept alive by the ones still using them
+struct SyncBlockTable {
+    slots: Vec<SyncBlockSlot>,
+    free: Vec<usize>,
+}
+
+impl SyncBlockTable {
+    fn inflate(&mut self, id: ThreadId) -> FatSync {
+        let block = Some(Arc::new(SyncBlock::with_thread_id(id)));
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].block = block;
+                index
+            }
+            None => {
+                let index = self.slots.len();
+                assert!(
+                    index <= FatSync::MAX_INDEX,
+                    "Too many sync blocks are in use"
+                );
+                self.slots.push(SyncBlockSlot {
+                    generation: 0,
+                    block,
+                });
+                index
+            }
         };
-        block.is_valid = false;
+        FatSyncBuilder::new()
+            .with_index(index)
+            .with_generation(self.slots[index].generation)
+            .build()
+    }
+
+    fn slot(&self, sync: FatSync) -> Option<&SyncBlockSlot> {
+        self.slots
+            .get(sync.index())
+            .filter(|x| x.generation == sync.generation())
+    }
+
+    fn get(&self, sync: FatSync) -> Option<Arc<SyncBlock>> {
+        self.slot(sync)?.block.clone()
+    }
+
+    fn destroy(&mut self, sync: FatSync) {
+        if self.slot(sync).is_none_or(|x| x.block.is_none()) {
+            return; // It has been destroyed
+        }
+        let slot = &mut self.slots[sync.index()];
+        slot.block = None;
+        slot.generation = (slot.generation + 1) & FatSync::MAX_GENERATION;
+        self.free.push(sync.index());
     }
 }
 
 pub struct SyncBlock {
-    is_valid: bool,
     id: ThreadId,
 
     lock: RawMutex,
+
+    /// The thread entered the monitor, 0 if there is none
+    owner: AtomicU64,
+    /// How many times the owner entered the monitor
+    recursion: AtomicUsize,
+    wait_state: Mutex<MonitorWaitState>,
+    pulsed: Condvar,
+}
+
+struct MonitorWaitState {
+    waiting: usize,
+    pulses: usize,
 }
 
 impl Default for SyncBlock {
@@ -194,12 +288,121 @@ impl SyncBlock {
     #[inline]
     pub const fn with_thread_id(id: ThreadId) -> Self {
         Self {
-            is_valid: true,
             id,
             lock: RawMutex::INIT,
+            owner: AtomicU64::new(0),
+            recursion: AtomicUsize::new(0),
+            wait_state: Mutex::new(MonitorWaitState {
+                waiting: 0,
+                pulses: 0,
+            }),
+            pulsed: Condvar::new(),
+        }
+    }
+
+    #[inline]
+    fn current_thread() -> u64 {
+        std::thread::current_id().as_u64().get()
+    }
+
+    #[inline]
+    pub fn is_entered_by_current_thread(&self) -> bool {
+        self.owner.load(Ordering::Relaxed) == Self::current_thread()
+    }
+
+    fn set_entered(&self, recursion: usize) {
+        self.owner.store(Self::current_thread(), Ordering::Relaxed);
+        self.recursion.store(recursion, Ordering::Relaxed);
+    }
+
+    /// Enter the monitor, blocking until it is available
+    ///
+    /// Entering it again from the owner thread only increases the recursion count.
+    pub fn enter(&self) {
+        if self.is_entered_by_current_thread() {
+            self.recursion.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.lock.lock();
+        self.set_entered(1);
+    }
+
+    /// Same as [`Self::enter`] but returns false instead of blocking
+    pub fn try_enter(&self) -> bool {
+        if self.is_entered_by_current_thread() {
+            self.recursion.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        if !self.lock.try_lock() {
+            return false;
+        }
+        self.set_entered(1);
+        true
+    }
+
+    /// Returns false if the current thread has not entered the monitor
+    pub fn exit(&self) -> bool {
+        if !self.is_entered_by_current_thread() {
+            return false;
+        }
+        if self.recursion.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.owner.store(0, Ordering::Relaxed);
+            unsafe {
+                self.lock.unlock();
+            }
+        }
+        true
+    }
+
+    /// Release the monitor until it is pulsed, then enter it again with the same recursion count
+    ///
+    /// Returns false if the current thread has not entered the monitor
+    pub fn wait(&self) -> bool {
+        if !self.is_entered_by_current_thread() {
+            return false;
+        }
+        let recursion = self.recursion.load(Ordering::Relaxed);
+
+        let mut state = self.wait_state.lock();
+        state.waiting += 1;
+        // Release the monitor while holding the wait state,
+        // so a pulse cannot be missed between them
+        self.owner.store(0, Ordering::Relaxed);
+        unsafe {
+            self.lock.unlock();
+        }
+        while state.pulses == 0 {
+            self.pulsed.wait(&mut state);
+        }
+        state.pulses -= 1;
+        state.waiting -= 1;
+        drop(state);
+
+        self.lock.lock();
+        self.set_entered(recursion);
+        true
+    }
+
+    /// Wake up one waiting thread, or all of them if `all` is true
+    ///
+    /// Returns false if the current thread has not entered the monitor
+    pub fn pulse(&self, all: bool) -> bool {
+        if !self.is_entered_by_current_thread() {
+            return false;
+        }
+        let mut state = self.wait_state.lock();
+        if all {
+            state.pulses = state.waiting;
+            self.pulsed.notify_all();
+        } else if state.waiting > state.pulses {
+            state.pulses += 1;
+            self.pulsed.notify_one();
         }
+        true
     }
 }
 
-static G_SYNC_BLOCKS_LOCK: RwLock<()> = RwLock::new(());
-static mut G_SYNC_BLOCKS: Vec<SyncBlock> = Vec::new();
+static G_SYNC_BLOCKS: RwLock<SyncBlockTable> = RwLock::new(SyncBlockTable {
+    slots: Vec::new(),
+    free: Vec::new(),
+});
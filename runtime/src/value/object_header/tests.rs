@@ -0,0 +1,58 @@
+use std::{ptr::NonNull, sync::Arc};
+
+use crate::memory::ThreadSafeNonNull;
+
+use super::{ObjectHeader, SyncBlockTable};
+
+#[test]
+fn reusing_destroyed_slots() {
+    let mut table = SyncBlockTable {
+        slots: Vec::new(),
+        free: Vec::new(),
+    };
+    let id = std::thread::current_id();
+
+    let first = table.inflate(id);
+    let first_block = table.get(first).unwrap();
+    table.destroy(first);
+    assert!(table.get(first).is_none());
+
+    let second = table.inflate(id);
+    assert_eq!(table.slots.len(), 1);
+    assert_eq!(second.index(), first.index());
+    assert_ne!(second.generation(), first.generation());
+    assert!(table.get(first).is_none());
+    assert!(!Arc::ptr_eq(&first_block, &table.get(second).unwrap()));
+
+    // A stale sync cannot destroy the block now in its slot
+    table.destroy(first);
+    assert!(table.get(second).is_some());
+    assert!(table.free.is_empty());
+}
+
+#[test]
+fn inflating_concurrently() {
+    let header =
+        ThreadSafeNonNull::new(NonNull::from(Box::leak(Box::new(ObjectHeader::new(false)))));
+
+    let blocks = std::thread::scope(|s| {
+        let threads = (0..8)
+            .map(|_| {
+                s.spawn(move || {
+                    // Moved as a whole, as the pointer inside is not `Send`
+                    let header = header;
+                    unsafe { ObjectHeader::sync_block(header.0) }
+                })
+            })
+            .collect::<Vec<_>>();
+        threads
+            .into_iter()
+            .map(|x| x.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+    assert!(blocks.windows(2).all(|x| Arc::ptr_eq(&x[0], &x[1])));
+
+    let header = unsafe { Box::from_raw(header.0.as_ptr()) };
+    header.sync().destroy();
+    assert!(unsafe { header.sync().fat.get_block() }.is_none());
+}
@@ -17,6 +17,7 @@ mod call_stack;
 mod exception;
 mod gc;
 mod mem_record;
+mod tail_call;
 
 pub use call_stack::{CallStack, CallStackFrame, CommonCallStackFrame, NativeCallStackFrame};
 pub use exception::{ExceptionManager, ThrowHelper};
@@ -26,6 +27,7 @@ use global::{
 };
 use line_ending::LineEnding;
 pub use mem_record::MemoryRecord;
+pub use tail_call::TailCall;
 
 #[cfg(test)]
 mod tests;
@@ -44,6 +46,9 @@ pub struct CPU {
 
     mem_records: Vec<MemoryRecord>,
     exception_manager: ExceptionManager,
+
+    #[getset(skip)]
+    tail_call: Option<TailCall>,
 }
 
 impl CPU {
@@ -53,6 +58,7 @@ impl CPU {
             call_stack: CallStack::new(),
             mem_records: Vec::new(),
             exception_manager: ExceptionManager::new(),
+            tail_call: None,
         }))
     }
 }
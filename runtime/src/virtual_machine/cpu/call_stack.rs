@@ -1,4 +1,10 @@
-use std::{alloc::Layout, cell::RefCell, ptr::NonNull, rc::Rc};
+use std::{
+    alloc::Layout,
+    cell::{Cell, RefCell},
+    fmt::Write as _,
+    ptr::NonNull,
+    rc::Rc,
+};
 
 use enumflags2::BitFlags;
 use fastarena::Checkpoint;
@@ -13,7 +19,7 @@ use crate::{
         class::Class,
         get_traits::{GetAssemblyRef, GetNonGenericTypeHandleKind, GetTypeVars},
         interface::Interface,
-        method::{Method, MethodDisplayOptions},
+        method::{Method, MethodDisplayOptions, SequencePoint},
         r#struct::Struct,
         type_handle::{MethodGenericResolver, NonGenericTypeHandle, NonGenericTypeHandleKind},
     },
@@ -23,6 +29,8 @@ use crate::{
 
 pub struct CallStack {
     stack: Vec<CallStackFrame>,
+    /// The most frames the stack has held at once
+    max_depth: usize,
 
     allocator: Rc<RefCell<fastarena::Arena>>,
 }
@@ -31,10 +39,19 @@ impl CallStack {
     pub fn new() -> Self {
         Self {
             stack: Vec::new(),
+            max_depth: 0,
             allocator: Rc::new(RefCell::new(fastarena::Arena::new())),
         }
     }
 
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     pub fn pop(&mut self) {
         self.stack.pop();
     }
@@ -54,6 +71,7 @@ impl CallStack {
         self.stack.push(CallStackFrame::Common(
             CommonCallStackFrame::prepare_for_method(method, self.allocator.clone()),
         ));
+        self.max_depth = self.max_depth.max(self.stack.len());
     }
 
     pub fn push_native<T: GetNonGenericTypeHandleKind>(&mut self, method: &Method<T>) {
@@ -62,6 +80,7 @@ impl CallStack {
                 method,
                 self.allocator.clone(),
             )));
+        self.max_depth = self.max_depth.max(self.stack.len());
     }
 
     pub fn set_marker(&mut self, val: bool) {
@@ -90,11 +109,8 @@ impl CallStack {
         fn filter(frame: &&CallStackFrame) -> bool {
             !frame.should_hide_when_capturing()
         }
-        self.stack
-            .iter()
-            .rev()
-            .filter(filter)
-            .map(move |x| match x.method() {
+        self.stack.iter().rev().filter(filter).map(move |x| {
+            let mut s = match x.method() {
                 (m, NonGenericTypeHandleKind::Class) => unsafe {
                     m.cast::<Method<Class>>()
                         .as_ref()
@@ -113,7 +129,14 @@ impl CallStack {
                         .display(options)
                         .to_string()
                 },
-            })
+            };
+            if let CallStackFrame::Common(frame) = x
+                && let Some(point) = frame.sequence_point()
+            {
+                let _ = write!(s, " at {point}");
+            }
+            s
+        })
     }
 }
 
@@ -397,6 +420,9 @@ pub struct CommonCallStackFrame {
     register_ptr: NonNull<u8>,
     checkpoint: fastarena::Checkpoint,
     allocator: Rc<RefCell<fastarena::Arena>>,
+
+    /// Index of the instruction being executed
+    pc: Cell<usize>,
 }
 
 impl CommonCallStackFrame {
@@ -425,6 +451,8 @@ impl CommonCallStackFrame {
             register_ptr,
             checkpoint,
             allocator,
+
+            pc: Cell::new(0),
         }
     }
 
@@ -456,6 +484,31 @@ impl CommonCallStackFrame {
         Self::new(method, allocator, full_layout, infos)
     }
 
+    pub fn pc(&self) -> usize {
+        self.pc.get()
+    }
+    pub fn set_pc(&self, pc: usize) {
+        self.pc.set(pc);
+    }
+    /// Returns [`None`] if the method has no debug info
+    pub fn sequence_point(&self) -> Option<&SequencePoint> {
+        let debug_info = match self.kind {
+            NonGenericTypeHandleKind::Class => unsafe {
+                self.method.cast::<Method<Class>>().as_ref().debug_info()
+            },
+            NonGenericTypeHandleKind::Struct => unsafe {
+                self.method.cast::<Method<Struct>>().as_ref().debug_info()
+            },
+            NonGenericTypeHandleKind::Interface => unsafe {
+                self.method
+                    .cast::<Method<Interface>>()
+                    .as_ref()
+                    .debug_info()
+            },
+        };
+        debug_info?.sequence_point_at(self.pc())
+    }
+
     pub fn get<TRegisterAddr: IRegisterAddr>(&self, i: TRegisterAddr) -> Option<LocalVariable> {
         self.layouts
             .get(i.get_usize())
@@ -613,6 +666,12 @@ mod getset_cpu {
             self.current_call_frame_mut()
                 .and_then(|x| x.unwrap_common_mut().ok())
         }
+        pub fn call_stack_depth(&self) -> usize {
+            self.call_stack.depth()
+        }
+        pub fn max_call_stack_depth(&self) -> usize {
+            self.call_stack.max_depth()
+        }
         pub fn capture<'a>(&'a self) -> Vec<widestring::Utf16String> {
             self.call_stack.capture().map(ToOwned::to_owned).collect()
         }
@@ -88,6 +88,75 @@ impl ThrowHelper {
 
         true
     }
+    pub fn divide_by_zero(&mut self) -> bool {
+        let exception = match self.0.new_object(
+            self.0
+                .vm_ref()
+                .assembly_manager()
+                .get_core_type(CoreTypeId::System_DivideByZeroException)
+                .unwrap_class(),
+            &stdlib_header::MethodId!(DivideByZeroException::Constructor).into(),
+            &[],
+        ) {
+            None => return false,
+            Some(exception) => exception,
+        };
+        self.0.throw_exception(exception);
+
+        true
+    }
+    pub fn overflow(&mut self) -> bool {
+        let exception = match self.0.new_object(
+            self.0
+                .vm_ref()
+                .assembly_manager()
+                .get_core_type(CoreTypeId::System_OverflowException)
+                .unwrap_class(),
+            &stdlib_header::MethodId!(OverflowException::Constructor).into(),
+            &[],
+        ) {
+            None => return false,
+            Some(exception) => exception,
+        };
+        self.0.throw_exception(exception);
+
+        true
+    }
+    pub fn invalid_cast(&mut self) -> bool {
+        let exception = match self.0.new_object(
+            self.0
+                .vm_ref()
+                .assembly_manager()
+                .get_core_type(CoreTypeId::System_InvalidCastException)
+                .unwrap_class(),
+            &stdlib_header::MethodId!(InvalidCastException::Constructor).into(),
+            &[],
+        ) {
+            None => return false,
+            Some(exception) => exception,
+        };
+        self.0.throw_exception(exception);
+
+        true
+    }
+    pub fn synchronization_lock(&mut self) -> bool {
+        let exception = match self.0.new_object(
+            self.0
+                .vm_ref()
+                .assembly_manager()
+                .get_core_type(CoreTypeId::System_Threading_SynchronizationLockException)
+                .unwrap_class(),
+            &stdlib_header::System::Threading::SynchronizationLockException::MethodId::Constructor
+                .into(),
+            &[],
+        ) {
+            None => return false,
+            Some(exception) => exception,
+        };
+        self.0.throw_exception(exception);
+
+        true
+    }
     pub fn invalid_enum(&mut self, enum_name: &str, message: &str) -> bool {
         let mut enum_name = ManagedReference::new_string(&mut self.0, enum_name);
         let mut message = ManagedReference::new_string(&mut self.0, message);
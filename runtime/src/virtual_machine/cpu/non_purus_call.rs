@@ -34,6 +34,8 @@ fn non_purus_type_to_libffi_type(ty: &NonPurusCallType) -> libffi::middle::Type
         NonPurusCallType::I64 => LibffiType::i64(),
         NonPurusCallType::String => LibffiType::pointer(),
         NonPurusCallType::Object => LibffiType::pointer(),
+        NonPurusCallType::F32 => LibffiType::f32(),
+        NonPurusCallType::F64 => LibffiType::f64(),
         NonPurusCallType::Structure(types) => {
             LibffiType::structure(types.iter().map(non_purus_type_to_libffi_type))
         }
@@ -0,0 +1,113 @@
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    ffi::c_void,
+    ptr::NonNull,
+};
+
+use crate::{
+    type_system::{
+        class::Class,
+        method::{Method, MethodPointer},
+        r#struct::Struct,
+        type_handle::{NonGenericTypeHandle, NonGenericTypeHandleKind},
+    },
+    value::managed_reference::ManagedReference,
+};
+
+use super::CPU;
+
+/// A call requested by the tail call instructions
+///
+/// It is made after the frame of the caller has been popped,
+/// so `this` and the args are copied out of the registers of the caller.
+pub struct TailCall {
+    method: MethodPointer,
+    this: Option<ManagedReference<Class>>,
+    args: Vec<(NonNull<u8>, Layout)>,
+}
+
+impl TailCall {
+    /// The args copied so far are freed if one of them cannot be allocated
+    ///
+    /// # Safety
+    /// Every pointer in `args` must be valid for reads of its layout
+    pub unsafe fn new(
+        method: MethodPointer,
+        this: Option<ManagedReference<Class>>,
+        args: impl IntoIterator<Item = (NonNull<u8>, Layout)>,
+    ) -> Result<Self, AllocError> {
+        let mut tail_call = Self {
+            method,
+            this,
+            args: Vec::new(),
+        };
+        for (ptr, layout) in args {
+            let copied = if layout.size() == 0 {
+                NonNull::dangling()
+            } else {
+                Global.allocate(layout)?.as_non_null_ptr()
+            };
+            unsafe {
+                copied.copy_from_nonoverlapping(ptr, layout.size());
+            }
+            tail_call.args.push((copied, layout));
+        }
+        Ok(tail_call)
+    }
+
+    /// See [`MethodPointer::return_type`]
+    pub fn return_type(&self) -> Option<NonGenericTypeHandle> {
+        self.method.return_type()
+    }
+
+    /// # Safety
+    /// See [`Method::buffered_call`]
+    pub unsafe fn call(self, cpu: &mut CPU, return_buffer: NonNull<c_void>) {
+        let (method, kind) = self.method.get().unwrap();
+        let this = self.this.as_ref().map(|x| NonNull::from_ref(x).cast());
+        let args = self
+            .args
+            .iter()
+            .map(|(ptr, _)| ptr.as_ptr().cast::<c_void>())
+            .collect::<Vec<_>>();
+        macro call($T:ty) {
+            unsafe {
+                method.cast::<Method<$T>>().as_ref().buffered_call_once(
+                    cpu,
+                    this,
+                    &args,
+                    return_buffer,
+                )
+            }
+        }
+        match kind {
+            NonGenericTypeHandleKind::Class => call!(Class),
+            NonGenericTypeHandleKind::Struct => call!(Struct),
+            NonGenericTypeHandleKind::Interface => unreachable!(),
+        }
+    }
+}
+
+impl Drop for TailCall {
+    fn drop(&mut self) {
+        for (ptr, layout) in self.args.drain(..) {
+            if layout.size() != 0 {
+                unsafe {
+                    Global.deallocate(ptr, layout);
+                }
+            }
+        }
+    }
+}
+
+impl CPU {
+    /// Requests a call to be made after the current method returns
+    pub fn set_tail_call(&mut self, tail_call: TailCall) {
+        debug_assert!(self.tail_call.is_none());
+        self.tail_call = Some(tail_call);
+    }
+
+    pub fn take_tail_call(&mut self) -> Option<TailCall> {
+        self.tail_call.take()
+    }
+}